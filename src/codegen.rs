@@ -1,6 +1,7 @@
 pub mod asm_gen;
 pub mod binop;
 pub mod builtin_functions;
+pub mod float;
 pub mod functions;
 pub mod reg;
 pub mod register_allocator;
@@ -8,14 +9,21 @@ pub mod unop;
 
 use crate::{
     parser::global_strings::get_string_label,
-    tac::{tac_func::TacFunc, tac_instr::TacInstr, Identifier, TacVal},
+    tac::{
+        global_vars::get_global_var_label, tac_func::TacFunc, tac_instr::TacInstr, Identifier,
+        TacVal,
+    },
     types::VarSize,
 };
 
 use self::{
     binop::gen_binop_code,
-    functions::{gen_load_arg_code, generate_function_call_code},
-    reg::Reg,
+    float::{
+        gen_float_binop_code, gen_float_copy_code, gen_float_unop_code, gen_float_zero_test_code,
+        get_float_bits,
+    },
+    functions::{gen_load_arg_code, generate_function_call_code, get_arg_locations, CallTarget},
+    reg::{Reg, XmmReg},
     register_allocator::RegisterAllocator,
     unop::gen_unop_code,
 };
@@ -28,6 +36,12 @@ pub enum CCode {
     LE,
     G,
     GE,
+    B,  // below, the unsigned version of L
+    BE, // below or equal, the unsigned version of LE
+    A,  // above, the unsigned version of G
+    AE, // above or equal, the unsigned version of GE
+    P,  // parity, set by floating point comparisons when an operand is NaN
+    NP, // no parity
 }
 
 impl CCode {
@@ -39,6 +53,12 @@ impl CCode {
             CCode::LE => "le".to_owned(),
             CCode::G => "g".to_owned(),
             CCode::GE => "ge".to_owned(),
+            CCode::B => "b".to_owned(),
+            CCode::BE => "be".to_owned(),
+            CCode::A => "a".to_owned(),
+            CCode::AE => "ae".to_owned(),
+            CCode::P => "p".to_owned(),
+            CCode::NP => "np".to_owned(),
         }
     }
 }
@@ -74,16 +94,50 @@ pub enum X86Instr {
     IMul {
         dst: Reg,
         src: Reg,
+        size: VarSize,
     },
     SubImm {
         dst: Reg,
         imm: i64,
         size: VarSize,
     },
+    And {
+        dst: Reg,
+        src: Reg,
+        size: VarSize,
+    },
+    Or {
+        dst: Reg,
+        src: Reg,
+        size: VarSize,
+    },
+    Xor {
+        dst: Reg,
+        src: Reg,
+        size: VarSize,
+    },
+    Sal {
+        dst: Reg,
+        size: VarSize,
+    }, // shifts dst left by cl
+    Sar {
+        dst: Reg,
+        size: VarSize,
+    }, // arithmetic (sign preserving) right shift of dst by cl
+    Shr {
+        dst: Reg,
+        size: VarSize,
+    }, // logical right shift of dst by cl
     Cdq, // convert double to quad, sign extends eax into edx:eax
+    Cqo, // convert quad to octo, sign extends rax into rdx:rax
     Idiv {
         src: Reg,
-    }, // divides rax by src, quotient stored in rax
+        size: VarSize,
+    }, // divides rdx:rax by src, quotient stored in rax, remainder in rdx
+    Div {
+        src: Reg,
+        size: VarSize,
+    }, // unsigned version of Idiv
     Label {
         name: String,
     },
@@ -94,6 +148,14 @@ pub enum X86Instr {
         label: String,
         condition: CCode,
     },
+    JmpIndirect {
+        table: Reg,
+        index: Reg,
+    }, // jumps to the address at index `index` of the table of 8 byte addresses pointed to by `table`
+    JmpTableData {
+        name: String,
+        labels: Vec<String>,
+    }, // a table of label addresses, placed in the read only data section
     SetCC {
         dst: Reg,
         condition: CCode,
@@ -118,16 +180,83 @@ pub enum X86Instr {
     Call {
         name: String,
     },
+    CallIndirect {
+        reg: Reg,
+    },
     SignExtend {
         reg: Reg,
         size: VarSize,
     },
+    ZeroExtend {
+        reg: Reg,
+        size: VarSize,
+    },
     Ret,
     StartLabel,
     MovStaticLabel {
         reg: Reg,
         label_name: String,
     },
+    MovToXmm {
+        dst: XmmReg,
+        src: Reg,
+        size: VarSize,
+    }, // moves the bits of a float (dword) or double (quad) from a general purpose register
+    MovFromXmm {
+        dst: Reg,
+        src: XmmReg,
+        size: VarSize,
+    },
+    FAdd {
+        dst: XmmReg,
+        src: XmmReg,
+        size: VarSize,
+    },
+    FSub {
+        dst: XmmReg,
+        src: XmmReg,
+        size: VarSize,
+    },
+    FMul {
+        dst: XmmReg,
+        src: XmmReg,
+        size: VarSize,
+    },
+    FDiv {
+        dst: XmmReg,
+        src: XmmReg,
+        size: VarSize,
+    },
+    FCmp {
+        left: XmmReg,
+        right: XmmReg,
+        size: VarSize,
+    }, // unordered comparison, sets ZF, PF and CF like an unsigned comparison (all three are set for NaN)
+    CvtIntToFloat {
+        dst: XmmReg,
+        src: Reg,
+        size: VarSize,
+    }, // converts the signed 64 bit integer in src to a float (dword) or double (quad)
+    CvtUnsignedToFloat {
+        dst: XmmReg,
+        src: Reg,
+        size: VarSize,
+    }, // like CvtIntToFloat, but for an unsigned 64 bit integer. Overwrites rax and rdx
+    CvtFloatToInt {
+        dst: Reg,
+        src: XmmReg,
+        size: VarSize,
+    }, // truncates the float (dword) or double (quad) in src to a signed 64 bit integer
+    CvtFloatToUnsigned {
+        dst: Reg,
+        src: XmmReg,
+        size: VarSize,
+    }, // like CvtFloatToInt, but to an unsigned 64 bit integer. Overwrites rax, rdx, src and xmm15
+    CvtFloatToFloat {
+        dst: XmmReg,
+        src: XmmReg,
+        size: VarSize,
+    }, // converts between float and double, size is the size of dst
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -170,22 +299,39 @@ fn generate_function_x86(result: &mut Vec<X86Instr>, function: &TacFunc) {
         imm: num_bytes_needed as i64,
         size: VarSize::Quad,
     });
+    for (reg, offset) in reg_alloc.get_saved_regs() {
+        result.push(X86Instr::Mov {
+            dst: Location::Mem(*offset),
+            src: Location::Reg(*reg),
+            size: VarSize::Quad,
+        });
+    }
 
-    for (index, (arg_ident, _)) in function.args.iter().enumerate() {
-        gen_load_arg_code(result, arg_ident, index, &reg_alloc);
+    let arg_locations = get_arg_locations(function.args.iter().map(|(ident, _)| ident.is_float()));
+    for ((arg_ident, _), location) in function.args.iter().zip(arg_locations) {
+        gen_load_arg_code(result, arg_ident, location, &reg_alloc);
     }
 
-    for instr in &function.body {
+    for instr in function.instrs() {
         gen_x86_for_tac(result, instr, &reg_alloc);
     }
 }
 
 fn gen_x86_for_tac(result: &mut Vec<X86Instr>, instr: &TacInstr, reg_alloc: &RegisterAllocator) {
     match instr {
+        TacInstr::BinOp(dst_ident, val1, val2, op) if val1.is_float() || val2.is_float() => {
+            gen_float_binop_code(result, dst_ident, val1, val2, *op, reg_alloc);
+        }
         TacInstr::BinOp(dst_ident, val1, val2, op) => {
             gen_binop_code(result, dst_ident, val1, val2, *op, reg_alloc);
         }
+        TacInstr::UnOp(dst_ident, val, op) if val.is_float() => {
+            gen_float_unop_code(result, dst_ident, val, *op, reg_alloc)
+        }
         TacInstr::UnOp(dst_ident, val, op) => gen_unop_code(result, dst_ident, val, *op, reg_alloc),
+        TacInstr::Copy(dst_ident, src_val) if src_val.is_float() || dst_ident.is_float() => {
+            gen_float_copy_code(result, dst_ident, src_val, reg_alloc);
+        }
         TacInstr::Copy(dst_ident, src_val) => {
             gen_load_val_code(result, src_val, Reg::Rdi, reg_alloc);
             result.push(X86Instr::Mov {
@@ -202,10 +348,14 @@ fn gen_x86_for_tac(result: &mut Vec<X86Instr>, instr: &TacInstr, reg_alloc: &Reg
         }),
         TacInstr::JmpZero(label_name, val) => {
             gen_load_val_code(result, val, Reg::Rdi, reg_alloc);
-            result.push(X86Instr::Test {
-                src: Reg::Rdi,
-                size: val.get_size(),
-            });
+            if val.is_float() {
+                gen_float_zero_test_code(result, Reg::Rdi, val.get_size());
+            } else {
+                result.push(X86Instr::Test {
+                    src: Reg::Rdi,
+                    size: val.get_size(),
+                });
+            }
             result.push(X86Instr::JmpCC {
                 label: label_name.clone(),
                 condition: CCode::E,
@@ -213,21 +363,66 @@ fn gen_x86_for_tac(result: &mut Vec<X86Instr>, instr: &TacInstr, reg_alloc: &Reg
         }
         TacInstr::JmpNotZero(label_name, val) => {
             gen_load_val_code(result, val, Reg::Rdi, reg_alloc);
-            result.push(X86Instr::Test {
-                src: Reg::Rdi,
-                size: val.get_size(),
-            });
+            if val.is_float() {
+                gen_float_zero_test_code(result, Reg::Rdi, val.get_size());
+            } else {
+                result.push(X86Instr::Test {
+                    src: Reg::Rdi,
+                    size: val.get_size(),
+                });
+            }
             result.push(X86Instr::JmpCC {
                 label: label_name.clone(),
                 condition: CCode::NE,
             })
         }
-        TacInstr::Call(function_name, args, optional_ident) => {
-            generate_function_call_code(result, function_name, args, *optional_ident, reg_alloc)
+        TacInstr::JmpTable(table_name, val, labels) => {
+            gen_load_val_code(result, val, Reg::Rdi, reg_alloc);
+            result.push(X86Instr::MovStaticLabel {
+                reg: Reg::Rsi,
+                label_name: format!(".{}", table_name),
+            });
+            result.push(X86Instr::JmpIndirect {
+                table: Reg::Rsi,
+                index: Reg::Rdi,
+            });
+            result.push(X86Instr::JmpTableData {
+                name: table_name.clone(),
+                labels: labels.clone(),
+            });
         }
+        TacInstr::Call(function_name, args, optional_ident) => generate_function_call_code(
+            result,
+            CallTarget::Direct(function_name),
+            args,
+            *optional_ident,
+            reg_alloc,
+        ),
+        TacInstr::CallIndirect(callee, args, optional_ident) => generate_function_call_code(
+            result,
+            CallTarget::Indirect(callee),
+            args,
+            *optional_ident,
+            reg_alloc,
+        ),
         TacInstr::Return(val) => {
             gen_load_val_code(result, val, Reg::Rax, reg_alloc);
+            if val.is_float() {
+                // floating point values are returned in xmm0
+                result.push(X86Instr::MovToXmm {
+                    dst: XmmReg(0),
+                    src: Reg::Rax,
+                    size: val.get_size(),
+                });
+            }
             // FUNCTION EPILOGUE: generate this before each return statement in function
+            for (reg, offset) in reg_alloc.get_saved_regs() {
+                result.push(X86Instr::Mov {
+                    dst: Location::Reg(*reg),
+                    src: Location::Mem(*offset),
+                    size: VarSize::Quad,
+                });
+            }
             result.push(X86Instr::Mov {
                 dst: Location::Reg(Reg::Rsp),
                 src: Location::Reg(Reg::Rbp),
@@ -257,6 +452,31 @@ fn gen_x86_for_tac(result: &mut Vec<X86Instr>, instr: &TacInstr, reg_alloc: &Reg
                 size: dst_ident.get_size(),
             });
         }
+        TacInstr::FunctionPtr(dst_ident, function_name) => {
+            assert_eq!(dst_ident.get_size(), VarSize::Quad);
+            result.push(X86Instr::MovStaticLabel {
+                reg: Reg::Rdi,
+                label_name: format!(".{}", function_name),
+            });
+            result.push(X86Instr::Mov {
+                dst: reg_alloc.get_location(*dst_ident),
+                src: Location::Reg(Reg::Rdi),
+                size: dst_ident.get_size(),
+            });
+        }
+        TacInstr::Phi(_, _) => unreachable!("phi instructions are removed when leaving SSA form"),
+        TacInstr::GlobalVarPtr(dst_ident, var_name) => {
+            assert_eq!(dst_ident.get_size(), VarSize::Quad);
+            result.push(X86Instr::MovStaticLabel {
+                reg: Reg::Rdi,
+                label_name: get_global_var_label(var_name),
+            });
+            result.push(X86Instr::Mov {
+                dst: reg_alloc.get_location(*dst_ident),
+                src: Location::Reg(Reg::Rdi),
+                size: dst_ident.get_size(),
+            });
+        }
     }
 }
 
@@ -272,6 +492,11 @@ fn gen_load_val_code(
             imm: *imm,
             size: *size,
         }),
+        TacVal::FloatLit(value, size) => result.push(X86Instr::MovImm {
+            dst: Location::Reg(reg),
+            imm: get_float_bits(*value, *size),
+            size: *size,
+        }),
         TacVal::Var(var_ident) => {
             let loc = reg_alloc.get_location(*var_ident);
             result.push(X86Instr::Mov {
@@ -279,16 +504,48 @@ fn gen_load_val_code(
                 src: loc,
                 size: val.get_size(),
             });
-            if val.get_size() != VarSize::Quad {
-                result.push(X86Instr::SignExtend {
-                    reg,
-                    size: val.get_size(),
-                });
+            // floating point values are moved as raw bits, without extending them
+            if val.get_size() != VarSize::Quad && !var_ident.is_float() {
+                if var_ident.is_unsigned() {
+                    result.push(X86Instr::ZeroExtend {
+                        reg,
+                        size: val.get_size(),
+                    });
+                } else {
+                    result.push(X86Instr::SignExtend {
+                        reg,
+                        size: val.get_size(),
+                    });
+                }
             }
         }
     }
 }
 
+/// sign or zero extends the result of an operation to the size of its destination, if the destination is bigger
+pub fn gen_extend_result_code(
+    result: &mut Vec<X86Instr>,
+    reg: Reg,
+    operation_size: VarSize,
+    dst_size: VarSize,
+    is_unsigned: bool,
+) {
+    if operation_size.num_bytes() >= dst_size.num_bytes() {
+        return;
+    }
+    if is_unsigned {
+        result.push(X86Instr::ZeroExtend {
+            reg,
+            size: operation_size,
+        });
+    } else {
+        result.push(X86Instr::SignExtend {
+            reg,
+            size: operation_size,
+        });
+    }
+}
+
 fn generate_mem_chunk_init_code(
     result: &mut Vec<X86Instr>,
     ident: Identifier,
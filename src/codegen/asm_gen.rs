@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 
-use crate::{parser::global_strings::generate_global_strings_asm, types::VarSize};
+use crate::{
+    parser::global_strings::generate_global_strings_asm,
+    tac::global_vars::generate_global_vars_asm, types::VarSize,
+};
 
-use super::{builtin_functions::BUILTIN_FUNCTIONS, Location, X86Instr};
+use super::{builtin_functions::BUILTIN_FUNCTIONS, reg::Reg, Location, X86Instr};
 
 fn convert_location_to_asm(location: &Location, size: VarSize) -> String {
     match location {
@@ -61,23 +64,62 @@ fn convert_to_asm(instr: &X86Instr) -> String {
                 src.get_sized_name(*size)
             )
         }
-        X86Instr::IMul { dst, src } => {
+        X86Instr::IMul { dst, src, size } => {
             format!(
                 "imul {}, {}",
-                dst.get_default_name(),
-                src.get_default_name()
+                dst.get_sized_name(*size),
+                src.get_sized_name(*size)
             )
         }
         X86Instr::SubImm { dst, imm, size } => {
             format!("sub {}, {}", dst.get_sized_name(*size), imm)
         }
+        X86Instr::And { dst, src, size } => {
+            format!(
+                "and {}, {}",
+                dst.get_sized_name(*size),
+                src.get_sized_name(*size)
+            )
+        }
+        X86Instr::Or { dst, src, size } => {
+            format!(
+                "or {}, {}",
+                dst.get_sized_name(*size),
+                src.get_sized_name(*size)
+            )
+        }
+        X86Instr::Xor { dst, src, size } => {
+            format!(
+                "xor {}, {}",
+                dst.get_sized_name(*size),
+                src.get_sized_name(*size)
+            )
+        }
+        X86Instr::Sal { dst, size } => format!("sal {}, cl", dst.get_sized_name(*size)),
+        X86Instr::Sar { dst, size } => format!("sar {}, cl", dst.get_sized_name(*size)),
+        X86Instr::Shr { dst, size } => format!("shr {}, cl", dst.get_sized_name(*size)),
         X86Instr::Cdq => "cdq".to_owned(),
-        X86Instr::Idiv { src } => format!("idiv {}", src.get_default_name()),
+        X86Instr::Cqo => "cqo".to_owned(),
+        X86Instr::Idiv { src, size } => format!("idiv {}", src.get_sized_name(*size)),
+        X86Instr::Div { src, size } => format!("div {}", src.get_sized_name(*size)),
         X86Instr::Label { name } => format!(".{}:", name),
         X86Instr::Jmp { label } => format!("jmp .{}", label),
         X86Instr::JmpCC { label, condition } => {
             format!("j{} .{}", condition.to_suffix(), label)
         }
+        X86Instr::JmpIndirect { table, index } => format!(
+            "jmp qword [{} + {} * 8]",
+            table.get_64_bit_name(),
+            index.get_64_bit_name()
+        ),
+        X86Instr::JmpTableData { name, labels } => {
+            let labels: Vec<String> = labels.iter().map(|label| format!(".{}", label)).collect();
+            format!(
+                "section .rodata\nalign 8\n.{}: dq {}\nsection .text",
+                name,
+                labels.join(", ")
+            )
+        }
         X86Instr::SetCC { dst, condition } => {
             format!("set{} {}", condition.to_suffix(), dst.get_8_bit_name(),)
         }
@@ -95,16 +137,158 @@ fn convert_to_asm(instr: &X86Instr) -> String {
         X86Instr::Not { dst, size } => format!("not {}", dst.get_sized_name(*size),),
         X86Instr::Neg { dst, size } => format!("neg {}", dst.get_sized_name(*size),),
         X86Instr::Call { name } => format!("call .{}", name),
+        X86Instr::CallIndirect { reg } => format!("call {}", reg.get_64_bit_name()),
         X86Instr::SignExtend { reg, size } => format!(
             "movsx {}, {}",
             reg.get_64_bit_name(),
             reg.get_sized_name(*size)
         ),
+        X86Instr::ZeroExtend { reg, size } => match size {
+            // writing to a 32 bit register clears the upper 32 bits of the 64 bit register
+            VarSize::Dword => format!("mov {}, {}", reg.get_32_bit_name(), reg.get_32_bit_name()),
+            _ => format!(
+                "movzx {}, {}",
+                reg.get_64_bit_name(),
+                reg.get_sized_name(*size)
+            ),
+        },
         X86Instr::Ret => "ret".to_owned(),
         X86Instr::StartLabel => "_start:".to_owned(),
         X86Instr::MovStaticLabel { reg, label_name } => {
             format!("mov {}, {}", reg.get_64_bit_name(), label_name)
         }
+        X86Instr::MovToXmm { dst, src, size } => format!(
+            "{} {}, {}",
+            get_movd_or_movq(*size),
+            dst.get_name(),
+            src.get_sized_name(*size)
+        ),
+        X86Instr::MovFromXmm { dst, src, size } => format!(
+            "{} {}, {}",
+            get_movd_or_movq(*size),
+            dst.get_sized_name(*size),
+            src.get_name()
+        ),
+        X86Instr::FAdd { dst, src, size } => {
+            format!(
+                "add{} {}, {}",
+                get_sse_suffix(*size),
+                dst.get_name(),
+                src.get_name()
+            )
+        }
+        X86Instr::FSub { dst, src, size } => {
+            format!(
+                "sub{} {}, {}",
+                get_sse_suffix(*size),
+                dst.get_name(),
+                src.get_name()
+            )
+        }
+        X86Instr::FMul { dst, src, size } => {
+            format!(
+                "mul{} {}, {}",
+                get_sse_suffix(*size),
+                dst.get_name(),
+                src.get_name()
+            )
+        }
+        X86Instr::FDiv { dst, src, size } => {
+            format!(
+                "div{} {}, {}",
+                get_sse_suffix(*size),
+                dst.get_name(),
+                src.get_name()
+            )
+        }
+        X86Instr::FCmp { left, right, size } => format!(
+            "ucomi{} {}, {}",
+            get_sse_suffix(*size),
+            left.get_name(),
+            right.get_name()
+        ),
+        X86Instr::CvtIntToFloat { dst, src, size } => format!(
+            "cvtsi2{} {}, {}",
+            get_sse_suffix(*size),
+            dst.get_name(),
+            src.get_64_bit_name()
+        ),
+        X86Instr::CvtUnsignedToFloat { dst, src, size } => {
+            let (suffix, dst, src) = (get_sse_suffix(*size), dst.get_name(), src.get_64_bit_name());
+            let mov = get_movd_or_movq(*size);
+            let (rax, rdx) = (
+                Reg::Rax.get_sized_name(*size),
+                Reg::Rdx.get_sized_name(*size),
+            );
+            [
+                // values of 2^63 and above are halved (keeping the lowest bit for rounding),
+                // converted as a signed integer, and doubled
+                format!("mov rax, {}", src),
+                "shr rax, 1".to_owned(),
+                format!("mov rdx, {}", src),
+                "and rdx, 1".to_owned(),
+                "or rax, rdx".to_owned(),
+                format!("cvtsi2{} {}, rax", suffix, dst),
+                format!("add{} {}, {}", suffix, dst, dst),
+                format!("{} {}, {}", mov, rax, dst),
+                // smaller values are converted directly, and the result is chosen based on the sign bit
+                format!("cvtsi2{} {}, {}", suffix, dst, src),
+                format!("{} {}, {}", mov, rdx, dst),
+                format!("test {}, {}", src, src),
+                "cmovs rdx, rax".to_owned(),
+                format!("{} {}, {}", mov, dst, rdx),
+            ]
+            .join("\n  ")
+        }
+        X86Instr::CvtFloatToInt { dst, src, size } => format!(
+            "cvtt{}2si {}, {}",
+            get_sse_suffix(*size),
+            dst.get_64_bit_name(),
+            src.get_name()
+        ),
+        X86Instr::CvtFloatToUnsigned { dst, src, size } => {
+            let (suffix, dst, src) = (get_sse_suffix(*size), dst.get_64_bit_name(), src.get_name());
+            let two_to_the_63 = match size {
+                VarSize::Dword => (2f32.powi(63)).to_bits() as i64,
+                _ => (2f64.powi(63)).to_bits() as i64,
+            };
+            [
+                // values below 2^63 are converted directly, larger values have 2^63 subtracted first,
+                // and the top bit is set again afterwards
+                format!("cvtt{}2si {}, {}", suffix, dst, src),
+                format!("mov rax, {}", two_to_the_63),
+                format!(
+                    "{} xmm15, {}",
+                    get_movd_or_movq(*size),
+                    Reg::Rax.get_sized_name(*size)
+                ),
+                format!("sub{} {}, xmm15", suffix, src),
+                format!("cvtt{}2si rdx, {}", suffix, src),
+                "btc rdx, 63".to_owned(),
+                format!("test {}, {}", dst, dst),
+                format!("cmovs {}, rdx", dst),
+            ]
+            .join("\n  ")
+        }
+        X86Instr::CvtFloatToFloat { dst, src, size } => match size {
+            VarSize::Dword => format!("cvtsd2ss {}, {}", dst.get_name(), src.get_name()),
+            _ => format!("cvtss2sd {}, {}", dst.get_name(), src.get_name()),
+        },
+    }
+}
+
+/// the suffix of scalar SSE instructions, `ss` for floats and `sd` for doubles
+fn get_sse_suffix(size: VarSize) -> &'static str {
+    match size {
+        VarSize::Dword => "ss",
+        _ => "sd",
+    }
+}
+
+fn get_movd_or_movq(size: VarSize) -> &'static str {
+    match size {
+        VarSize::Dword => "movd",
+        _ => "movq",
     }
 }
 
@@ -113,6 +297,7 @@ pub fn generate_program_asm(instrs: &Vec<X86Instr>) -> String {
     let mut result = String::new();
 
     result.push_str(&generate_global_strings_asm());
+    result.push_str(&generate_global_vars_asm());
 
     let indent = "  ";
 
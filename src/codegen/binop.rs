@@ -4,7 +4,9 @@ use crate::{
     types::VarSize,
 };
 
-use super::{gen_load_val_code, CCode, Location, Reg, RegisterAllocator, X86Instr};
+use super::{
+    gen_extend_result_code, gen_load_val_code, CCode, Location, Reg, RegisterAllocator, X86Instr,
+};
 
 pub fn gen_binop_code(
     result: &mut Vec<X86Instr>,
@@ -23,67 +25,112 @@ pub fn gen_binop_code(
     let bigger_operand_size =
         get_bigger_size(Some(val1.get_size()), Some(val2.get_size())).unwrap();
 
+    // the operands of arithmetic and comparisons have already been converted to a common type,
+    // so if either operand is unsigned, the operation is unsigned.
+    let is_unsigned = val1.is_unsigned() || val2.is_unsigned();
+
+    // 8 and 16 bit operands have been promoted, so operations are either done at 32 or 64 bits
+    let operation_size = match bigger_operand_size {
+        VarSize::Quad => VarSize::Quad,
+        _ => VarSize::Dword,
+    };
+
     match op {
         BinOp::Multiply => result.push(X86Instr::IMul {
             dst: val1_reg,
             src: val2_reg,
+            size: operation_size,
         }),
-        BinOp::Divide => {
-            result.push(X86Instr::Mov {
-                dst: Location::Reg(Reg::Rax),
-                src: Location::Reg(val1_reg),
-                size: val1.get_size(),
-            }); // move dividend into eax
-            result.push(X86Instr::Cdq); // converts the 32 bit quantity in eax to a sign-extended 64 bit quantity.
-            result.push(X86Instr::Idiv { src: val2_reg });
-            // after idiv instruction, result is stored in eax,
-            // so it needs to be sign extended to rax.
-            result.push(X86Instr::SignExtend {
-                reg: Reg::Rax,
-                size: VarSize::Dword,
-            });
+        BinOp::Divide | BinOp::Modulus => {
+            gen_division_code(result, val1_reg, val2_reg, operation_size, is_unsigned);
+            // after the division, the quotient is stored in rax and the remainder in rdx
+            let result_reg = if op == BinOp::Divide {
+                Reg::Rax
+            } else {
+                Reg::Rdx
+            };
             result.push(X86Instr::Mov {
                 dst: Location::Reg(val1_reg),
-                src: Location::Reg(Reg::Rax),
-                size: dst_ident.get_size(),
+                src: Location::Reg(result_reg),
+                size: VarSize::Quad,
             }); // move result into val1_reg
         }
-        BinOp::Modulus => {
-            result.push(X86Instr::Mov {
-                dst: Location::Reg(Reg::Rax),
-                src: Location::Reg(val1_reg),
-                size: val1.get_size(),
-            }); // move dividend into eax
-            result.push(X86Instr::Cdq); // converts the 32 bit quantity in eax to a sign-extended 64 bit quantity.
-            result.push(X86Instr::Idiv { src: val2_reg });
+        BinOp::BitwiseAnd => result.push(X86Instr::And {
+            dst: val1_reg,
+            src: val2_reg,
+            size: operation_size,
+        }),
+        BinOp::BitwiseOr => result.push(X86Instr::Or {
+            dst: val1_reg,
+            src: val2_reg,
+            size: operation_size,
+        }),
+        BinOp::BitwiseXor => result.push(X86Instr::Xor {
+            dst: val1_reg,
+            src: val2_reg,
+            size: operation_size,
+        }),
+        BinOp::ShiftLeft | BinOp::ShiftRight => {
+            // the shift count must be in cl
             result.push(X86Instr::Mov {
-                dst: Location::Reg(val1_reg),
-                src: Location::Reg(Reg::Rdx),
-                size: dst_ident.get_size(),
-            }); // move result into val1_reg
+                dst: Location::Reg(Reg::Rcx),
+                src: Location::Reg(val2_reg),
+                size: VarSize::Quad,
+            });
+            let size = get_shift_size(val1);
+            result.push(match op {
+                BinOp::ShiftLeft => X86Instr::Sal {
+                    dst: val1_reg,
+                    size,
+                },
+                _ if val1.is_unsigned() => X86Instr::Shr {
+                    dst: val1_reg,
+                    size,
+                },
+                _ => X86Instr::Sar {
+                    dst: val1_reg,
+                    size,
+                },
+            });
         }
         BinOp::Plus => result.push(X86Instr::Add {
             dst: val1_reg,
             src: val2_reg,
-            size: dst_ident.get_size(),
+            size: operation_size,
         }),
         BinOp::Minus => result.push(X86Instr::Sub {
             dst: val1_reg,
             src: val2_reg,
-            size: dst_ident.get_size(),
+            size: operation_size,
         }),
-        BinOp::GreaterThan => {
-            generate_cmp_code(result, val1_reg, val2_reg, CCode::G, bigger_operand_size)
-        }
-        BinOp::GreaterThanEq => {
-            generate_cmp_code(result, val1_reg, val2_reg, CCode::GE, bigger_operand_size)
-        }
-        BinOp::LessThan => {
-            generate_cmp_code(result, val1_reg, val2_reg, CCode::L, bigger_operand_size)
-        }
-        BinOp::LessThanEq => {
-            generate_cmp_code(result, val1_reg, val2_reg, CCode::LE, bigger_operand_size)
-        }
+        BinOp::GreaterThan => generate_cmp_code(
+            result,
+            val1_reg,
+            val2_reg,
+            if is_unsigned { CCode::A } else { CCode::G },
+            bigger_operand_size,
+        ),
+        BinOp::GreaterThanEq => generate_cmp_code(
+            result,
+            val1_reg,
+            val2_reg,
+            if is_unsigned { CCode::AE } else { CCode::GE },
+            bigger_operand_size,
+        ),
+        BinOp::LessThan => generate_cmp_code(
+            result,
+            val1_reg,
+            val2_reg,
+            if is_unsigned { CCode::B } else { CCode::L },
+            bigger_operand_size,
+        ),
+        BinOp::LessThanEq => generate_cmp_code(
+            result,
+            val1_reg,
+            val2_reg,
+            if is_unsigned { CCode::BE } else { CCode::LE },
+            bigger_operand_size,
+        ),
         BinOp::Equals => {
             generate_cmp_code(result, val1_reg, val2_reg, CCode::E, bigger_operand_size)
         }
@@ -92,6 +139,34 @@ pub fn gen_binop_code(
         }
         BinOp::LogicalAnd | BinOp::LogicalOr => unreachable!(), // unreachable because short-circuiting code was generated in conversion to Tac phase.
         BinOp::Assign => unreachable!(), // unreachable because we will never generate TAC with the assign operator
+        BinOp::Comma => unreachable!(), // the operands of a comma are generated one after another in conversion to Tac phase.
+    }
+
+    // an operation on 32 bits leaves the upper half of the register zeroed, so its result is extended
+    // when it is stored straight into a bigger destination, such as an int quotient assigned to a long
+    match op {
+        BinOp::Plus
+        | BinOp::Minus
+        | BinOp::Multiply
+        | BinOp::Divide
+        | BinOp::Modulus
+        | BinOp::BitwiseAnd
+        | BinOp::BitwiseOr
+        | BinOp::BitwiseXor => gen_extend_result_code(
+            result,
+            val1_reg,
+            operation_size,
+            dst_ident.get_size(),
+            is_unsigned,
+        ),
+        BinOp::ShiftLeft | BinOp::ShiftRight => gen_extend_result_code(
+            result,
+            val1_reg,
+            get_shift_size(val1),
+            dst_ident.get_size(),
+            val1.is_unsigned(),
+        ),
+        _ => {}
     }
 
     result.push(X86Instr::Mov {
@@ -101,6 +176,14 @@ pub fn gen_binop_code(
     });
 }
 
+/// only the left operand determines the type of a shift, which is done on 32 or 64 bits like other operations
+fn get_shift_size(val1: &TacVal) -> VarSize {
+    match val1.get_size() {
+        VarSize::Quad => VarSize::Quad,
+        _ => VarSize::Dword,
+    }
+}
+
 fn generate_cmp_code(result: &mut Vec<X86Instr>, reg1: Reg, reg2: Reg, cc: CCode, size: VarSize) {
     // makes comparison between reg1 and reg2, stores result in reg1.
     result.push(X86Instr::Cmp {
@@ -118,3 +201,41 @@ fn generate_cmp_code(result: &mut Vec<X86Instr>, reg1: Reg, reg2: Reg, cc: CCode
         condition: cc,
     });
 }
+
+fn gen_division_code(
+    result: &mut Vec<X86Instr>,
+    dividend_reg: Reg,
+    divisor_reg: Reg,
+    size: VarSize,
+    is_unsigned: bool,
+) {
+    result.push(X86Instr::Mov {
+        dst: Location::Reg(Reg::Rax),
+        src: Location::Reg(dividend_reg),
+        size: VarSize::Quad,
+    }); // move dividend into rax
+
+    if is_unsigned {
+        // the upper half of the dividend (in rdx) is zero for unsigned division
+        result.push(X86Instr::MovImm {
+            dst: Location::Reg(Reg::Rdx),
+            imm: 0,
+            size: VarSize::Quad,
+        });
+        result.push(X86Instr::Div {
+            src: divisor_reg,
+            size,
+        });
+    } else {
+        // sign extends the dividend into rdx (or edx, for 32 bit division)
+        if size == VarSize::Quad {
+            result.push(X86Instr::Cqo);
+        } else {
+            result.push(X86Instr::Cdq);
+        }
+        result.push(X86Instr::Idiv {
+            src: divisor_reg,
+            size,
+        });
+    }
+}
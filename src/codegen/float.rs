@@ -0,0 +1,311 @@
+use crate::{
+    parser::expr_parser::{BinOp, UnOp},
+    tac::{Identifier, TacVal},
+    types::VarSize,
+};
+
+use super::{gen_load_val_code, reg::XmmReg, CCode, Location, Reg, RegisterAllocator, X86Instr};
+
+/// returns the bits of a float (for dwords) or a double (for quads)
+pub fn get_float_bits(value: f64, size: VarSize) -> i64 {
+    match size {
+        VarSize::Dword => (value as f32).to_bits() as i32 as i64,
+        VarSize::Quad => value.to_bits() as i64,
+        VarSize::Byte | VarSize::Word => unreachable!(), // floating point values are 4 or 8 bytes
+    }
+}
+
+/// loads a floating point value of the given size into an xmm register.
+/// Integer literals are converted to floating point at compile time.
+fn gen_load_float_val_code(
+    result: &mut Vec<X86Instr>,
+    val: &TacVal,
+    xmm_reg: XmmReg,
+    size: VarSize,
+    reg_alloc: &RegisterAllocator,
+) {
+    let val = match val {
+        TacVal::Lit(value, _) => TacVal::FloatLit(*value as f64, size),
+        _ => val.clone(),
+    };
+    gen_load_val_code(result, &val, Reg::Rax, reg_alloc);
+    result.push(X86Instr::MovToXmm {
+        dst: xmm_reg,
+        src: Reg::Rax,
+        size,
+    });
+}
+
+pub fn gen_float_binop_code(
+    result: &mut Vec<X86Instr>,
+    dst_ident: &Identifier,
+    val1: &TacVal,
+    val2: &TacVal,
+    op: BinOp,
+    reg_alloc: &RegisterAllocator,
+) {
+    // the operands have already been converted to a common floating point type,
+    // except for integer literals (such as the 1 of `x++`)
+    let size = if val1.is_float() {
+        val1.get_size()
+    } else {
+        val2.get_size()
+    };
+    let (val1_reg, val2_reg) = (XmmReg(0), XmmReg(1));
+
+    gen_load_float_val_code(result, val1, val1_reg, size, reg_alloc);
+    gen_load_float_val_code(result, val2, val2_reg, size, reg_alloc);
+
+    let (dst, src) = (val1_reg, val2_reg);
+    match op {
+        BinOp::Plus => result.push(X86Instr::FAdd { dst, src, size }),
+        BinOp::Minus => result.push(X86Instr::FSub { dst, src, size }),
+        BinOp::Multiply => result.push(X86Instr::FMul { dst, src, size }),
+        BinOp::Divide => result.push(X86Instr::FDiv { dst, src, size }),
+        BinOp::GreaterThan
+        | BinOp::GreaterThanEq
+        | BinOp::LessThan
+        | BinOp::LessThanEq
+        | BinOp::Equals
+        | BinOp::NotEquals => {
+            gen_float_cmp_code(result, val1_reg, val2_reg, op, size);
+            result.push(X86Instr::Mov {
+                dst: reg_alloc.get_location(*dst_ident),
+                src: Location::Reg(Reg::Rdi),
+                size: dst_ident.get_size(),
+            });
+            return;
+        }
+        // check_types only allows arithmetic and comparisons on floating point values,
+        // and the other operators are generated in conversion to Tac phase.
+        _ => unreachable!(),
+    }
+
+    result.push(X86Instr::MovFromXmm {
+        dst: Reg::Rdi,
+        src: val1_reg,
+        size,
+    });
+    result.push(X86Instr::Mov {
+        dst: reg_alloc.get_location(*dst_ident),
+        src: Location::Reg(Reg::Rdi),
+        size: dst_ident.get_size(),
+    });
+}
+
+/// compares the values in reg1 and reg2, storing the result (0 or 1) in rdi.
+fn gen_float_cmp_code(
+    result: &mut Vec<X86Instr>,
+    reg1: XmmReg,
+    reg2: XmmReg,
+    op: BinOp,
+    size: VarSize,
+) {
+    // every comparison with NaN is false, except for !=. Since an unordered comparison sets ZF, PF and CF,
+    // `<` and `<=` are done as `>` and `>=` with the operands swapped, which are false when CF is set.
+    let (left, right, condition) = match op {
+        BinOp::GreaterThan => (reg1, reg2, CCode::A),
+        BinOp::GreaterThanEq => (reg1, reg2, CCode::AE),
+        BinOp::LessThan => (reg2, reg1, CCode::A),
+        BinOp::LessThanEq => (reg2, reg1, CCode::AE),
+        BinOp::Equals => (reg1, reg2, CCode::E),
+        BinOp::NotEquals => (reg1, reg2, CCode::NE),
+        _ => unreachable!(),
+    };
+
+    for reg in [Reg::Rdi, Reg::Rsi] {
+        result.push(X86Instr::MovImm {
+            dst: Location::Reg(reg),
+            imm: 0,
+            size: VarSize::Quad,
+        });
+    }
+    result.push(X86Instr::FCmp { left, right, size });
+    result.push(X86Instr::SetCC {
+        dst: Reg::Rdi,
+        condition,
+    });
+
+    // ZF is also set for NaN, so equality additionally requires the parity flag to be clear
+    match op {
+        BinOp::Equals => {
+            result.push(X86Instr::SetCC {
+                dst: Reg::Rsi,
+                condition: CCode::NP,
+            });
+            result.push(X86Instr::And {
+                dst: Reg::Rdi,
+                src: Reg::Rsi,
+                size: VarSize::Quad,
+            });
+        }
+        BinOp::NotEquals => {
+            result.push(X86Instr::SetCC {
+                dst: Reg::Rsi,
+                condition: CCode::P,
+            });
+            result.push(X86Instr::Or {
+                dst: Reg::Rdi,
+                src: Reg::Rsi,
+                size: VarSize::Quad,
+            });
+        }
+        _ => {}
+    }
+}
+
+pub fn gen_float_unop_code(
+    result: &mut Vec<X86Instr>,
+    dst_ident: &Identifier,
+    val: &TacVal,
+    op: UnOp,
+    reg_alloc: &RegisterAllocator,
+) {
+    let working_reg = Reg::Rsi;
+    let size = val.get_size();
+
+    gen_load_val_code(result, val, working_reg, reg_alloc);
+
+    match op {
+        UnOp::Plus => unreachable!(), // unary plus generates no TAC instruction
+        UnOp::Negation => {
+            // negating a floating point value flips its sign bit
+            let sign_bit = match size {
+                VarSize::Dword => i32::MIN as i64,
+                _ => i64::MIN,
+            };
+            result.push(X86Instr::MovImm {
+                dst: Location::Reg(Reg::Rdi),
+                imm: sign_bit,
+                size: VarSize::Quad,
+            });
+            result.push(X86Instr::Xor {
+                dst: working_reg,
+                src: Reg::Rdi,
+                size,
+            });
+        }
+        UnOp::Not => {
+            gen_float_zero_test_code(result, working_reg, size);
+            result.push(X86Instr::MovImm {
+                dst: Location::Reg(working_reg),
+                imm: 0,
+                size: VarSize::Quad,
+            });
+            result.push(X86Instr::SetCC {
+                dst: working_reg,
+                condition: CCode::E,
+            });
+        }
+        UnOp::BitwiseComplement => unreachable!(), // check_types rejects ~ on floating point values
+    }
+
+    result.push(X86Instr::Mov {
+        dst: reg_alloc.get_location(*dst_ident),
+        src: Location::Reg(working_reg),
+        size: dst_ident.get_size(),
+    });
+}
+
+/// sets the zero flag if the floating point value in reg is zero.
+/// Both 0.0 and -0.0 are zero, so the sign bit is shifted out before the remaining bits are tested.
+pub fn gen_float_zero_test_code(result: &mut Vec<X86Instr>, reg: Reg, size: VarSize) {
+    result.push(X86Instr::Add {
+        dst: reg,
+        src: reg,
+        size,
+    });
+}
+
+/// copies a value into a temporary, converting between integers, floats and doubles.
+pub fn gen_float_copy_code(
+    result: &mut Vec<X86Instr>,
+    dst_ident: &Identifier,
+    src_val: &TacVal,
+    reg_alloc: &RegisterAllocator,
+) {
+    let dst_size = dst_ident.get_size();
+
+    // literals are converted at compile time
+    let converted_lit = match src_val {
+        TacVal::Lit(value, _) if dst_ident.is_float() => {
+            Some(TacVal::FloatLit(*value as f64, dst_size))
+        }
+        TacVal::FloatLit(value, _) if dst_ident.is_float() => {
+            Some(TacVal::FloatLit(*value, dst_size))
+        }
+        TacVal::FloatLit(value, _) if dst_ident.is_unsigned() && dst_size == VarSize::Quad => {
+            Some(TacVal::Lit(*value as u64 as i64, dst_size))
+        }
+        TacVal::FloatLit(value, _) => Some(TacVal::Lit(*value as i64, dst_size)),
+        TacVal::Lit(_, _) | TacVal::Var(_) => None,
+    };
+
+    match (converted_lit, src_val) {
+        (Some(lit), _) => gen_load_val_code(result, &lit, Reg::Rdi, reg_alloc),
+        (None, TacVal::Var(src_ident))
+            if src_ident.get_kind() == dst_ident.get_kind() && src_ident.get_size() == dst_size =>
+        {
+            // a float copied into a float, or a double into a double
+            gen_load_val_code(result, src_val, Reg::Rdi, reg_alloc);
+        }
+        (None, TacVal::Var(src_ident)) if !src_ident.is_float() => {
+            // integer to floating point, the integer is sign or zero extended to 64 bits first
+            gen_load_val_code(result, src_val, Reg::Rdi, reg_alloc);
+            if src_ident.is_unsigned() && src_ident.get_size() == VarSize::Quad {
+                result.push(X86Instr::CvtUnsignedToFloat {
+                    dst: XmmReg(0),
+                    src: Reg::Rdi,
+                    size: dst_size,
+                });
+            } else {
+                result.push(X86Instr::CvtIntToFloat {
+                    dst: XmmReg(0),
+                    src: Reg::Rdi,
+                    size: dst_size,
+                });
+            }
+            result.push(X86Instr::MovFromXmm {
+                dst: Reg::Rdi,
+                src: XmmReg(0),
+                size: dst_size,
+            });
+        }
+        (None, TacVal::Var(src_ident)) => {
+            let src_size = src_ident.get_size();
+            gen_load_float_val_code(result, src_val, XmmReg(0), src_size, reg_alloc);
+            if dst_ident.is_float() {
+                result.push(X86Instr::CvtFloatToFloat {
+                    dst: XmmReg(0),
+                    src: XmmReg(0),
+                    size: dst_size,
+                });
+                result.push(X86Instr::MovFromXmm {
+                    dst: Reg::Rdi,
+                    src: XmmReg(0),
+                    size: dst_size,
+                });
+            } else if dst_ident.is_unsigned() && dst_size == VarSize::Quad {
+                result.push(X86Instr::CvtFloatToUnsigned {
+                    dst: Reg::Rdi,
+                    src: XmmReg(0),
+                    size: src_size,
+                });
+            } else {
+                // smaller integer types keep the low bits of the 64 bit result
+                result.push(X86Instr::CvtFloatToInt {
+                    dst: Reg::Rdi,
+                    src: XmmReg(0),
+                    size: src_size,
+                });
+            }
+        }
+        (None, TacVal::Lit(_, _) | TacVal::FloatLit(_, _)) => unreachable!(),
+    }
+
+    result.push(X86Instr::Mov {
+        dst: reg_alloc.get_location(*dst_ident),
+        src: Location::Reg(Reg::Rdi),
+        size: dst_size,
+    });
+}
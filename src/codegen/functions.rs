@@ -1,36 +1,100 @@
 use crate::{
-    codegen::{gen_load_val_code, reg::Reg, Location},
+    codegen::{
+        gen_load_val_code,
+        reg::{Reg, XmmReg},
+        Location,
+    },
     tac::{Identifier, TacVal},
 };
 
 use super::{RegisterAllocator, X86Instr};
 
+/// where an argument is passed, following the System V calling convention
+#[derive(Clone, Copy)]
+pub enum ArgLocation {
+    Reg(Reg),
+    Xmm(XmmReg),
+    Stack(usize), // the index of the argument among the arguments passed on the stack
+}
+
+/// integer and pointer arguments are passed in the first 6 argument registers, and floating point arguments
+/// in xmm0 to xmm7. The remaining arguments are passed on the stack, in order.
+pub fn get_arg_locations(args_are_float: impl Iterator<Item = bool>) -> Vec<ArgLocation> {
+    let (mut num_int_args, mut num_float_args, mut num_stack_args) = (0, 0, 0);
+    args_are_float
+        .map(|is_float| {
+            if is_float && num_float_args < 8 {
+                num_float_args += 1;
+                ArgLocation::Xmm(XmmReg(num_float_args - 1))
+            } else if !is_float && num_int_args < 6 {
+                num_int_args += 1;
+                ArgLocation::Reg(get_nth_arg_reg(num_int_args - 1).unwrap())
+            } else {
+                num_stack_args += 1;
+                ArgLocation::Stack(num_stack_args - 1)
+            }
+        })
+        .collect()
+}
+
+/// the function called by a call instruction
+pub enum CallTarget<'a> {
+    Direct(&'a str),      // a function called by name
+    Indirect(&'a TacVal), // a pointer to the function
+}
+
 pub fn generate_function_call_code(
     result: &mut Vec<X86Instr>,
-    function_name: &str,
+    target: CallTarget,
     args: &[TacVal],
     optional_ident: Option<Identifier>,
     reg_alloc: &RegisterAllocator,
 ) {
-    // we reverse the order of args in order to store things to memory first.
-    // this way if the arguments past the 6th arg require using any of the 6 registers used
-    // for the first 6 arguments, conflicts may be avoided.
-    // also must ensure that last arguments get pushed on stack first
-    for (index, arg) in args.iter().enumerate().rev() {
-        if index < 6 {
-            let arg_reg = get_nth_arg_reg(index).unwrap();
-            gen_load_val_code(result, arg, arg_reg, reg_alloc);
-        } else {
+    let arg_locations = get_arg_locations(args.iter().map(|arg| arg.is_float()));
+
+    // the arguments passed on the stack are pushed first, so that loading them doesn't overwrite
+    // the argument registers. The last argument must be pushed on the stack first.
+    for (arg, location) in args.iter().zip(&arg_locations).rev() {
+        if let ArgLocation::Stack(_) = location {
             gen_load_val_code(result, arg, Reg::Rdi, reg_alloc);
             result.push(X86Instr::Push { reg: Reg::Rdi });
         }
     }
+    for (arg, location) in args.iter().zip(&arg_locations) {
+        match location {
+            ArgLocation::Reg(arg_reg) => gen_load_val_code(result, arg, *arg_reg, reg_alloc),
+            ArgLocation::Xmm(xmm_reg) => {
+                gen_load_val_code(result, arg, Reg::Rax, reg_alloc);
+                result.push(X86Instr::MovToXmm {
+                    dst: *xmm_reg,
+                    src: Reg::Rax,
+                    size: arg.get_size(),
+                });
+            }
+            ArgLocation::Stack(_) => {}
+        }
+    }
 
-    result.push(X86Instr::Call {
-        name: function_name.to_owned(),
-    });
+    match target {
+        CallTarget::Direct(function_name) => result.push(X86Instr::Call {
+            name: function_name.to_owned(),
+        }),
+        CallTarget::Indirect(function_ptr) => {
+            // r11 does not hold an argument, and may be overwritten by the callee anyways
+            gen_load_val_code(result, function_ptr, Reg::R11, reg_alloc);
+            result.push(X86Instr::CallIndirect { reg: Reg::R11 });
+        }
+    }
 
     if let Some(function_return_val_ident) = optional_ident {
+        if function_return_val_ident.is_float() {
+            // floating point values are returned in xmm0
+            result.push(X86Instr::MovFromXmm {
+                dst: Reg::Rax,
+                src: XmmReg(0),
+                size: function_return_val_ident.get_size(),
+            });
+        }
         result.push(X86Instr::Mov {
             dst: reg_alloc.get_location(function_return_val_ident),
             src: Location::Reg(Reg::Rax),
@@ -42,28 +106,35 @@ pub fn generate_function_call_code(
 pub fn gen_load_arg_code(
     result: &mut Vec<X86Instr>,
     ident: &Identifier,
-    arg_num: usize,
+    location: ArgLocation,
     reg_alloc: &RegisterAllocator,
 ) {
-    if arg_num < 6 {
-        let source_reg = get_nth_arg_reg(arg_num).unwrap();
-        result.push(X86Instr::Mov {
-            dst: reg_alloc.get_location(*ident),
-            src: Location::Reg(source_reg),
-            size: ident.get_size(),
-        });
-    } else {
-        result.push(X86Instr::Mov {
-            dst: Location::Reg(Reg::Rdi),
-            src: Location::MemAbove((arg_num - 4) * 8),
-            size: ident.get_size(),
-        });
-        result.push(X86Instr::Mov {
-            dst: reg_alloc.get_location(*ident),
-            src: Location::Reg(Reg::Rdi),
-            size: ident.get_size(),
-        });
-    }
+    // rax is used as a scratch register, since it does not hold an argument
+    let source_reg = match location {
+        ArgLocation::Reg(reg) => reg,
+        ArgLocation::Xmm(xmm_reg) => {
+            result.push(X86Instr::MovFromXmm {
+                dst: Reg::Rax,
+                src: xmm_reg,
+                size: ident.get_size(),
+            });
+            Reg::Rax
+        }
+        ArgLocation::Stack(index) => {
+            // the stack arguments are above the return address and the saved rbp
+            result.push(X86Instr::Mov {
+                dst: Location::Reg(Reg::Rax),
+                src: Location::MemAbove(16 + index * 8),
+                size: ident.get_size(),
+            });
+            Reg::Rax
+        }
+    };
+    result.push(X86Instr::Mov {
+        dst: reg_alloc.get_location(*ident),
+        src: Location::Reg(source_reg),
+        size: ident.get_size(),
+    });
 }
 
 pub fn get_nth_arg_reg(n: usize) -> Option<Reg> {
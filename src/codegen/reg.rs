@@ -117,11 +117,14 @@ impl Reg {
             Reg::R15 => "r15b".to_owned(),
         }
     }
+}
 
-    pub fn get_default_name(&self) -> String {
-        match self {
-            Reg::Rsp | Reg::Rbp => self.get_64_bit_name(),
-            _ => self.get_32_bit_name(),
-        }
+/// one of the SSE registers xmm0 to xmm15, which hold floating point values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct XmmReg(pub usize);
+
+impl XmmReg {
+    pub fn get_name(&self) -> String {
+        format!("xmm{}", self.0)
     }
 }
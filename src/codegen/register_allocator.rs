@@ -1,49 +1,135 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::tac::{tac_func::TacFunc, tac_instr::TacInstr, Identifier};
+use crate::tac::{
+    cfg::Cfg,
+    tac_func::{TacBody, TacFunc},
+    tac_instr::TacInstr,
+    Identifier,
+};
 
-use super::Location;
+use super::{reg::Reg, Location};
+
+/// registers which calls preserve, so temporaries which are live across calls can be kept in them.
+/// A function which uses them saves them in its prologue and restores them before returning.
+const CALLEE_SAVED_REGS: [Reg; 5] = [Reg::Rbx, Reg::R12, Reg::R13, Reg::R14, Reg::R15];
+
+/// registers which calls may overwrite, and which are not used as scratch registers by the code generator,
+/// other than r8 and r9 holding arguments during calls
+const CALLER_SAVED_REGS: [Reg; 3] = [Reg::R8, Reg::R9, Reg::R10];
+
+/// the positions of the instructions from the first one where a temporary is live to the last one
+struct LiveInterval {
+    ident: Identifier,
+    start: usize,
+    end: usize,
+    may_use_caller_saved: bool,
+}
 
 pub struct RegisterAllocator {
     ident_to_loc_map: HashMap<Identifier, Location>,
     ident_to_init_val_map: HashMap<Identifier, usize>, // here the usize represents an offset, in bytes, from rbp.
+    saved_regs: Vec<(Reg, usize)>, // the callee saved registers which are used, and the offset from rbp they are saved at
 }
 
 impl RegisterAllocator {
+    /// assigns each temporary of the function a register or a place on the stack, using the linear scan algorithm
+    /// from "Linear Scan Register Allocation" by Poletto and Sarkar. Temporaries whose address is taken are always
+    /// kept on the stack. Returns the allocator and the number of bytes of stack the function needs.
     pub fn new(f: &TacFunc) -> (Self, usize) {
-        let mut set_of_temporaries: Vec<Identifier> = Vec::new();
+        let address_taken: HashSet<Identifier> = f
+            .instrs()
+            .filter_map(|instr| match instr {
+                TacInstr::Ref(_, ident) => Some(*ident),
+                _ => None,
+            })
+            .collect();
 
-        for (arg_ident, _) in &f.args {
-            set_of_temporaries.push(*arg_ident);
-        }
+        let mut intervals = get_live_intervals(f, &address_taken);
+        intervals.sort_by_key(|interval| interval.start);
 
-        for instr in &f.body {
-            for ident in instr.get_read_identifiers() {
-                if !set_of_temporaries.contains(&ident) {
-                    eprintln!(
-                        "warning: found read from temporary {:?} without writing first.",
-                        ident
-                    );
+        let mut ident_to_loc_map = HashMap::new();
+        let mut spilled = HashSet::new();
+        let mut free_regs: Vec<Reg> = CALLER_SAVED_REGS
+            .into_iter()
+            .chain(CALLEE_SAVED_REGS)
+            .collect();
+        let mut active: Vec<(&LiveInterval, Reg)> = Vec::new();
+        for interval in &intervals {
+            active.retain(|(active_interval, reg)| {
+                let has_ended = active_interval.end < interval.start;
+                if has_ended {
+                    free_regs.push(*reg);
                 }
-            }
-            if let Some(ident) = instr.get_written_identifier() {
-                if !set_of_temporaries.contains(&ident) {
-                    set_of_temporaries.push(ident);
+                !has_ended
+            });
+
+            let can_use =
+                |reg: &Reg| interval.may_use_caller_saved || CALLEE_SAVED_REGS.contains(reg);
+            // the caller saved registers are preferred, since they do not need to be saved
+            let free_index = free_regs
+                .iter()
+                .position(|reg| can_use(reg) && CALLER_SAVED_REGS.contains(reg))
+                .or_else(|| free_regs.iter().position(can_use));
+            match free_index {
+                Some(index) => {
+                    let reg = free_regs.remove(index);
+                    ident_to_loc_map.insert(interval.ident, Location::Reg(reg));
+                    active.push((interval, reg));
+                }
+                None => {
+                    // the temporary which is live for the longest is spilled to the stack
+                    let furthest = active
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, (_, reg))| can_use(reg))
+                        .max_by_key(|(_, (active_interval, _))| active_interval.end)
+                        .map(|(index, _)| index);
+                    match furthest {
+                        Some(index) if active[index].0.end > interval.end => {
+                            let (spilled_interval, reg) = active.remove(index);
+                            ident_to_loc_map.remove(&spilled_interval.ident);
+                            spilled.insert(spilled_interval.ident);
+                            ident_to_loc_map.insert(interval.ident, Location::Reg(reg));
+                            active.push((interval, reg));
+                        }
+                        _ => {
+                            spilled.insert(interval.ident);
+                        }
+                    }
                 }
             }
         }
 
-        let mut ident_to_loc_map = HashMap::new();
-
         let mut bytes_needed = 0;
 
-        for identifier in &set_of_temporaries {
-            bytes_needed += identifier.get_num_bytes();
-            ident_to_loc_map.insert(*identifier, Location::Mem(bytes_needed));
+        // the temporaries on the stack are given their places in the order they appear in the function
+        let mut temporaries: Vec<Identifier> = f.args.iter().map(|(ident, _)| *ident).collect();
+        for instr in f.instrs() {
+            temporaries.extend(instr.get_read_identifiers());
+            temporaries.extend(instr.get_written_identifier());
+        }
+        for ident in temporaries {
+            if (spilled.contains(&ident) || address_taken.contains(&ident))
+                && !ident_to_loc_map.contains_key(&ident)
+            {
+                bytes_needed += ident.get_num_bytes();
+                ident_to_loc_map.insert(ident, Location::Mem(bytes_needed));
+            }
+        }
+
+        let mut saved_regs = Vec::new();
+        for reg in CALLEE_SAVED_REGS {
+            if ident_to_loc_map
+                .values()
+                .any(|loc| *loc == Location::Reg(reg))
+            {
+                bytes_needed += 8;
+                saved_regs.push((reg, bytes_needed));
+            }
         }
 
         let mut ident_to_init_val_map: HashMap<Identifier, usize> = HashMap::new();
-        for instr in &f.body {
+        for instr in f.instrs() {
             if let TacInstr::MemChunk(ptr_ident, chunk_size, _) = instr {
                 bytes_needed += chunk_size;
                 ident_to_init_val_map.insert(*ptr_ident, bytes_needed);
@@ -54,18 +140,148 @@ impl RegisterAllocator {
             RegisterAllocator {
                 ident_to_loc_map,
                 ident_to_init_val_map,
+                saved_regs,
             },
             bytes_needed,
         )
     }
 
     pub fn get_location(&self, temporary: Identifier) -> Location {
-        return *self.ident_to_loc_map.get(&temporary).unwrap();
+        *self.ident_to_loc_map.get(&temporary).unwrap()
     }
 
     pub fn get_ptr_init_val(&self, temporary: Identifier) -> usize {
         // expects the temporary to be initialized with an MemChunk() TAC statement
         // returns a usize representing an offset from rbp of the chunk of allocated stack memory
-        return *self.ident_to_init_val_map.get(&temporary).unwrap();
+        *self.ident_to_init_val_map.get(&temporary).unwrap()
+    }
+
+    /// returns the callee saved registers which the function uses, and the offsets from rbp where
+    /// they are saved in the prologue, to be restored before returning
+    pub fn get_saved_regs(&self) -> &[(Reg, usize)] {
+        &self.saved_regs
+    }
+}
+
+/// finds the live interval of each temporary which is not kept on the stack. The instructions are numbered in the
+/// order they are laid out, with the start and end of each basic block numbered too, so that temporaries which are
+/// live into or out of a block are live from its start or until its end. The arguments are live from position 0.
+fn get_live_intervals(f: &TacFunc, address_taken: &HashSet<Identifier>) -> Vec<LiveInterval> {
+    let built_cfg;
+    let cfg = match &f.body {
+        TacBody::Cfg(cfg) => cfg,
+        // the function was not split into basic blocks, such as when it was not optimized
+        TacBody::Instrs(instrs) => {
+            built_cfg = Cfg::new(instrs.clone());
+            &built_cfg
+        }
+    };
+    let (live_in, live_out) = get_liveness(cfg);
+
+    let mut intervals: Vec<LiveInterval> = Vec::new();
+    let mut ident_to_interval: HashMap<Identifier, usize> = HashMap::new();
+    let mut extend = |ident: Identifier, pos: usize| {
+        if address_taken.contains(&ident) {
+            return;
+        }
+        match ident_to_interval.get(&ident) {
+            Some(index) => {
+                let interval = &mut intervals[*index];
+                interval.start = interval.start.min(pos);
+                interval.end = interval.end.max(pos);
+            }
+            None => {
+                ident_to_interval.insert(ident, intervals.len());
+                intervals.push(LiveInterval {
+                    ident,
+                    start: pos,
+                    end: pos,
+                    may_use_caller_saved: true,
+                });
+            }
+        }
+    };
+
+    for (arg_ident, _) in &f.args {
+        extend(*arg_ident, 0);
+    }
+
+    let mut call_positions = Vec::new();
+    let mut pos = 0;
+    for (block_id, block) in cfg.blocks.iter().enumerate() {
+        let block_start = pos;
+        for instr in &block.instrs {
+            pos += 1;
+            if matches!(instr, TacInstr::Call(..) | TacInstr::CallIndirect(..)) {
+                call_positions.push(pos);
+            }
+            for ident in instr
+                .get_read_identifiers()
+                .into_iter()
+                .chain(instr.get_written_identifier())
+            {
+                extend(ident, pos);
+            }
+        }
+        pos += 1;
+        for ident in &live_in[block_id] {
+            extend(*ident, block_start);
+        }
+        for ident in &live_out[block_id] {
+            extend(*ident, pos);
+        }
+        pos += 1;
+    }
+
+    // the arguments are moved out of the argument registers one after another, so they are kept out of r8 and r9,
+    // and temporaries which are read during or after a call cannot be kept in the registers it overwrites
+    let args: HashSet<Identifier> = f.args.iter().map(|(ident, _)| *ident).collect();
+    for interval in &mut intervals {
+        interval.may_use_caller_saved = !args.contains(&interval.ident)
+            && !call_positions
+                .iter()
+                .any(|call_pos| interval.start < *call_pos && *call_pos <= interval.end);
+    }
+    intervals
+}
+
+/// returns the temporaries which are live at the start and at the end of each basic block,
+/// which are the temporaries which may be read before they are written again
+fn get_liveness(cfg: &Cfg) -> (Vec<HashSet<Identifier>>, Vec<HashSet<Identifier>>) {
+    let num_blocks = cfg.blocks.len();
+    let mut uses = vec![HashSet::new(); num_blocks];
+    let mut defs = vec![HashSet::new(); num_blocks];
+    for (block_id, block) in cfg.blocks.iter().enumerate() {
+        for instr in &block.instrs {
+            for ident in instr.get_read_identifiers() {
+                if !defs[block_id].contains(&ident) {
+                    uses[block_id].insert(ident);
+                }
+            }
+            if let Some(ident) = instr.get_written_identifier() {
+                defs[block_id].insert(ident);
+            }
+        }
+    }
+
+    let mut live_in: Vec<HashSet<Identifier>> = uses.clone();
+    let mut live_out: Vec<HashSet<Identifier>> = vec![HashSet::new(); num_blocks];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        // liveness flows backwards, so visiting the blocks in reverse usually needs fewer passes
+        for block_id in (0..num_blocks).rev() {
+            let block_live_out: HashSet<Identifier> = cfg.succs[block_id]
+                .iter()
+                .flat_map(|succ| live_in[*succ].iter().copied())
+                .collect();
+            for ident in &block_live_out {
+                if !defs[block_id].contains(ident) && live_in[block_id].insert(*ident) {
+                    changed = true;
+                }
+            }
+            live_out[block_id] = block_live_out;
+        }
     }
+    (live_in, live_out)
 }
@@ -4,7 +4,9 @@ use crate::{
     types::VarSize,
 };
 
-use super::{gen_load_val_code, CCode, Location, Reg, RegisterAllocator, X86Instr};
+use super::{
+    gen_extend_result_code, gen_load_val_code, CCode, Location, Reg, RegisterAllocator, X86Instr,
+};
 
 pub fn gen_unop_code(
     result: &mut Vec<X86Instr>,
@@ -18,6 +20,7 @@ pub fn gen_unop_code(
     gen_load_val_code(result, val, working_reg, reg_alloc);
 
     match op {
+        UnOp::Plus => unreachable!(), // unary plus generates no TAC instruction
         UnOp::Negation => result.push(X86Instr::Neg {
             dst: working_reg,
             size: val.get_size(),
@@ -43,6 +46,17 @@ pub fn gen_unop_code(
         }
     }
 
+    // like the result of a binary operation, the result is extended if it is stored into a bigger destination
+    if let UnOp::Negation | UnOp::BitwiseComplement = op {
+        gen_extend_result_code(
+            result,
+            working_reg,
+            val.get_size(),
+            dst_ident.get_size(),
+            val.is_unsigned(),
+        );
+    }
+
     result.push(X86Instr::Mov {
         dst: reg_alloc.get_location(*dst_ident),
         src: Location::Reg(working_reg),
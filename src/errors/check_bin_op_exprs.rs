@@ -1,26 +1,51 @@
 use crate::{
     parser::expr_parser::{BinOp, Expr},
+    tokenizer::source_cursor::SourcePtr,
     types::{FundT, VarType},
 };
 
 use super::{
     check_types::{
-        are_assignment_compatible_types, are_interchangable_types, get_type, is_l_value, CodeEnv,
+        are_assignment_compatible_types, are_interchangable_types, get_type, get_type_name,
+        is_l_value, warn_if_narrowing, CodeEnv,
     },
-    display::err_display_no_source,
+    display::err_display,
+    warnings::{warn, Warning},
 };
 
+/// returns the type of a binary operator expression, where `pos` is the location of the operator
 pub fn get_binop_type(
     op: BinOp,
     expr1: &mut Expr,
     expr2: &mut Expr,
     code_env: &CodeEnv,
+    pos: SourcePtr,
 ) -> Option<VarType> {
     let t1 = get_type(expr1, code_env);
     let t2 = get_type(expr2, code_env);
+    let invalid_operands = || -> ! {
+        err_display(
+            format!(
+                "invalid operands to binary {} (have {} and {})",
+                op,
+                get_type_name(&t1),
+                get_type_name(&t2)
+            ),
+            pos,
+        )
+    };
 
     match op {
-        BinOp::Multiply | BinOp::Divide | BinOp::Modulus | BinOp::LogicalAnd | BinOp::LogicalOr => {
+        BinOp::Multiply
+        | BinOp::Divide
+        | BinOp::Modulus
+        | BinOp::LogicalAnd
+        | BinOp::LogicalOr
+        | BinOp::BitwiseAnd
+        | BinOp::BitwiseOr
+        | BinOp::BitwiseXor
+        | BinOp::ShiftLeft
+        | BinOp::ShiftRight => {
             // These operations require 2 fundamental types
             let mut error = false;
             if let Some(VarType::Ptr(_)) = t1 {
@@ -35,33 +60,71 @@ pub fn get_binop_type(
             if let Some(VarType::Arr(_, _)) = t2 {
                 error = true;
             }
+            if let Some(VarType::Struct(_)) = t1 {
+                error = true;
+            }
+            if let Some(VarType::Struct(_)) = t2 {
+                error = true;
+            }
             if error {
-                err_display_no_source("expected integer in *, /, %, &&, or ||")
+                invalid_operands()
+            }
+            // the remaining operators require integers
+            if !matches!(
+                op,
+                BinOp::Multiply | BinOp::Divide | BinOp::LogicalAnd | BinOp::LogicalOr
+            ) && [&t1, &t2].into_iter().flatten().any(|t| t.is_float())
+            {
+                invalid_operands()
+            }
+            match op {
+                BinOp::LogicalAnd | BinOp::LogicalOr => Some(VarType::Fund(FundT::Int)),
+                // the type of a shift is the promoted type of the left operand
+                BinOp::ShiftLeft | BinOp::ShiftRight => get_common_type(&t1, &t1),
+                _ => get_common_type(&t1, &t2),
             }
-            t1
         }
 
         BinOp::Plus | BinOp::Minus => match (&t1, &t2) {
-            (None | Some(VarType::Fund(_)), None | Some(VarType::Fund(_))) => t1,
+            (None | Some(VarType::Fund(_)), None | Some(VarType::Fund(_))) => {
+                get_common_type(&t1, &t2)
+            }
+            (Some(VarType::Fund(f)), Some(_)) | (Some(_), Some(VarType::Fund(f)))
+                if f.is_float() =>
+            {
+                invalid_operands()
+            }
             (None | Some(VarType::Fund(_)), Some(t)) | (Some(t), None | Some(VarType::Fund(_))) => {
                 match t {
                     VarType::Fund(_) => Some(t.clone()),
+                    VarType::Ptr(_) if t.is_void_ptr() || t.is_func_ptr() => err_display(
+                        format!(
+                            "arithmetic on {}, whose pointee has no size (have {} and {})",
+                            t,
+                            get_type_name(&t1),
+                            get_type_name(&t2)
+                        ),
+                        pos,
+                    ),
                     VarType::Ptr(_) => Some(t.clone()),
                     VarType::Arr(array_inner_type, _) => {
                         Some(VarType::Ptr(array_inner_type.clone()))
                     }
+                    VarType::Struct(_) => invalid_operands(),
+                    // get_type rejects void values, and functions are only used through pointers
+                    VarType::Void | VarType::Func(_, _) => unreachable!(),
                 }
             }
             (Some(VarType::Ptr(t1)), Some(VarType::Ptr(t2)))
-                if op == BinOp::Minus && t1.num_bytes() == t2.num_bytes() =>
+                if op == BinOp::Minus
+                    && t1.is_complete()
+                    && t2.is_complete()
+                    && t1.num_bytes() == t2.num_bytes() =>
             {
                 // subtracting two pointers gives an integer
                 Some(VarType::Fund(FundT::Long))
             }
-            (Some(t1), Some(t2)) => err_display_no_source(format!(
-                "trying to add or subtract incompatible types {} and {}",
-                t1, t2
-            )),
+            (Some(_), Some(_)) => invalid_operands(),
         },
         BinOp::GreaterThan
         | BinOp::GreaterThanEq
@@ -69,23 +132,100 @@ pub fn get_binop_type(
         | BinOp::LessThanEq
         | BinOp::Equals
         | BinOp::NotEquals => {
-            if !are_interchangable_types(&t1, &t2) {
-                err_display_no_source(format!(
-                    "trying to compare incompatible types {:?} and {:?}",
-                    t1, t2
-                ));
+            if let (Some(VarType::Ptr(inner_1)), Some(VarType::Ptr(inner_2))) = (&t1, &t2) {
+                // like gcc, pointers of different types may be compared, but probably by mistake
+                if inner_1 != inner_2 && **inner_1 != VarType::Void && **inner_2 != VarType::Void {
+                    warn(
+                        Warning::CompareDistinctPointerTypes,
+                        format!(
+                            "comparison of distinct pointer types {} and {} lacks a cast",
+                            get_type_name(&t1),
+                            get_type_name(&t2)
+                        ),
+                        pos,
+                    );
+                }
+            } else if !are_interchangable_types(&t1, &t2) {
+                err_display(
+                    format!(
+                        "comparison between incompatible types {} and {}",
+                        get_type_name(&t1),
+                        get_type_name(&t2)
+                    ),
+                    pos,
+                );
             }
-            t1
+            Some(VarType::Fund(FundT::Int))
         }
 
         BinOp::Assign => {
             if !is_l_value(expr1) {
-                err_display_no_source("cannot assign to a non l value");
+                err_display("cannot assign to a non l value", pos);
             }
             if !are_assignment_compatible_types(&t1, &t2) {
-                err_display_no_source("wrong types in assignment.")
+                err_display(
+                    format!(
+                        "incompatible types when assigning to type {} from type {}",
+                        get_type_name(&t1),
+                        get_type_name(&t2)
+                    ),
+                    pos,
+                )
             }
+            warn_if_narrowing(&t1, &t2, pos);
             t1
         }
+        BinOp::Comma => unreachable!(), // the comma operator is typed by check_types, since its left operand may be void
+    }
+}
+
+/// Performs the usual arithmetic conversions on the types of the two operands of a binary operator,
+/// returning the type both operands are converted to before the operation.
+/// Integer literals have no type (None), and are treated as an `int`.
+pub fn get_common_type(t1: &Option<VarType>, t2: &Option<VarType>) -> Option<VarType> {
+    let (t1, t2) = match (t1, t2) {
+        (None, None) => return None,
+        (Some(VarType::Fund(t1)), Some(VarType::Fund(t2))) => (*t1, *t2),
+        (Some(VarType::Fund(t)), None) | (None, Some(VarType::Fund(t))) => (*t, FundT::Int),
+        _ => unreachable!(), // only called with arithmetic operands
+    };
+
+    // if either operand is floating point, the other is converted to the bigger floating point type
+    if t1 == FundT::Double || t2 == FundT::Double {
+        return Some(VarType::Fund(FundT::Double));
+    } else if t1 == FundT::Float || t2 == FundT::Float {
+        return Some(VarType::Fund(FundT::Float));
+    }
+
+    // integer promotion: types smaller than an int are promoted to int, since int can represent all of their values
+    let (t1, t2) = (promote_integer(t1), promote_integer(t2));
+
+    let common_type = if t1 == t2 {
+        t1
+    } else if t1.is_unsigned() == t2.is_unsigned() {
+        // the type with the bigger size wins
+        if t1.to_size().num_bytes() > t2.to_size().num_bytes() {
+            t1
+        } else {
+            t2
+        }
+    } else {
+        let (unsigned_t, signed_t) = if t1.is_unsigned() { (t1, t2) } else { (t2, t1) };
+        if unsigned_t.to_size().num_bytes() >= signed_t.to_size().num_bytes() {
+            unsigned_t
+        } else {
+            // the signed type can represent all values of the unsigned type
+            signed_t
+        }
+    };
+    Some(VarType::Fund(common_type))
+}
+
+/// integer promotion converts types smaller than an int to int, since int can represent all of their values
+pub fn promote_integer(t: FundT) -> FundT {
+    match t {
+        FundT::Char | FundT::Short | FundT::UChar | FundT::UShort => FundT::Int,
+        FundT::Int | FundT::Long | FundT::UInt | FundT::ULong => t,
+        FundT::Float | FundT::Double => unreachable!(), // floating point types are not promoted
     }
 }
@@ -1,60 +1,130 @@
-use super::display::err_display_no_source;
+use super::display::{report_error, report_error_no_source};
 use crate::{
     codegen::builtin_functions::BUILTIN_FUNCTIONS,
     parser::{
         expr_parser::{Expr, ExprEnum},
         Program, Statement,
     },
+    tokenizer::source_cursor::SourcePtr,
+    types::VarType,
 };
 
-#[derive(PartialEq, Eq)]
 struct FuncDecl {
     name: String,
-    num_args: usize,
+    param_types: Vec<VarType>,
+    return_type: VarType,
+    is_defined: bool, // false if the function only has a prototype
+    pos: SourcePtr,
 }
 
 /// The check_funcs function takes a program AST,
 /// and verifies that:
-///    - Every function call must have a definition with the same number of parameters
-///    - There are no duplicate function names
+///    - Every function call, including those in the initializers of global variables,
+///      must have a definition with the same number of parameters
+///    - There are no duplicate function names, and no global variables with the name of a function
+///    - Prototypes agree with each other and with the definition of the function
 ///    - There is a "main" function
 pub fn check_funcs(program: &Program) {
-    let mut known_functions = Vec::new();
-    let mut declared_names = Vec::new();
+    // the definitions and prototypes are checked in the order they appear in the source,
+    // so that each one is compared against the earlier declaration of the same function
+    let mut decls: Vec<FuncDecl> = program
+        .functions
+        .iter()
+        .map(|function| FuncDecl {
+            name: function.name.clone(),
+            param_types: function.param_types(),
+            return_type: function.return_type.clone(),
+            is_defined: true,
+            pos: function.pos,
+        })
+        .chain(program.prototypes.iter().map(|prototype| FuncDecl {
+            name: prototype.name.clone(),
+            param_types: prototype.param_types.clone(),
+            return_type: prototype.return_type.clone(),
+            is_defined: false,
+            pos: prototype.pos,
+        }))
+        .collect();
+    decls.sort_by_key(|decl| (decl.pos.line, decl.pos.col));
 
-    for function in &program.functions {
-        if declared_names.contains(&function.name) {
-            err_display_no_source(format!(
-                "function declared more than once: {}",
-                function.name
-            ));
+    let mut known_functions: Vec<FuncDecl> = Vec::new();
+    for decl in decls {
+        match known_functions.iter_mut().find(|f| f.name == decl.name) {
+            Some(earlier) => {
+                if earlier.is_defined && decl.is_defined {
+                    report_error(
+                        format!("function declared more than once: {}", decl.name),
+                        decl.pos,
+                    );
+                } else if earlier.param_types != decl.param_types
+                    || earlier.return_type != decl.return_type
+                {
+                    report_error(
+                        format!(
+                            "conflicting types for function {}: declared as {}, but previously declared as {}",
+                            decl.name,
+                            format_signature(&decl.return_type, &decl.param_types),
+                            format_signature(&earlier.return_type, &earlier.param_types),
+                        ),
+                        decl.pos,
+                    );
+                }
+                earlier.is_defined |= decl.is_defined;
+            }
+            None => known_functions.push(decl),
         }
+    }
 
-        known_functions.push(FuncDecl {
-            name: function.name.clone(),
-            num_args: function.args.len(),
-        });
-        declared_names.push(function.name.clone());
+    // functions and global variables share the same names, so a name cannot be both
+    for global_var in &program.global_vars {
+        let Statement::Declare(var_name, _, _, var_pos) = global_var else {
+            continue;
+        };
+        if let Some(func) = known_functions.iter().find(|f| &f.name == var_name) {
+            let later_pos = if (var_pos.line, var_pos.col) > (func.pos.line, func.pos.col) {
+                *var_pos
+            } else {
+                func.pos
+            };
+            report_error(
+                format!(
+                    "{} is declared as both a global variable and a function",
+                    var_name
+                ),
+                later_pos,
+            );
+        }
     }
 
-    if !declared_names.contains(&"main".to_owned()) {
-        err_display_no_source("could not find \"main\" function!");
+    if !program.functions.iter().any(|f| f.name == "main") {
+        report_error_no_source("could not find \"main\" function!");
     }
     for func in &known_functions {
-        if func.name == "main" && func.num_args != 0 {
-            err_display_no_source(format!(
-                "main function must take 0 arguments instead of {}.",
-                func.num_args
-            ));
+        if func.name == "main" && !func.param_types.is_empty() {
+            report_error(
+                format!(
+                    "main function must take 0 arguments instead of {}.",
+                    func.param_types.len()
+                ),
+                func.pos,
+            );
         }
     }
 
+    // the initializers of global variables may also call functions, which is rejected later
+    // since they are not constant, but the functions must exist for their types to be checked
+    check_stmts_funcs(&program.global_vars, &known_functions);
     for function in &program.functions {
         let body = &function.body;
         check_stmts_funcs(body, &known_functions);
     }
 }
 
+fn format_signature(return_type: &VarType, param_types: &[VarType]) -> String {
+    let params: Vec<String> = param_types.iter().map(|t| t.to_string()).collect();
+    format!("{} ({})", return_type, params.join(", "))
+}
+
 fn check_stmts_funcs(stmts: &Vec<Statement>, known_funcs: &Vec<FuncDecl>) {
     // note that known_var_names is a owned hashset, not a reference, because
     // this function add to the hashset, but it should not change the hashset owned
@@ -67,9 +137,16 @@ fn check_stmts_funcs(stmts: &Vec<Statement>, known_funcs: &Vec<FuncDecl>) {
 
 fn check_stmt_funcs(stmt: &Statement, known_funcs: &Vec<FuncDecl>) {
     match stmt {
-        Statement::Continue | Statement::Break | Statement::Empty => {}
-        Statement::Return(expr) => check_expr_funcs(expr, known_funcs),
-        Statement::Declare(_, optional_expr, _) => {
+        Statement::Continue(_)
+        | Statement::Break(_)
+        | Statement::Empty
+        | Statement::Default(_)
+        | Statement::Goto(_, _)
+        | Statement::EnumConsts(_) => {}
+        Statement::Labeled(_, inner_stmt, _) => check_stmt_funcs(inner_stmt, known_funcs),
+        Statement::Return(Some(expr), _) => check_expr_funcs(expr, known_funcs),
+        Statement::Return(None, _) => {}
+        Statement::Declare(_, optional_expr, _, _) => {
             if let Some(expr) = optional_expr {
                 check_expr_funcs(expr, known_funcs);
             }
@@ -82,12 +159,21 @@ fn check_stmt_funcs(stmt: &Statement, known_funcs: &Vec<FuncDecl>) {
                 check_stmt_funcs(not_taken_expr, known_funcs);
             }
         }
+        Statement::Switch(ctrl_expr, body) => {
+            check_expr_funcs(ctrl_expr, known_funcs);
+            check_stmt_funcs(body, known_funcs);
+        }
+        Statement::Case(expr) => check_expr_funcs(expr, known_funcs),
         Statement::While(ctrl_expr, body) => {
             check_expr_funcs(ctrl_expr, known_funcs);
             check_stmt_funcs(body, known_funcs);
         }
-        Statement::For(init_expr, ctrl_expr, post_expr, body) => {
-            check_stmt_funcs(init_expr, known_funcs);
+        Statement::DoWhile(body, ctrl_expr) => {
+            check_stmt_funcs(body, known_funcs);
+            check_expr_funcs(ctrl_expr, known_funcs);
+        }
+        Statement::For(init_stmts, ctrl_expr, post_expr, body) => {
+            check_stmts_funcs(init_stmts, known_funcs);
             if let Some(expr) = ctrl_expr {
                 check_expr_funcs(expr, known_funcs);
             }
@@ -106,11 +192,13 @@ fn check_expr_funcs(expr: &Expr, known_funcs: &Vec<FuncDecl>) {
 
     match &expr.content {
         ExprEnum::Int(_)
+        | ExprEnum::Float(_, _)
         | ExprEnum::Var(_)
         | ExprEnum::PostfixDec(_)
         | ExprEnum::PostfixInc(_)
         | ExprEnum::PrefixDec(_)
         | ExprEnum::PrefixInc(_)
+        | ExprEnum::SizeofType(_)
         | ExprEnum::StaticStrPtr(_) => {}
         ExprEnum::UnOp(_, inner_expr) => exprs_to_check = vec![inner_expr.as_ref()],
         ExprEnum::BinOp(_, expr1, expr2) => exprs_to_check = vec![expr1.as_ref(), expr2.as_ref()],
@@ -119,35 +207,71 @@ fn check_expr_funcs(expr: &Expr, known_funcs: &Vec<FuncDecl>) {
         }
         ExprEnum::FunctionCall(func_name, exprs) => {
             exprs_to_check = exprs.iter().collect();
-            func_to_check = Some(FuncDecl {
-                name: func_name.clone(),
-                num_args: exprs.len(),
-            });
+            func_to_check = Some((func_name, exprs.len()));
         }
-        ExprEnum::Deref(inner_expr) | ExprEnum::Ref(inner_expr) | ExprEnum::Sizeof(inner_expr) => {
-            exprs_to_check = vec![inner_expr.as_ref()]
+        ExprEnum::IndirectCall(callee, exprs) => {
+            exprs_to_check = exprs.iter().collect();
+            exprs_to_check.push(callee.as_ref());
+        }
+        ExprEnum::FunctionPtr(func_name) => {
+            // the function is declared, since the parser only creates function pointers to declared functions
+            if known_funcs
+                .iter()
+                .any(|f| &f.name == func_name && !f.is_defined)
+            {
+                report_error(
+                    format!("function {} is declared but never defined", func_name),
+                    expr.pos,
+                )
+            }
         }
+        ExprEnum::Deref(inner_expr)
+        | ExprEnum::Ref(inner_expr)
+        | ExprEnum::Sizeof(inner_expr)
+        | ExprEnum::Member(inner_expr, _)
+        | ExprEnum::Cast(_, inner_expr) => exprs_to_check = vec![inner_expr.as_ref()],
         ExprEnum::ArrInitExpr(exprs) => exprs_to_check = exprs.iter().collect(),
     }
 
-    if let Some(func) = func_to_check {
-        if !known_funcs.contains(&func) {
+    if let Some((func_name, num_args)) = func_to_check {
+        if let Some(func_decl) = known_funcs.iter().find(|f| &f.name == func_name) {
+            if !func_decl.is_defined {
+                report_error(
+                    format!("function {} is declared but never defined", func_name),
+                    expr.pos,
+                )
+            }
+            if func_decl.param_types.len() != num_args {
+                report_error(
+                    format!(
+                        "function {} expects {} arguments, {} given",
+                        func_name,
+                        func_decl.param_types.len(),
+                        num_args
+                    ),
+                    expr.pos,
+                )
+            }
+        } else {
             let mut is_builtin = false;
 
             for func_decl in BUILTIN_FUNCTIONS {
-                if func_decl.name == func.name {
+                if func_decl.name == func_name {
                     is_builtin = true;
-                    if func.num_args != func_decl.num_args {
-                        err_display_no_source(format!(
-                            "{} expects exactly one argument, {} given",
-                            func_decl.name, func.num_args,
-                        ))
+                    if num_args != func_decl.num_args {
+                        report_error(
+                            format!(
+                                "{} expects {} arguments, {} given",
+                                func_decl.name, func_decl.num_args, num_args,
+                            ),
+                            expr.pos,
+                        )
                     }
                 }
             }
 
             if !is_builtin {
-                err_display_no_source(format!("undefined function: {}", &func.name))
+                report_error(format!("undefined function: {}", func_name), expr.pos)
             }
         }
     }
@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+use super::display::report_error;
+use crate::parser::{Program, Statement};
+use crate::tokenizer::source_cursor::SourcePtr;
+
+/// The check_labels function takes a program AST,
+/// and verifies that within each function:
+///    - No label is defined more than once
+///    - Every goto jumps to a label defined in the same function
+pub fn check_labels(program: &Program) {
+    for function in &program.functions {
+        let mut defined_labels = HashSet::new();
+        let mut goto_targets = Vec::new();
+        for stmt in &function.body {
+            collect_stmt_labels(stmt, &mut defined_labels, &mut goto_targets, &function.name);
+        }
+
+        for (target, pos) in goto_targets {
+            if !defined_labels.contains(target) {
+                report_error(
+                    format!(
+                        "label {} used but not defined in function {}",
+                        target, function.name
+                    ),
+                    pos,
+                );
+            }
+        }
+    }
+}
+
+fn collect_stmt_labels<'a>(
+    stmt: &'a Statement,
+    defined_labels: &mut HashSet<&'a String>,
+    goto_targets: &mut Vec<(&'a String, SourcePtr)>,
+    func_name: &String,
+) {
+    match stmt {
+        Statement::Labeled(label, inner_stmt, pos) => {
+            if !defined_labels.insert(label) {
+                report_error(
+                    format!("duplicate label {} in function {}", label, func_name),
+                    *pos,
+                );
+            }
+            collect_stmt_labels(inner_stmt, defined_labels, goto_targets, func_name);
+        }
+        Statement::Goto(label, pos) => goto_targets.push((label, *pos)),
+        Statement::CompoundStmt(stmts) => {
+            for stmt in stmts {
+                collect_stmt_labels(stmt, defined_labels, goto_targets, func_name);
+            }
+        }
+        Statement::If(_, taken_stmt, opt_not_taken_stmt) => {
+            collect_stmt_labels(taken_stmt, defined_labels, goto_targets, func_name);
+            if let Some(not_taken_stmt) = opt_not_taken_stmt {
+                collect_stmt_labels(not_taken_stmt, defined_labels, goto_targets, func_name);
+            }
+        }
+        Statement::While(_, body)
+        | Statement::DoWhile(body, _)
+        | Statement::For(_, _, _, body)
+        | Statement::Switch(_, body) => {
+            collect_stmt_labels(body, defined_labels, goto_targets, func_name)
+        }
+        Statement::Continue(_)
+        | Statement::Break(_)
+        | Statement::Return(_, _)
+        | Statement::Declare(_, _, _, _)
+        | Statement::EnumConsts(_)
+        | Statement::Case(_)
+        | Statement::Default(_)
+        | Statement::Expr(_)
+        | Statement::Empty => {}
+    }
+}
@@ -0,0 +1,128 @@
+use super::warnings::{warn, Warning};
+use crate::{
+    parser::{
+        expr_parser::{Expr, ExprEnum},
+        Program, Statement,
+    },
+    types::VarType,
+};
+
+/// The check_returns function takes a program AST,
+/// and warns about non-void functions whose end can be reached without returning a value.
+/// Like in C99, reaching the end of `main` returns 0, so it is not warned about.
+pub fn check_returns(program: &Program) {
+    for function in &program.functions {
+        if function.return_type != VarType::Void
+            && function.name != "main"
+            && can_complete_stmts(&function.body)
+        {
+            warn(
+                Warning::ReturnType,
+                format!("control reaches end of non-void function {}", function.name),
+                function.pos,
+            );
+        }
+    }
+}
+
+/// returns true if the end of the statements can be reached
+fn can_complete_stmts(stmts: &[Statement]) -> bool {
+    let mut is_reachable = true;
+    for stmt in stmts {
+        // labels can be jumped to, even if the statements before them cannot complete
+        if matches!(
+            stmt,
+            Statement::Labeled(_, _, _) | Statement::Case(_) | Statement::Default(_)
+        ) {
+            is_reachable = true;
+        }
+        if is_reachable {
+            is_reachable = can_complete(stmt);
+        }
+    }
+    is_reachable
+}
+
+/// returns true if execution can continue after the statement, rather than always jumping somewhere else
+fn can_complete(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Return(_, _)
+        | Statement::Goto(_, _)
+        | Statement::Break(_)
+        | Statement::Continue(_) => false,
+        Statement::Expr(expr) => !is_exit_call(expr),
+        Statement::Labeled(_, inner_stmt, _) => can_complete(inner_stmt),
+        Statement::CompoundStmt(stmts) => can_complete_stmts(stmts),
+        Statement::If(_, taken_stmt, not_taken_stmt) => match not_taken_stmt {
+            Some(not_taken_stmt) => can_complete(taken_stmt) || can_complete(not_taken_stmt),
+            None => true,
+        },
+        // an infinite loop can only be left with a break
+        Statement::While(ctrl_expr, body) => !is_always_true(ctrl_expr) || has_break(body),
+        Statement::For(_, ctrl_expr, _, body) => {
+            ctrl_expr.as_ref().is_some_and(|e| !is_always_true(e)) || has_break(body)
+        }
+        Statement::DoWhile(body, ctrl_expr) => {
+            has_break(body)
+                || (!is_always_true(ctrl_expr) && (can_complete(body) || has_continue(body)))
+        }
+        // without a default label, none of the cases may be taken
+        Statement::Switch(_, body) => has_break(body) || !has_default(body) || can_complete(body),
+        Statement::Declare(_, _, _, _)
+        | Statement::EnumConsts(_)
+        | Statement::Case(_)
+        | Statement::Default(_)
+        | Statement::Empty => true,
+    }
+}
+
+fn is_always_true(expr: &Expr) -> bool {
+    matches!(expr.content, ExprEnum::Int(value) if value != 0)
+}
+
+/// calls to `exit` never return
+fn is_exit_call(expr: &Expr) -> bool {
+    matches!(&expr.content, ExprEnum::FunctionCall(name, _) if name == "exit")
+}
+
+/// returns true if the statement contains a `break` which leaves the statement,
+/// rather than a loop or switch statement nested inside of it
+fn has_break(stmt: &Statement) -> bool {
+    find_stmt(stmt, &|s| matches!(s, Statement::Break(_)), false, false)
+}
+
+/// returns true if the statement contains a `continue` which belongs to an enclosing loop
+fn has_continue(stmt: &Statement) -> bool {
+    find_stmt(stmt, &|s| matches!(s, Statement::Continue(_)), false, true)
+}
+
+/// returns true if the statement contains a `default` label which belongs to an enclosing switch
+fn has_default(stmt: &Statement) -> bool {
+    find_stmt(stmt, &|s| matches!(s, Statement::Default(_)), true, false)
+}
+
+/// searches the statement for a statement matching the predicate. Nested loops and switch statements
+/// are only searched if `enter_loops` and `enter_switches` are set respectively.
+fn find_stmt(
+    stmt: &Statement,
+    pred: &dyn Fn(&Statement) -> bool,
+    enter_loops: bool,
+    enter_switches: bool,
+) -> bool {
+    if pred(stmt) {
+        return true;
+    }
+    let find = |s: &Statement| find_stmt(s, pred, enter_loops, enter_switches);
+    match stmt {
+        Statement::Labeled(_, inner_stmt, _) => find(inner_stmt),
+        Statement::CompoundStmt(stmts) => stmts.iter().any(find),
+        Statement::If(_, taken_stmt, not_taken_stmt) => {
+            find(taken_stmt) || not_taken_stmt.as_deref().is_some_and(find)
+        }
+        Statement::While(_, body) | Statement::DoWhile(body, _) | Statement::For(_, _, _, body) => {
+            enter_loops && find(body)
+        }
+        Statement::Switch(_, body) => enter_switches && find(body),
+        _ => false,
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 
 use crate::codegen::builtin_functions::BUILTIN_FUNCTIONS;
-use crate::parser::expr_parser::ExprEnum;
+use crate::parser::expr_parser::{BinOp, ExprEnum, UnOp};
 use crate::parser::{expr_parser::Expr, Program, Statement};
-use crate::types::{FundT, VarType};
+use crate::tokenizer::source_cursor::SourcePtr;
+use crate::types::{struct_defs::get_struct_member, FundT, VarType};
 
-use super::check_bin_op_exprs::get_binop_type;
-use super::display::err_display_no_source;
+use super::check_bin_op_exprs::{get_binop_type, get_common_type, promote_integer};
+use super::display::{err_display, recover};
+use super::warnings::{warn, Warning};
 
 #[derive(Debug)]
 pub struct CodeEnv {
@@ -14,13 +16,22 @@ pub struct CodeEnv {
     pub var_map_list: Vec<HashMap<String, VarType>>,
     /// a map of function name to the return type of the function
     pub func_ret_type_map: HashMap<String, VarType>,
+    /// a map of function name to the types of the function's parameters
+    pub func_param_types_map: HashMap<String, Vec<VarType>>,
+    /// the return type of the function being checked, None for global variables
+    pub curr_func_ret_type: Option<VarType>,
 }
 
 impl CodeEnv {
-    fn new(func_ret_type_map: HashMap<String, VarType>) -> Self {
+    fn new(
+        func_ret_type_map: HashMap<String, VarType>,
+        func_param_types_map: HashMap<String, Vec<VarType>>,
+    ) -> Self {
         CodeEnv {
             var_map_list: Vec::new(),
             func_ret_type_map,
+            func_param_types_map,
+            curr_func_ret_type: None,
         }
     }
 
@@ -52,12 +63,23 @@ fn resolve_variable_to_temp_name(name: &String, code_env: &CodeEnv) -> VarType {
 /// This function will also evaluate expressions using `sizeof`.
 pub fn check_types(program: &mut Program) {
     let mut func_ret_type_map = HashMap::new();
+    let mut func_param_types_map = HashMap::new();
     for function in &program.functions {
         func_ret_type_map.insert(function.name.clone(), function.return_type.clone());
+        func_param_types_map.insert(function.name.clone(), function.param_types());
     }
 
+    let mut global_code_env = CodeEnv::new(func_ret_type_map.clone(), func_param_types_map.clone());
+    global_code_env.var_map_list.push(HashMap::new());
+    for global_var in &mut program.global_vars {
+        check_stmt_types_with_recovery(global_var, &mut global_code_env);
+    }
+    let global_variable_map = global_code_env.var_map_list.pop().unwrap();
+
     for function in &mut program.functions {
-        let mut code_env = CodeEnv::new(func_ret_type_map.clone());
+        let mut code_env = CodeEnv::new(func_ret_type_map.clone(), func_param_types_map.clone());
+        code_env.curr_func_ret_type = Some(function.return_type.clone());
+        code_env.var_map_list.push(global_variable_map.clone());
         let mut this_scopes_variable_map: HashMap<String, VarType> = HashMap::new();
 
         for (arg_name, arg_type) in &function.args {
@@ -74,37 +96,82 @@ fn check_compound_stmt_types(stmts: &mut Vec<Statement>, code_env: &mut CodeEnv)
     code_env.var_map_list.push(this_scopes_variable_map);
 
     for statement in stmts {
-        check_stmt_types(statement, code_env);
+        check_stmt_types_with_recovery(statement, code_env);
     }
 
     code_env.var_map_list.pop();
 }
 
+/// checks the types of a statement, and if there is an error, continues on to the next statement
+/// so that its errors are reported as well
+fn check_stmt_types_with_recovery(stmt: &mut Statement, code_env: &mut CodeEnv) {
+    let num_scopes = code_env.var_map_list.len();
+    if recover(|| check_stmt_types(stmt, code_env)).is_none() {
+        code_env.var_map_list.truncate(num_scopes);
+    }
+}
+
 fn check_stmt_types(stmt: &mut Statement, code_env: &mut CodeEnv) {
     match stmt {
-        Statement::Continue | Statement::Break | Statement::Empty => {}
-        Statement::Return(expr) => {
+        Statement::Continue(_)
+        | Statement::Break(_)
+        | Statement::Empty
+        | Statement::Default(_)
+        | Statement::Goto(_, _)
+        | Statement::EnumConsts(_) => {}
+        Statement::Labeled(_, inner_stmt, _) => check_stmt_types(inner_stmt, code_env),
+        Statement::Return(None, pos) => {
+            let return_type = code_env.curr_func_ret_type.as_ref().unwrap();
+            if *return_type != VarType::Void {
+                err_display(
+                    format!("return with no value in function returning {}", return_type),
+                    *pos,
+                );
+            }
+        }
+        Statement::Return(Some(expr), pos) => {
+            if code_env.curr_func_ret_type == Some(VarType::Void) {
+                err_display("return with a value in function returning void", *pos);
+            }
             let returned_type = get_type(expr, code_env);
+            warn_if_narrowing(&code_env.curr_func_ret_type, &returned_type, *pos);
             match returned_type {
                 Some(VarType::Arr(_, _)) => {
-                    err_display_no_source("error: trying to return array from function");
+                    err_display("function cannot return array type", *pos);
+                }
+                Some(VarType::Struct(_)) => {
+                    err_display("function cannot return struct type", *pos);
                 }
                 Some(VarType::Fund(_)) | Some(VarType::Ptr(_)) | None => {}
+                // get_type rejects void values, and functions are only used through pointers
+                Some(VarType::Void) | Some(VarType::Func(_, _)) => unreachable!(),
             }
         }
-        Statement::Declare(var_name, optional_expr, expected_type) => {
+        Statement::Declare(var_name, optional_expr, expected_type, pos) => {
             if let Some(init_expr) = optional_expr {
-                if let ExprEnum::ArrInitExpr(_) = init_expr.content {
-                    // ok
-                } else if !are_assignment_compatible_types(
-                    &Some(expected_type.clone()),
-                    &get_type(init_expr, code_env),
-                ) {
-                    err_display_no_source(format!(
-                        "incompatible types in declaration of {}",
-                        var_name
-                    ))
-                }
+                // the variable is declared even if its initializer has an error,
+                // so that the statements using it can still be checked
+                recover(|| {
+                    if let ExprEnum::ArrInitExpr(_) = init_expr.content {
+                        // the elements of initializer lists are checked by get_type
+                        get_type(init_expr, code_env);
+                    } else if !are_assignment_compatible_types(
+                        &Some(expected_type.clone()),
+                        &get_type(init_expr, code_env),
+                    ) {
+                        err_display(
+                            format!(
+                                "incompatible types in declaration of {}: expected {}, found {}",
+                                var_name,
+                                expected_type,
+                                get_type_name(&init_expr.type_)
+                            ),
+                            *pos,
+                        )
+                    } else {
+                        warn_if_narrowing(&Some(expected_type.clone()), &init_expr.type_, *pos);
+                    }
+                });
             }
             let var_map_list = &mut code_env.var_map_list;
             let last_elem_index = var_map_list.len() - 1;
@@ -123,120 +190,327 @@ fn check_stmt_types(stmt: &mut Statement, code_env: &mut CodeEnv) {
                 check_stmt_types(not_taken_branch, code_env);
             }
         }
+        Statement::Switch(ctrl_expr, body) => {
+            match get_type(ctrl_expr, code_env) {
+                None => {}
+                Some(VarType::Fund(t)) if !t.is_float() => {}
+                Some(other) => err_display(
+                    format!("switch statement requires an integer, found {}", other),
+                    ctrl_expr.pos,
+                ),
+            }
+            check_stmt_types(body, code_env);
+        }
+        Statement::Case(expr) => match get_type(expr, code_env) {
+            None => {}
+            Some(VarType::Fund(t)) if !t.is_float() => {}
+            Some(other) => err_display(
+                format!("case label must be an integer, found {}", other),
+                expr.pos,
+            ),
+        },
         Statement::While(ctrl_expr, body) => {
             check_bool_expr(ctrl_expr, code_env);
             check_stmt_types(body, code_env);
         }
-        Statement::For(init_expr, ctrl_expr, post_expr, body) => {
+        Statement::DoWhile(body, ctrl_expr) => {
+            check_stmt_types(body, code_env);
+            check_bool_expr(ctrl_expr, code_env);
+        }
+        Statement::For(init_stmts, ctrl_expr, post_expr, body) => {
             let this_scopes_variable_map: HashMap<String, VarType> = HashMap::new();
             code_env.var_map_list.push(this_scopes_variable_map);
 
-            check_stmt_types(init_expr, code_env);
+            for init_stmt in init_stmts {
+                check_stmt_types(init_stmt, code_env);
+            }
             if let Some(ctrl_expr) = ctrl_expr {
                 check_bool_expr(ctrl_expr, code_env);
             }
             if let Some(post_expr) = post_expr {
-                get_type(post_expr, code_env);
+                get_possibly_void_type(post_expr, code_env);
             }
             check_stmt_types(body, code_env);
             code_env.var_map_list.pop();
         }
         Statement::Expr(expr) => {
-            get_type(expr, code_env);
+            // the value of an expression statement is discarded, so it may be a call to a void function
+            get_possibly_void_type(expr, code_env);
         }
     }
 }
 
 fn check_bool_expr(expr: &mut Expr, code_env: &CodeEnv) {
+    warn_if_assignment(expr);
     let t = get_type(expr, code_env);
     match t {
         None | Some(VarType::Fund(_)) | Some(VarType::Ptr(_)) => {
             // ok
         }
         other => {
-            err_display_no_source(format!(
-                "expression is not a boolean value, has type {:?}",
-                other
-            ));
+            err_display(
+                format!(
+                    "used a value of type {} where a scalar is required",
+                    get_type_name(&other)
+                ),
+                expr.pos,
+            );
         }
     }
 }
 
 /// This function computes the type of an expression, returning None
 /// if the type is "flexible" such as a literal 3 which can be either an int or a long.
-/// This function calls err_display_no_source (and thus exits) if it detects improper use as deemed by:
+/// This function calls err_display (and thus exits) if it detects improper use as deemed by:
 ///      adding pointer to something not an integer type
 ///      mixing number/pointer types
 ///      doing anything with an array which is not "index" or "ref"
 ///      assignment to something which is not an l_value
+///      using the result of a void function as a value
 pub fn get_type(expr: &mut Expr, code_env: &CodeEnv) -> Option<VarType> {
+    let type_ = get_possibly_void_type(expr, code_env);
+    if type_ == Some(VarType::Void) {
+        err_display("void value not ignored as it ought to be", expr.pos);
+    }
+    type_
+}
+
+/// Like `get_type`, but allows the expression to have type void,
+/// for expressions whose value is discarded.
+fn get_possibly_void_type(expr: &mut Expr, code_env: &CodeEnv) -> Option<VarType> {
+    let pos = expr.pos;
     let type_;
     match &mut expr.content {
         ExprEnum::Int(_) => {
-            type_ = None;
+            // integer literals are untyped, unless they were folded from a cast such as `(unsigned)1` or `1u`
+            type_ = expr.type_.clone();
+        }
+        ExprEnum::Float(_, t) => {
+            type_ = Some(VarType::Fund(*t));
         }
         ExprEnum::Var(var_name) => type_ = Some(resolve_variable_to_temp_name(var_name, code_env)),
-        ExprEnum::UnOp(_, inner) => {
+        ExprEnum::UnOp(op, inner) => {
             let inner_type = get_type(inner, code_env);
             match &inner_type {
                 Some(t) => match &t {
-                    VarType::Fund(_) => {
+                    VarType::Fund(f) if f.is_float() && matches!(op, UnOp::BitwiseComplement) => {
+                        err_display(format!("cannot apply unary operator {} to {}", op, f), pos);
+                    }
+                    // the result of `!` is an int, even for floating point operands
+                    VarType::Fund(_) if matches!(op, UnOp::Not) => {
+                        type_ = Some(VarType::Fund(FundT::Int));
+                    }
+                    VarType::Fund(f) if f.is_float() => {
                         type_ = inner_type;
                     }
+                    // integer promotion: operands smaller than an int are converted to int first,
+                    // so that `-c` is negative for an unsigned char c
+                    VarType::Fund(f) => {
+                        let promoted_type = VarType::Fund(promote_integer(*f));
+                        if promoted_type != *t {
+                            let inner_pos = inner.pos;
+                            let unpromoted = std::mem::replace(
+                                inner.as_mut(),
+                                Expr::new(ExprEnum::Int(0), inner_pos),
+                            );
+                            **inner = Expr {
+                                content: ExprEnum::Cast(
+                                    promoted_type.clone(),
+                                    Box::new(unpromoted),
+                                ),
+                                type_: Some(promoted_type.clone()),
+                                pos: inner_pos,
+                                is_parenthesized: false,
+                            };
+                        }
+                        type_ = Some(promoted_type);
+                    }
                     VarType::Ptr(_) => {
-                        err_display_no_source("cannot apply unary operator to pointer");
+                        err_display(format!("cannot apply unary operator {} to {}", op, t), pos);
                     }
                     VarType::Arr(_, _) => {
-                        err_display_no_source("cannot apply unary operator to array");
+                        err_display(format!("cannot apply unary operator {} to {}", op, t), pos);
                     }
+                    VarType::Struct(_) => {
+                        err_display(format!("cannot apply unary operator {} to {}", op, t), pos);
+                    }
+                    VarType::Void | VarType::Func(_, _) => unreachable!(),
                 },
                 None => {
                     type_ = inner_type;
                 }
             }
         }
+        ExprEnum::BinOp(BinOp::Comma, expr_1, expr_2) => {
+            // the value of the left operand is discarded, so it may be void
+            get_possibly_void_type(expr_1, code_env);
+            type_ = match get_possibly_void_type(expr_2, code_env) {
+                Some(VarType::Arr(inner, _)) => Some(VarType::Ptr(inner)),
+                t => t,
+            };
+        }
         ExprEnum::BinOp(op, expr_1, expr_2) => {
-            type_ = get_binop_type(*op, expr_1, expr_2, code_env);
+            type_ = get_binop_type(*op, expr_1, expr_2, code_env, pos);
         }
         ExprEnum::Ternary(ctrl_expr, expr_1, expr_2) => {
+            warn_if_assignment(ctrl_expr);
             match get_type(ctrl_expr, code_env) {
                 None | Some(VarType::Fund(_)) => {
                     // ok
                 }
-                _ => {
-                    err_display_no_source(
-                        "control expression of ternary must be boolean compatible",
-                    );
-                }
+                other => err_display(
+                    format!(
+                        "used a value of type {} where a scalar is required",
+                        get_type_name(&other)
+                    ),
+                    ctrl_expr.pos,
+                ),
             }
 
             let t1 = get_type(expr_1, code_env);
             let t2 = get_type(expr_2, code_env);
             if !are_interchangable_types(&t1, &t2) {
-                err_display_no_source("cannot mix types in ternary expression");
+                err_display(
+                    format!(
+                        "type mismatch in conditional expression ({} and {})",
+                        get_type_name(&t1),
+                        get_type_name(&t2)
+                    ),
+                    pos,
+                );
             }
-            type_ = t1;
+            type_ = match (&t1, &t2) {
+                (Some(VarType::Fund(_)), _) | (_, Some(VarType::Fund(_))) => {
+                    get_common_type(&t1, &t2)
+                }
+                _ => t1,
+            };
         }
         ExprEnum::FunctionCall(func_name, exprs) => {
-            for expr in exprs {
-                get_type(expr, code_env);
+            for (index, expr) in exprs.iter_mut().enumerate() {
+                let arg_type = get_type(expr, code_env);
+                if let Some(VarType::Struct(_)) = arg_type {
+                    err_display(
+                        format!(
+                            "passing structs by value is not supported (in call to {})",
+                            func_name
+                        ),
+                        pos,
+                    );
+                }
+                // builtin functions do not have parameter types
+                if let Some(param_types) = code_env.func_param_types_map.get(func_name) {
+                    let param_type = Some(param_types[index].clone());
+                    if !are_assignment_compatible_types(&param_type, &arg_type) {
+                        err_display(
+                            format!(
+                                "incompatible type for argument {} of {}: expected {}, found {}",
+                                index + 1,
+                                func_name,
+                                param_types[index],
+                                arg_type.map_or("an integer literal".to_owned(), |t| t.to_string())
+                            ),
+                            pos,
+                        );
+                    }
+                    warn_if_narrowing(&param_type, &arg_type, expr.pos);
+                }
             }
 
             type_ = Some(code_env.get_func_ret_type(func_name));
         }
+        ExprEnum::IndirectCall(callee, exprs) => {
+            let callee_type = get_type(callee, code_env);
+            let (ret_type, param_types) = match &callee_type {
+                Some(VarType::Ptr(inner)) => match inner.as_ref() {
+                    VarType::Func(ret_type, param_types) => (ret_type, param_types),
+                    _ => err_display(
+                        format!(
+                            "called object of type {} is not a function or function pointer",
+                            callee_type.as_ref().unwrap()
+                        ),
+                        pos,
+                    ),
+                },
+                Some(t) => err_display(
+                    format!(
+                        "called object of type {} is not a function or function pointer",
+                        t
+                    ),
+                    pos,
+                ),
+                None => err_display(
+                    "called object of type int is not a function or function pointer",
+                    pos,
+                ),
+            };
+            if param_types.len() != exprs.len() {
+                err_display(
+                    format!(
+                        "function pointer of type {} expects {} arguments, {} given",
+                        callee_type.as_ref().unwrap(),
+                        param_types.len(),
+                        exprs.len()
+                    ),
+                    pos,
+                )
+            }
+            for (index, expr) in exprs.iter_mut().enumerate() {
+                let arg_type = get_type(expr, code_env);
+                let param_type = Some(param_types[index].clone());
+                if !are_assignment_compatible_types(&param_type, &arg_type) {
+                    err_display(format!(
+                        "incompatible type for argument {} of call through function pointer: expected {}, found {}",
+                        index + 1,
+                        param_types[index],
+                        arg_type.map_or("an integer literal".to_owned(), |t| t.to_string())
+                    ), pos);
+                }
+                warn_if_narrowing(&param_type, &arg_type, expr.pos);
+            }
+
+            type_ = Some(*ret_type.clone());
+        }
+        ExprEnum::FunctionPtr(func_name) => {
+            let ret_type = code_env.get_func_ret_type(func_name);
+            let param_types = code_env.func_param_types_map[func_name].clone();
+            type_ = Some(VarType::Ptr(Box::new(VarType::Func(
+                Box::new(ret_type),
+                param_types,
+            ))));
+        }
         ExprEnum::Deref(inner) => {
             let inner_type = get_type(inner, code_env);
-            if let Some(VarType::Ptr(t)) = inner_type {
+            if inner_type.as_ref().is_some_and(|t| t.is_func_ptr()) {
+                // dereferencing a function pointer gives the function, which becomes a pointer again
+                type_ = inner_type;
+            } else if let Some(VarType::Ptr(t)) = inner_type {
+                if *t == VarType::Void {
+                    err_display("tried to dereference a void pointer.", pos)
+                }
                 type_ = Some(*t);
             } else if let Some(VarType::Arr(t, _)) = inner_type {
                 type_ = Some(*t);
             } else {
-                err_display_no_source("tried to dereference something that isn't a pointer.")
+                err_display(
+                    format!(
+                        "invalid type argument of unary * (have {})",
+                        get_type_name(&inner_type)
+                    ),
+                    pos,
+                )
             }
         }
+        ExprEnum::Ref(inner) if matches!(inner.content, ExprEnum::FunctionPtr(_)) => {
+            // taking the address of a function gives the same pointer as using its name
+            type_ = get_type(inner, code_env);
+        }
         ExprEnum::Ref(inner) => {
             if !is_l_value(inner) {
-                err_display_no_source("tried to take a reference to something that isn't a lvalue.")
+                err_display(
+                    "tried to take a reference to something that isn't a lvalue.",
+                    pos,
+                )
             }
             let inner_type = get_type(inner, code_env).unwrap();
 
@@ -247,17 +521,82 @@ pub fn get_type(expr: &mut Expr, code_env: &CodeEnv) -> Option<VarType> {
         | ExprEnum::PrefixDec(inner)
         | ExprEnum::PrefixInc(inner) => {
             if !is_l_value(inner) {
-                err_display_no_source("tried use ++ or -- on something that isn't a lvalue.")
+                err_display("tried use ++ or -- on something that isn't a lvalue.", pos)
             }
-            type_ = Some(get_type(inner, code_env).unwrap());
+            let inner_type = get_type(inner, code_env).unwrap();
+            if inner_type.is_void_ptr() {
+                err_display("tried use ++ or -- on a void pointer.", pos)
+            }
+            if inner_type.is_func_ptr() {
+                err_display("tried use ++ or -- on a function pointer.", pos)
+            }
+            type_ = Some(inner_type);
         }
         ExprEnum::Sizeof(inner_expr) => {
             let inner_type = get_type(inner_expr, code_env);
             let inner_type = inner_type.unwrap_or(VarType::Fund(FundT::Int));
-            *expr = Expr::new(ExprEnum::Int(inner_type.num_bytes() as i64));
-            type_ = Some(VarType::Fund(crate::types::FundT::Long));
+            *expr = Expr::new(ExprEnum::Int(inner_type.num_bytes() as i64), pos);
+            type_ = Some(VarType::Fund(FundT::ULong));
+        }
+        ExprEnum::SizeofType(t) => {
+            if !t.is_complete() {
+                err_display(format!("sizeof applied to incomplete type {}", t), pos);
+            }
+            *expr = Expr::new(ExprEnum::Int(t.num_bytes() as i64), pos);
+            type_ = Some(VarType::Fund(FundT::ULong));
+        }
+        ExprEnum::Member(inner, member_name) => match get_type(inner, code_env) {
+            Some(VarType::Struct(tag)) => match get_struct_member(&tag, member_name) {
+                Some(member) => type_ = Some(member.type_),
+                None => err_display(
+                    format!("struct {} has no member named {}", tag, member_name),
+                    pos,
+                ),
+            },
+            Some(other) => err_display(
+                format!(
+                    "request for member {} in something that is not a struct (has type {})",
+                    member_name, other
+                ),
+                pos,
+            ),
+            None => unreachable!(),
+        },
+        ExprEnum::ArrInitExpr(exprs) => {
+            for expr in exprs {
+                get_type(expr, code_env);
+            }
+            type_ = None;
+        }
+        ExprEnum::Cast(target_type, inner) => {
+            if *target_type == VarType::Void {
+                // casting to void explicitly discards the value
+                get_possibly_void_type(inner, code_env);
+            } else {
+                // integers and pointers can be converted to each other, arrays decay to pointers
+                let inner_type = get_type(inner, code_env).unwrap_or(VarType::Fund(FundT::Int));
+                match (&*target_type, &inner_type) {
+                    // floating point values cannot be converted to or from pointers
+                    (VarType::Ptr(_), t) | (t, VarType::Ptr(_) | VarType::Arr(_, _))
+                        if t.is_float() =>
+                    {
+                        err_display(
+                            format!("cannot cast {} to {}", inner_type, target_type),
+                            pos,
+                        )
+                    }
+                    (
+                        VarType::Fund(_) | VarType::Ptr(_),
+                        VarType::Fund(_) | VarType::Ptr(_) | VarType::Arr(_, _),
+                    ) => {}
+                    _ => err_display(
+                        format!("cannot cast {} to {}", inner_type, target_type),
+                        pos,
+                    ),
+                }
+            }
+            type_ = Some(target_type.clone());
         }
-        ExprEnum::ArrInitExpr(_) => type_ = None,
         ExprEnum::StaticStrPtr(_) => {
             type_ = Some(VarType::Ptr(Box::new(VarType::Fund(FundT::Char))));
         }
@@ -266,20 +605,72 @@ pub fn get_type(expr: &mut Expr, code_env: &CodeEnv) -> Option<VarType> {
     type_
 }
 
+/// warns about an assignment used as a condition, such as `if (a = b)`, which was probably meant to be `==`
+/// An extra pair of parentheses, as in `if ((a = b))`, shows that the assignment is intended.
+fn warn_if_assignment(ctrl_expr: &Expr) {
+    if ctrl_expr.is_parenthesized {
+        return;
+    }
+    if let ExprEnum::BinOp(BinOp::Assign, _, _) = ctrl_expr.content {
+        warn(
+            Warning::Parentheses,
+            "suggest parentheses around assignment used as truth value",
+            ctrl_expr.pos,
+        );
+    }
+}
+
+/// warns about an implicit conversion from `source` to `target` which may change the value,
+/// such as from a `long` to an `int`, or from a `double` to an `int` or a `float`
+pub fn warn_if_narrowing(target: &Option<VarType>, source: &Option<VarType>, pos: SourcePtr) {
+    let (Some(VarType::Fund(target_t)), Some(VarType::Fund(source_t))) = (target, source) else {
+        // integer literals have no type, and conversions involving pointers are checked elsewhere
+        return;
+    };
+    let is_narrowing = match (target_t.is_float(), source_t.is_float()) {
+        (false, true) => true,
+        // an integer may have more significant digits than the floating point type can hold
+        (true, false) => source_t.to_size().num_bytes() >= target_t.to_size().num_bytes(),
+        (true, true) | (false, false) => {
+            source_t.to_size().num_bytes() > target_t.to_size().num_bytes()
+        }
+    };
+    if is_narrowing {
+        warn(
+            Warning::Conversion,
+            format!(
+                "conversion from {} to {} may change value",
+                source_t, target_t
+            ),
+            pos,
+        );
+    }
+}
+
+/// the name of a type in error messages, where untyped integer literals are ints
+pub fn get_type_name(t: &Option<VarType>) -> String {
+    t.as_ref().map_or("int".to_owned(), |t| t.to_string())
+}
+
 pub fn is_l_value(expr: &Expr) -> bool {
     match expr.content {
-        ExprEnum::Var(_) | ExprEnum::Deref(_) => true,
+        ExprEnum::Var(_) | ExprEnum::Deref(_) | ExprEnum::Member(_, _) => true,
         ExprEnum::Int(_)
+        | ExprEnum::Float(_, _)
         | ExprEnum::BinOp(_, _, _)
         | ExprEnum::UnOp(_, _)
         | ExprEnum::Ternary(_, _, _)
         | ExprEnum::FunctionCall(_, _)
+        | ExprEnum::IndirectCall(_, _)
+        | ExprEnum::FunctionPtr(_)
         | ExprEnum::Ref(_)
         | ExprEnum::PostfixDec(_)
         | ExprEnum::PostfixInc(_)
         | ExprEnum::PrefixDec(_)
         | ExprEnum::PrefixInc(_)
         | ExprEnum::Sizeof(_)
+        | ExprEnum::Cast(_, _)
+        | ExprEnum::SizeofType(_)
         | ExprEnum::ArrInitExpr(_)
         | ExprEnum::StaticStrPtr(_) => false,
     }
@@ -292,13 +683,21 @@ pub fn are_interchangable_types(t1: &Option<VarType>, t2: &Option<VarType>) -> b
             VarType::Fund(_) => true,
             VarType::Ptr(_) => false,
             VarType::Arr(_, _) => false,
+            VarType::Struct(_) => false,
+            VarType::Void | VarType::Func(_, _) => false,
         },
         (Some(inner_t1), Some(inner_t2)) => match (inner_t1, inner_t2) {
+            (VarType::Struct(_), _) | (_, VarType::Struct(_)) => false,
+            (VarType::Void, _) | (_, VarType::Void) => false,
+            (VarType::Func(_, _), _) | (_, VarType::Func(_, _)) => false,
             (VarType::Fund(_), VarType::Fund(_)) => true,
             (VarType::Fund(_), VarType::Ptr(_)) => false,
             (VarType::Fund(_), VarType::Arr(_, _)) => false,
             (VarType::Ptr(_), VarType::Fund(_)) => false,
-            (VarType::Ptr(t1), VarType::Ptr(t2)) => t1 == t2,
+            (VarType::Ptr(t1), VarType::Ptr(t2)) => {
+                // a void pointer can be compared with any other pointer
+                t1 == t2 || **t1 == VarType::Void || **t2 == VarType::Void
+            }
             (VarType::Ptr(_), VarType::Arr(_, _)) => false,
             (VarType::Arr(_, _), VarType::Fund(_)) => false,
             (VarType::Arr(_, _), VarType::Ptr(_)) => false,
@@ -315,8 +714,16 @@ pub fn are_assignment_compatible_types(t1: &Option<VarType>, t2: &Option<VarType
             VarType::Fund(_) => true,
             VarType::Ptr(_) => false,
             VarType::Arr(_, _) => false,
+            VarType::Struct(_) => false,
+            VarType::Void | VarType::Func(_, _) => false,
         },
         (Some(inner_t1), Some(inner_t2)) => match (inner_t1, inner_t2) {
+            (VarType::Struct(tag1), VarType::Struct(tag2)) => tag1 == tag2,
+            (VarType::Struct(_), _) | (_, VarType::Struct(_)) => false,
+            (VarType::Void, _) | (_, VarType::Void) => false,
+            (VarType::Func(_, _), _) | (_, VarType::Func(_, _)) => false,
+            // like gcc, pointers of different types may be assigned to each other (gcc only warns).
+            // void pointers are the generic pointer type, and convert to and from any other pointer type.
             (VarType::Fund(_), VarType::Fund(_))
             | (VarType::Ptr(_), VarType::Ptr(_))
             | (VarType::Ptr(_), VarType::Arr(_, _)) => true,
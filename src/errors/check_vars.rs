@@ -1,111 +1,224 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use super::display::err_display_no_source;
-use crate::parser::{
-    expr_parser::{Expr, ExprEnum},
-    Program, Statement,
+use super::{
+    display::report_error,
+    warnings::{warn, Warning},
 };
+use crate::{
+    parser::{
+        expr_parser::{Expr, ExprEnum},
+        Program, Statement,
+    },
+    tokenizer::source_cursor::SourcePtr,
+};
+
+/// a map of the names of the variables in scope to their index in the list of local variables,
+/// or to None for global variables and enumerators, whose uses are not tracked
+type KnownVars = HashMap<String, Option<usize>>;
+
+/// a parameter or local variable of a function, which is warned about if it is never used
+struct LocalVar {
+    name: String,
+    pos: SourcePtr,
+    is_param: bool,
+    is_used: bool,
+}
 
 /// The check_vars function takes a program AST,
 /// and verifies that there are no usages of undeclared variables
-/// or doubly-declared variables.
+/// or doubly-declared variables. It also warns about local variables and parameters which are never used.
 pub fn check_vars(program: &Program) {
+    let mut global_var_names = KnownVars::new();
+    let mut global_vars_decl_scope = HashSet::new();
+    for global_var in &program.global_vars {
+        check_stmt_vars(
+            global_var,
+            &mut global_var_names,
+            &mut global_vars_decl_scope,
+            &mut None,
+        );
+    }
+
     for function in &program.functions {
         let body: &Vec<Statement> = &function.body;
-        let mut known_vars = HashSet::new();
+        let mut known_vars = global_var_names.clone();
+        let mut locals = Vec::new();
         for (arg_name, _) in &function.args {
-            known_vars.insert(arg_name.clone());
+            known_vars.insert(arg_name.clone(), Some(locals.len()));
+            locals.push(LocalVar {
+                name: arg_name.clone(),
+                pos: function.pos,
+                is_param: true,
+                is_used: false,
+            });
         }
 
-        check_stmts_vars(body, known_vars);
+        let mut locals = Some(locals);
+        check_stmts_vars(body, known_vars, &mut locals);
+
+        for local in locals.unwrap() {
+            match (local.is_used, local.is_param) {
+                (true, _) => {}
+                (false, true) => warn(
+                    Warning::UnusedParameter,
+                    format!(
+                        "unused parameter {} of function {}",
+                        local.name, function.name
+                    ),
+                    local.pos,
+                ),
+                (false, false) => warn(
+                    Warning::UnusedVariable,
+                    format!("unused variable {}", local.name),
+                    local.pos,
+                ),
+            }
+        }
     }
 }
 
-fn check_stmts_vars(stmts: &Vec<Statement>, mut known_var_names: HashSet<String>) {
-    // note that known_var_names is a owned hashset, not a reference, because
-    // this function add to the hashset, but it should not change the hashset owned
-    // by the caller. The caller should clone a known_var_names hashset before passing it
+fn check_stmts_vars(
+    stmts: &Vec<Statement>,
+    mut known_var_names: KnownVars,
+    locals: &mut Option<Vec<LocalVar>>,
+) {
+    // note that known_var_names is a owned map, not a reference, because
+    // this function add to the map, but it should not change the map owned
+    // by the caller. The caller should clone a known_var_names map before passing it
     // into here
     let mut vars_decl_local_scope = HashSet::new();
     for stmt in stmts {
-        check_stmt_vars(stmt, &mut known_var_names, &mut vars_decl_local_scope)
+        check_stmt_vars(
+            stmt,
+            &mut known_var_names,
+            &mut vars_decl_local_scope,
+            locals,
+        )
     }
 }
 
 fn check_stmt_vars(
     stmt: &Statement,
-    known_var_names: &mut HashSet<String>,
+    known_var_names: &mut KnownVars,
     vars_decl_local_scope: &mut HashSet<String>,
+    locals: &mut Option<Vec<LocalVar>>,
 ) {
     match stmt {
-        Statement::Continue | Statement::Break | Statement::Empty => {}
-        Statement::Return(expr) => check_expr_vars(expr, known_var_names),
-        Statement::Declare(var_name, optional_expr, _) => {
+        Statement::Continue(_)
+        | Statement::Break(_)
+        | Statement::Empty
+        | Statement::Default(_)
+        | Statement::Goto(_, _) => {}
+        Statement::Labeled(_, inner_stmt, _) => {
+            check_stmt_vars(inner_stmt, known_var_names, vars_decl_local_scope, locals)
+        }
+        Statement::Return(Some(expr), _) => check_expr_vars(expr, known_var_names, locals),
+        Statement::Return(None, _) => {}
+        Statement::Declare(var_name, optional_expr, _, pos) => {
             if let Some(expr) = optional_expr {
-                check_expr_vars(expr, known_var_names);
+                check_expr_vars(expr, known_var_names, locals);
             }
             if vars_decl_local_scope.contains(var_name) {
-                err_display_no_source(format!("variable declared twice: {}", var_name));
+                // the name keeps referring to the first declaration, and the rejected one is not
+                // tracked, so that it is not also warned about as unused
+                report_error(format!("variable declared twice: {}", var_name), *pos);
+                return;
             }
-            known_var_names.insert(var_name.clone());
+            // local variables are tracked so that they can be warned about if they are never used
+            let index = locals.as_mut().map(|locals| {
+                locals.push(LocalVar {
+                    name: var_name.clone(),
+                    pos: *pos,
+                    is_param: false,
+                    is_used: false,
+                });
+                locals.len() - 1
+            });
+            known_var_names.insert(var_name.clone(), index);
             vars_decl_local_scope.insert(var_name.clone());
         }
+        Statement::EnumConsts(enum_const_names) => {
+            // enumerators share their namespace with variables
+            for (name, pos) in enum_const_names {
+                if vars_decl_local_scope.contains(name) {
+                    report_error(format!("enumerator declared twice: {}", name), *pos);
+                }
+                known_var_names.insert(name.clone(), None);
+                vars_decl_local_scope.insert(name.clone());
+            }
+        }
         Statement::CompoundStmt(inner_stmts) => {
-            check_stmts_vars(inner_stmts, known_var_names.clone())
+            check_stmts_vars(inner_stmts, known_var_names.clone(), locals)
         }
         Statement::If(ctrl_expr, taken_expr, option_not_taken_expr) => {
-            check_expr_vars(ctrl_expr, known_var_names);
-            check_stmt_vars(taken_expr, known_var_names, vars_decl_local_scope);
+            check_expr_vars(ctrl_expr, known_var_names, locals);
+            check_stmt_vars(taken_expr, known_var_names, vars_decl_local_scope, locals);
             if let Some(not_taken_expr) = option_not_taken_expr {
-                check_stmt_vars(not_taken_expr, known_var_names, vars_decl_local_scope);
+                check_stmt_vars(
+                    not_taken_expr,
+                    known_var_names,
+                    vars_decl_local_scope,
+                    locals,
+                );
             }
         }
+        Statement::Switch(ctrl_expr, body) => {
+            check_expr_vars(ctrl_expr, known_var_names, locals);
+            check_stmt_vars(body, known_var_names, vars_decl_local_scope, locals);
+        }
+        Statement::Case(expr) => check_expr_vars(expr, known_var_names, locals),
         Statement::While(ctrl_expr, body) => {
-            check_expr_vars(ctrl_expr, known_var_names);
-            check_stmt_vars(body, known_var_names, vars_decl_local_scope);
+            check_expr_vars(ctrl_expr, known_var_names, locals);
+            check_stmt_vars(body, known_var_names, vars_decl_local_scope, locals);
+        }
+        Statement::DoWhile(body, ctrl_expr) => {
+            check_stmt_vars(body, known_var_names, vars_decl_local_scope, locals);
+            check_expr_vars(ctrl_expr, known_var_names, locals);
         }
-        Statement::For(init_expr, ctrl_expr, post_expr, body) => check_for_loop_vars(
-            init_expr,
-            ctrl_expr.as_ref().unwrap_or(&Expr::new(ExprEnum::Int(1))),
-            post_expr.as_ref().unwrap_or(&Expr::new(ExprEnum::Int(0))),
+        Statement::For(init_stmts, ctrl_expr, post_expr, body) => check_for_loop_vars(
+            init_stmts,
+            ctrl_expr.as_ref(),
+            post_expr.as_ref(),
             body,
             known_var_names.clone(),
             vars_decl_local_scope,
+            locals,
         ),
-        Statement::Expr(expr) => check_expr_vars(expr, known_var_names),
+        Statement::Expr(expr) => check_expr_vars(expr, known_var_names, locals),
     }
 }
 
 fn check_for_loop_vars(
-    init_expr: &Statement,
-    ctrl_expr: &Expr,
-    post_expr: &Expr,
+    init_stmts: &Vec<Statement>,
+    ctrl_expr: Option<&Expr>,
+    post_expr: Option<&Expr>,
     body: &Statement,
-    mut known_var_names: HashSet<String>,
+    mut known_var_names: KnownVars,
     vars_decl_local_scope: &mut HashSet<String>,
+    locals: &mut Option<Vec<LocalVar>>,
 ) {
-    match init_expr {
-        Statement::Declare(var_name, optional_expr, _) => {
-            if let Some(decl_expr) = optional_expr {
-                check_expr_vars(decl_expr, &known_var_names);
-            }
-            known_var_names.insert(var_name.clone());
-        }
-
-        Statement::Expr(decl_expr) => {
-            check_expr_vars(decl_expr, &known_var_names);
+    // the variables declared in the initial clause are in a scope of their own
+    let mut vars_decl_header_scope = HashSet::new();
+    for init_stmt in init_stmts {
+        match init_stmt {
+            Statement::Declare(_, _, _, _) | Statement::Expr(_) => check_stmt_vars(
+                init_stmt,
+                &mut known_var_names,
+                &mut vars_decl_header_scope,
+                locals,
+            ),
+            _ => unreachable!(),
         }
-        Statement::Empty => {}
-        _ => unreachable!(),
     }
 
-    check_expr_vars(ctrl_expr, &known_var_names);
-    check_expr_vars(post_expr, &known_var_names);
+    for expr in ctrl_expr.into_iter().chain(post_expr) {
+        check_expr_vars(expr, &known_var_names, locals);
+    }
 
-    check_stmt_vars(body, &mut known_var_names, vars_decl_local_scope);
+    check_stmt_vars(body, &mut known_var_names, vars_decl_local_scope, locals);
 }
 
-fn check_expr_vars(expr: &Expr, known_var_names: &HashSet<String>) {
+fn check_expr_vars(expr: &Expr, known_var_names: &KnownVars, locals: &mut Option<Vec<LocalVar>>) {
     // note that known_var_names is a reference, not a owned hashset, because this
     // function does not modify it.
 
@@ -113,7 +226,10 @@ fn check_expr_vars(expr: &Expr, known_var_names: &HashSet<String>) {
     let mut exprs_to_check = Vec::new();
 
     match &expr.content {
-        ExprEnum::Int(_) | ExprEnum::StaticStrPtr(_) => {}
+        ExprEnum::Int(_)
+        | ExprEnum::Float(_, _)
+        | ExprEnum::StaticStrPtr(_)
+        | ExprEnum::SizeofType(_) => {}
         ExprEnum::Var(var_name) => var_name_to_check = Some(var_name),
         ExprEnum::UnOp(_, inner_expr) => exprs_to_check = vec![inner_expr.as_ref()],
         ExprEnum::BinOp(_, expr1, expr2) => exprs_to_check = vec![expr1.as_ref(), expr2.as_ref()],
@@ -121,6 +237,11 @@ fn check_expr_vars(expr: &Expr, known_var_names: &HashSet<String>) {
             exprs_to_check = vec![expr1.as_ref(), expr2.as_ref(), expr3.as_ref()]
         }
         ExprEnum::FunctionCall(_, exprs) => exprs_to_check = exprs.iter().collect(),
+        ExprEnum::IndirectCall(callee, exprs) => {
+            exprs_to_check = exprs.iter().collect();
+            exprs_to_check.push(callee);
+        }
+        ExprEnum::FunctionPtr(_) => {}
         ExprEnum::Deref(expr) => exprs_to_check = vec![expr],
         ExprEnum::Ref(expr) => exprs_to_check = vec![expr],
         ExprEnum::PostfixDec(var_name) => exprs_to_check = vec![var_name],
@@ -128,15 +249,19 @@ fn check_expr_vars(expr: &Expr, known_var_names: &HashSet<String>) {
         ExprEnum::PrefixDec(var_name) => exprs_to_check = vec![var_name],
         ExprEnum::PrefixInc(var_name) => exprs_to_check = vec![var_name],
         ExprEnum::Sizeof(inner_expr) => exprs_to_check = vec![inner_expr],
+        ExprEnum::Member(inner_expr, _) => exprs_to_check = vec![inner_expr],
+        ExprEnum::Cast(_, inner_expr) => exprs_to_check = vec![inner_expr],
         ExprEnum::ArrInitExpr(exprs) => exprs_to_check = exprs.iter().collect(),
     }
 
     if let Some(var_name) = var_name_to_check {
-        if !known_var_names.contains(var_name) {
-            err_display_no_source(format!("undeclared variable: {}", var_name));
+        match known_var_names.get(var_name) {
+            None => report_error(format!("undeclared variable: {}", var_name), expr.pos),
+            Some(Some(index)) => locals.as_mut().unwrap()[*index].is_used = true,
+            Some(None) => {}
         }
     }
     for expr in exprs_to_check {
-        check_expr_vars(expr, known_var_names);
+        check_expr_vars(expr, known_var_names, locals);
     }
 }
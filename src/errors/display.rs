@@ -1,18 +1,198 @@
-use std::process::exit;
+use std::{
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    process::exit,
+    ptr::addr_of_mut,
+};
 
-use crate::tokenizer::source_cursor::SourcePtr;
+use crate::{preprocessor::line_map::get_original_location, tokenizer::source_cursor::SourcePtr};
 
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+struct Diagnostic {
+    severity: Severity,
+    msg: String,
+    src_ptr: Option<SourcePtr>, // None for errors which do not belong to any place in the source code
+}
+
+// the errors and warnings reported so far, in the order they were reported
+static mut DIAGNOSTICS: Vec<Diagnostic> = Vec::new();
+// the number of diagnostics which have already been printed
+static mut NUM_PRINTED: usize = 0;
+// the number of calls to `recover` which are currently running
+static mut NUM_RECOVERY_POINTS: usize = 0;
+
+/// the payload used to unwind from an error to the innermost call to `recover`
+struct RecoverableError;
+
+/// records an error, and continues compiling so that later errors are reported as well.
+/// When the errors are printed, each error is shown along with the line of source code it occurred on, such as
+/// ```text
+/// test.c:3:14: error: undeclared variable: y
+///     3 |     return x + y;
+///       |                ^
+/// ```
+pub fn report_error<S: Into<String>>(msg: S, src_ptr: SourcePtr) {
+    push_diagnostic(Severity::Error, msg.into(), Some(src_ptr));
+}
+
+/// for errors which do not belong to any particular place in the source code, such as a missing `main`
+pub fn report_error_no_source<S: Into<String>>(msg: S) {
+    push_diagnostic(Severity::Error, msg.into(), None);
+}
+
+/// records a warning, which is printed along with the errors but does not stop the compilation.
+/// Warnings should be reported through `warnings::warn`, which decides whether they are enabled.
+pub fn report_warning<S: Into<String>>(msg: S, src_ptr: SourcePtr) {
+    push_diagnostic(Severity::Warning, msg.into(), Some(src_ptr));
+}
+
+/// records an error, and abandons the statement or declaration being processed by unwinding to the
+/// innermost call to `recover`. Outside of `recover`, all the errors are printed and the compiler exits.
 pub fn err_display<S: Into<String>>(msg: S, src_ptr: SourcePtr) -> ! {
-    eprintln!(
-        "Line {} col {} error: {}",
-        src_ptr.line,
-        src_ptr.col,
-        msg.into()
-    );
-    exit(1)
+    report_error(msg, src_ptr);
+    abandon()
 }
 
+/// like `err_display`, for errors which do not belong to any particular place in the source code
 pub fn err_display_no_source<S: Into<String>>(msg: S) -> ! {
-    eprintln!("error: {}", msg.into());
+    report_error_no_source(msg);
+    abandon()
+}
+
+/// runs `f`, returning None if it was abandoned because of an error reported by `err_display`.
+/// This is used as an error recovery point, so that the caller can skip over the erroneous code
+/// and keep looking for more errors.
+pub fn recover<T>(f: impl FnOnce() -> T) -> Option<T> {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        *addr_of_mut!(NUM_RECOVERY_POINTS) += 1;
+    }
+    let result = catch_unwind(AssertUnwindSafe(f));
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        *addr_of_mut!(NUM_RECOVERY_POINTS) -= 1;
+    }
+    match result {
+        Ok(value) => Some(value),
+        Err(payload) if payload.is::<RecoverableError>() => None,
+        // a bug in the compiler rather than an error in the program
+        Err(payload) => resume_unwind(payload),
+    }
+}
+
+/// prints the errors and warnings reported since the last call. If there are any errors,
+/// prints the number of errors and warnings, and exits.
+pub fn exit_if_errors() {
+    let diagnostics = unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        &*addr_of_mut!(DIAGNOSTICS)
+    };
+    let num_printed = unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        &mut *addr_of_mut!(NUM_PRINTED)
+    };
+    for diagnostic in &diagnostics[*num_printed..] {
+        let severity = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match diagnostic.src_ptr {
+            Some(src_ptr) => print_diagnostic(severity, &diagnostic.msg, src_ptr),
+            None => eprintln!("{}: {}", severity, diagnostic.msg),
+        }
+    }
+    *num_printed = diagnostics.len();
+
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let (num_errors, num_warnings) = (count(Severity::Error), count(Severity::Warning));
+    if num_errors == 0 {
+        return;
+    }
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    if num_warnings > 0 {
+        eprint!("{} warning{} and ", num_warnings, plural(num_warnings));
+    }
+    eprintln!("{} error{} generated.", num_errors, plural(num_errors));
     exit(1)
 }
+
+fn push_diagnostic(severity: Severity, msg: String, src_ptr: Option<SourcePtr>) {
+    let diagnostic = Diagnostic {
+        severity,
+        msg,
+        src_ptr,
+    };
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(DIAGNOSTICS)).push(diagnostic);
+    }
+}
+
+fn abandon() -> ! {
+    let num_recovery_points = unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        *addr_of_mut!(NUM_RECOVERY_POINTS)
+    };
+    if num_recovery_points == 0 {
+        exit_if_errors();
+        unreachable!(); // an error was just reported
+    }
+    // resume_unwind does not run the panic hook, so no panic message is printed
+    resume_unwind(Box::new(RecoverableError))
+}
+
+fn print_diagnostic(severity: &str, msg: &str, src_ptr: SourcePtr) {
+    // the line numbers of the tokens refer to the preprocessed source code,
+    // so they are mapped back to the file and line they were written on
+    match get_original_location(src_ptr.line) {
+        Some((file_name, line)) => {
+            eprintln!(
+                "{}:{}:{}: {}: {}",
+                file_name, line, src_ptr.col, severity, msg
+            );
+            if let Some(source_line) = get_source_line(&file_name, line) {
+                eprint!("{}", format_source_snippet(&source_line, line, src_ptr.col));
+            }
+        }
+        None => eprintln!(
+            "Line {} col {} {}: {}",
+            src_ptr.line, src_ptr.col, severity, msg
+        ),
+    }
+}
+
+fn get_source_line(file_name: &str, line: usize) -> Option<String> {
+    let contents = std::fs::read_to_string(file_name).ok()?;
+    contents
+        .lines()
+        .nth(line.checked_sub(1)?)
+        .map(|l| l.to_owned())
+}
+
+/// formats a line of source code, followed by a caret under the column (starting from 1)
+fn format_source_snippet(source_line: &str, line: usize, col: usize) -> String {
+    let gutter_width = line.to_string().len().max(4);
+    // tabs are kept in the padding so that the caret lines up with the source line
+    let padding: String = source_line
+        .chars()
+        .take(col.saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    format!(
+        "{:>width$} | {}\n{:>width$} | {}^\n",
+        line,
+        source_line,
+        "",
+        padding,
+        width = gutter_width
+    )
+}
@@ -1,5 +1,8 @@
 pub mod check_bin_op_exprs;
 pub mod check_funcs;
+pub mod check_labels;
+pub mod check_returns;
 pub mod check_types;
 pub mod check_vars;
 pub mod display;
+pub mod warnings;
@@ -0,0 +1,112 @@
+use std::ptr::addr_of_mut;
+
+use crate::tokenizer::source_cursor::SourcePtr;
+
+use super::display::{err_display_no_source, report_error, report_warning};
+
+/// the warnings which tcc can report, each of which can be turned on with `-W<name>`
+/// and off with `-Wno-<name>`, where the names are the same as gcc's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warning {
+    UnusedVariable,
+    UnusedParameter,
+    Parentheses, // an assignment used as a condition, such as `if (a = b)`
+    Conversion,  // an implicit conversion which may change the value, such as from `long` to `int`
+    CompareDistinctPointerTypes,
+    ReturnType, // a non-void function whose end can be reached without returning a value
+}
+
+const ALL_WARNINGS: [Warning; 6] = [
+    Warning::UnusedVariable,
+    Warning::UnusedParameter,
+    Warning::Parentheses,
+    Warning::Conversion,
+    Warning::CompareDistinctPointerTypes,
+    Warning::ReturnType,
+];
+
+impl Warning {
+    fn name(self) -> &'static str {
+        match self {
+            Warning::UnusedVariable => "unused-variable",
+            Warning::UnusedParameter => "unused-parameter",
+            Warning::Parentheses => "parentheses",
+            Warning::Conversion => "conversion",
+            Warning::CompareDistinctPointerTypes => "compare-distinct-pointer-types",
+            Warning::ReturnType => "return-type",
+        }
+    }
+
+    /// like gcc, unused parameters and conversions are only reported if asked for, since they are common in correct code
+    fn is_enabled_by_default(self) -> bool {
+        !matches!(self, Warning::UnusedParameter | Warning::Conversion)
+    }
+}
+
+// the warnings turned on by the command line flags, or by default
+static mut ENABLED_WARNINGS: Vec<Warning> = Vec::new();
+// true if `-Werror` was given, which turns every warning into an error
+static mut WARNINGS_ARE_ERRORS: bool = false;
+
+/// enables and disables warnings according to the `-W` command line flags, such as `unused-variable`
+/// for `-Wunused-variable`, `no-unused-variable` for `-Wno-unused-variable`, and `error` for `-Werror`.
+/// Later flags override earlier ones.
+pub fn configure_warnings(flags: &[String]) {
+    let mut enabled: Vec<Warning> = ALL_WARNINGS
+        .into_iter()
+        .filter(|w| w.is_enabled_by_default())
+        .collect();
+    let mut warnings_are_errors = false;
+
+    for flag in flags {
+        if flag == "error" {
+            warnings_are_errors = true;
+            continue;
+        }
+        if flag == "no-error" {
+            warnings_are_errors = false;
+            continue;
+        }
+        let (name, enable) = match flag.strip_prefix("no-") {
+            Some(name) => (name, false),
+            None => (flag.as_str(), true),
+        };
+        let warning = match ALL_WARNINGS.into_iter().find(|w| w.name() == name) {
+            Some(warning) => warning,
+            None => err_display_no_source(format!("unknown warning option -W{}", flag)),
+        };
+        enabled.retain(|w| *w != warning);
+        if enable {
+            enabled.push(warning);
+        }
+    }
+
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        *addr_of_mut!(ENABLED_WARNINGS) = enabled;
+        *addr_of_mut!(WARNINGS_ARE_ERRORS) = warnings_are_errors;
+    }
+}
+
+/// reports a warning if it is enabled, as an error if `-Werror` was given.
+/// The name of the flag controlling the warning is shown after the message, like gcc.
+pub fn warn<S: Into<String>>(warning: Warning, msg: S, src_ptr: SourcePtr) {
+    let (enabled, warnings_are_errors) = unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (
+            (*addr_of_mut!(ENABLED_WARNINGS)).contains(&warning),
+            *addr_of_mut!(WARNINGS_ARE_ERRORS),
+        )
+    };
+    if !enabled {
+        return;
+    }
+    if warnings_are_errors {
+        report_error(
+            format!("{} [-Werror={}]", msg.into(), warning.name()),
+            src_ptr,
+        );
+    } else {
+        report_warning(format!("{} [-W{}]", msg.into(), warning.name()), src_ptr);
+    }
+}
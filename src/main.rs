@@ -1,6 +1,7 @@
 mod codegen;
 mod errors;
 mod parser;
+mod preprocessor;
 mod tac;
 mod tokenizer;
 mod types;
@@ -14,11 +15,19 @@ use std::process::Command;
 use clap::Parser;
 use codegen::generate_x86_code;
 use errors::check_funcs::check_funcs;
+use errors::check_labels::check_labels;
+use errors::check_returns::check_returns;
 use errors::check_types::check_types;
 use errors::check_vars::check_vars;
+use errors::display::exit_if_errors;
+use errors::warnings::configure_warnings;
 use parser::const_eval::eval_program_const_exprs;
 use parser::generate_program_ast;
+use preprocessor::preprocess;
+use tac::dce::eliminate_dead_code;
 use tac::generate_tac;
+use tac::sccp::propagate_constants;
+use tac::ssa::{construct_ssa, destruct_ssa, verify_ssa};
 use tokenizer::get_tokens;
 
 use crate::codegen::asm_gen::generate_program_asm;
@@ -37,11 +46,27 @@ struct Cli {
     no_assemble: bool,
     #[arg(short = 'd', long = "debug")]
     debug: bool,
+    /// Add a directory to search for `#include` files
+    #[arg(short = 'I', value_name = "DIR")]
+    include_dirs: Vec<String>,
+    /// Define a macro, as `NAME` or `NAME=VALUE`
+    #[arg(short = 'D', value_name = "MACRO")]
+    defines: Vec<String>,
+    /// Enable a warning as `-W<name>`, disable it as `-Wno-<name>`, or treat warnings as errors with `-Werror`.
+    /// The warnings are unused-variable, unused-parameter, parentheses, conversion,
+    /// compare-distinct-pointer-types and return-type
+    #[arg(short = 'W', value_name = "WARNING")]
+    warnings: Vec<String>,
+    /// Optimization level, 0 skips the optimizations done in SSA form
+    #[arg(short = 'O', value_name = "LEVEL", default_value_t = 1)]
+    optimization_level: u8,
 }
 
 fn main() {
     let cli = Cli::parse();
 
+    configure_warnings(&cli.warnings);
+
     let input_filepath = cli.filename;
     let no_assemble = cli.no_assemble;
 
@@ -51,23 +76,54 @@ fn main() {
         .read_to_string(&mut contents)
         .unwrap_or_else(|_| panic!("error reading file: {}", &input_filepath));
 
-    let tokens = get_tokens(contents);
+    let preprocessed = preprocess(&input_filepath, &contents, &cli.include_dirs, &cli.defines);
+    exit_if_errors();
+    if cli.debug {
+        println!("{}", preprocessed);
+    }
+
+    // each stage reports as many errors as it can find, but the later stages
+    // rely on the earlier ones having succeeded
+    let tokens = get_tokens(preprocessed);
+    exit_if_errors();
     if cli.debug {
         dbg!(&tokens);
     }
     let mut program_ast = generate_program_ast(tokens);
+    exit_if_errors();
     check_funcs(&program_ast);
+    check_labels(&program_ast);
     check_vars(&program_ast);
+    check_returns(&program_ast);
+    exit_if_errors();
     check_types(&mut program_ast);
+    exit_if_errors();
     eval_program_const_exprs(&mut program_ast);
+    exit_if_errors();
     if cli.debug {
         dbg!(&program_ast);
     }
 
-    let tac_ir = generate_tac(program_ast);
+    let mut tac_ir = generate_tac(program_ast);
+    exit_if_errors();
+    // without optimizations, the code is generated from the instructions in the order they were generated
+    if cli.optimization_level > 0 {
+        for tac_func in &mut tac_ir {
+            construct_ssa(tac_func);
+            verify_ssa(tac_func);
+            propagate_constants(tac_func);
+            eliminate_dead_code(tac_func);
+            verify_ssa(tac_func);
+        }
+    }
     if cli.debug {
         dbg!(&tac_ir);
     }
+    if cli.optimization_level > 0 {
+        for tac_func in &mut tac_ir {
+            destruct_ssa(tac_func);
+        }
+    }
 
     let x86_code = generate_x86_code(&tac_ir);
     if cli.debug {
@@ -5,10 +5,11 @@ pub mod factor_parser;
 pub mod for_loop_parser;
 pub mod function_arg_decl;
 pub mod global_strings;
+pub mod scopes;
 pub mod token_cursor;
 pub mod types_parser;
 
-use crate::errors::display::err_display;
+use crate::errors::display::{err_display, recover, report_error};
 use crate::parser::{expr_parser::generate_expr_ast, token_cursor::TokenCursor};
 use crate::tokenizer::source_cursor::SourcePtr;
 use crate::tokenizer::Token;
@@ -16,32 +17,63 @@ use crate::types::VarType;
 use expr_parser::{BinOpPrecedenceLevel, Expr};
 use for_loop_parser::generate_for_loop_ast;
 
-use self::function_arg_decl::parse_function_arg_decl;
-use self::types_parser::parse_variable_declaration;
+use self::scopes::{
+    declare_func, declare_var, enter_scope, exit_scope, exit_scopes_until, get_scope_depth,
+    take_new_enum_consts,
+};
+use self::types_parser::{
+    is_declaration_start, parse_function_declarator, parse_init_declarator_list,
+    parse_type_specifier, parse_variable_declaration,
+};
 
 #[derive(Debug)]
 pub struct Program {
     pub functions: Vec<Function>,
+    pub prototypes: Vec<FunctionPrototype>, // function declarations without a body
+    pub global_vars: Vec<Statement>, // each global variable is a Statement::Declare, along with Statement::EnumConsts
 }
 
 #[derive(Debug)]
 pub struct Function {
     pub name: String,
+    pub pos: SourcePtr, // the location of the name
     pub args: Vec<(String, VarType)>,
     pub return_type: VarType,
     pub body: Vec<Statement>,
 }
 
+/// a function declaration without a body, such as `int f(int, char *);`
+#[derive(Debug)]
+pub struct FunctionPrototype {
+    pub name: String,
+    pub pos: SourcePtr,
+    pub param_types: Vec<VarType>,
+    pub return_type: VarType,
+}
+
+impl Function {
+    pub fn param_types(&self) -> Vec<VarType> {
+        self.args.iter().map(|(_, t)| t.clone()).collect()
+    }
+}
+
 #[derive(Debug)]
 pub enum Statement {
-    Continue,
-    Break,
-    Return(Expr),
-    Declare(String, Option<Expr>, VarType),
+    Continue(SourcePtr),
+    Break(SourcePtr),
+    Return(Option<Expr>, SourcePtr), // None for a bare `return;` in a void function
+    Declare(String, Option<Expr>, VarType, SourcePtr),
+    EnumConsts(Vec<(String, SourcePtr)>), // the enumerators defined by an `enum`, whose uses are replaced by their values while parsing
     CompoundStmt(Vec<Statement>),
     If(Expr, Box<Statement>, Option<Box<Statement>>),
     While(Expr, Box<Statement>),
-    For(Box<Statement>, Option<Expr>, Option<Expr>, Box<Statement>),
+    DoWhile(Box<Statement>, Expr),
+    Switch(Expr, Box<Statement>),
+    Case(Expr),         // a `case` label inside the body of a switch statement
+    Default(SourcePtr), // the `default` label inside the body of a switch statement
+    Labeled(String, Box<Statement>, SourcePtr), // a statement preceded by a label, such as `cleanup: free(p);`
+    Goto(String, SourcePtr),
+    For(Vec<Statement>, Option<Expr>, Option<Expr>, Box<Statement>), // the initial clause is an expression or declarations
     Expr(Expr),
     Empty,
 }
@@ -50,64 +82,167 @@ pub fn generate_program_ast(tokens: Vec<(Token, SourcePtr)>) -> Program {
     let mut tokens = TokenCursor::new(tokens);
 
     let mut functions = Vec::new();
+    let mut prototypes = Vec::new();
+    let mut global_vars = Vec::new();
+    enter_scope(); // the global scope
     while tokens.peek().is_some() {
-        let f = generate_function_ast(&mut tokens);
-        functions.push(f);
+        parse_with_recovery(&mut tokens, |tokens| {
+            parse_external_declaration(tokens, &mut functions, &mut prototypes, &mut global_vars)
+        });
     }
 
-    Program { functions }
+    Program {
+        functions,
+        prototypes,
+        global_vars,
+    }
 }
 
-fn generate_function_ast(tokens: &mut TokenCursor) -> Function {
-    let function_name;
+/// parses a function definition or prototype, or a declaration of global variables, types or enumerators
+fn parse_external_declaration(
+    tokens: &mut TokenCursor,
+    functions: &mut Vec<Function>,
+    prototypes: &mut Vec<FunctionPrototype>,
+    global_vars: &mut Vec<Statement>,
+) {
+    if !is_declaration_start(tokens.peek()) {
+        err_display(
+            "function definitions and global variables must begin with a type!",
+            tokens.peek_ptr(),
+        );
+    }
+    if tokens.peek() == Some(&Token::Typedef) {
+        global_vars.extend(parse_variable_declaration(tokens));
+        if tokens.next() != Some(&Token::Semicolon) {
+            err_display("expected semicolon after typedef", tokens.get_last_ptr())
+        }
+        return;
+    }
+    let is_tag_type = matches!(tokens.peek(), Some(Token::Struct) | Some(Token::Enum));
+    let return_type = parse_type_specifier(tokens);
+    push_enum_consts(global_vars);
+
+    if is_tag_type && tokens.peek() == Some(&Token::Semicolon) {
+        // a struct or enum declaration, such as `struct point { int x; int y; };`
+        tokens.next();
+        return;
+    }
 
-    let mut return_type;
-    match tokens.next() {
-        Some(&Token::Type(t)) => {
-            return_type = VarType::Fund(t);
+    if is_function_declaration(tokens) {
+        let has_empty_params = has_empty_param_list(tokens);
+        let (name, pos, args, return_type) = parse_function_declarator(tokens, return_type);
+        // declared before the body is parsed, so that the function may refer to itself
+        declare_func(&name);
+        // enums may also be defined in the parameter list
+        push_enum_consts(global_vars);
+        if tokens.peek() == Some(&Token::Semicolon) {
+            tokens.next(); // consume the semicolon
+            if has_empty_params {
+                report_error(
+                    format!(
+                        "old-style prototype of function {} with unspecified parameters is not supported, declare it as `{}(void)` or list the parameter types",
+                        name, name
+                    ),
+                    pos,
+                );
+            }
+            prototypes.push(FunctionPrototype {
+                name,
+                pos,
+                param_types: args.into_iter().map(|(_, t)| t).collect(),
+                return_type,
+            });
+        } else {
+            let f = generate_function_ast(tokens, name, pos, args, return_type);
+            functions.push(f);
         }
-        _ => {
+    } else {
+        global_vars.extend(parse_init_declarator_list(tokens, return_type));
+        if tokens.next() != Some(&Token::Semicolon) {
             err_display(
-                "function definitions must begin with a type that they return!",
+                "expected semicolon after global variable declaration",
                 tokens.get_last_ptr(),
-            );
+            )
         }
     }
-    while tokens.peek() == Some(&Token::Star) {
-        tokens.next();
-        return_type = VarType::Ptr(Box::new(return_type));
-    }
+}
 
-    if let Some(Token::Identifier { val }) = tokens.next() {
-        function_name = val.clone();
-    } else {
-        err_display(
-            "function name must be an identifier!",
-            tokens.get_last_ptr(),
-        );
+/// parses a statement or declaration with `parse`. If an error is reported while parsing it, the rest of
+/// it is skipped, so that the statements and declarations after it are still parsed and checked for errors.
+fn parse_with_recovery(tokens: &mut TokenCursor, parse: impl FnOnce(&mut TokenCursor)) {
+    let start = tokens.get_index();
+    let scope_depth = get_scope_depth();
+    if recover(|| parse(tokens)).is_none() {
+        exit_scopes_until(scope_depth);
+        tokens.skip_past_statement(start);
     }
+}
 
-    if tokens.next() != Some(&Token::OpenParen) {
-        err_display(
-            "expected `(` to begin function arguments!",
-            tokens.get_last_ptr(),
-        )
+/// records the enumerators defined while parsing the current declaration, if there are any
+fn push_enum_consts(stmts: &mut Vec<Statement>) {
+    let enum_consts = take_new_enum_consts();
+    if !enum_consts.is_empty() {
+        stmts.push(Statement::EnumConsts(enum_consts));
     }
+}
 
-    let function_args = parse_function_arg_decl(tokens);
+/// after the type at the beginning of a top-level declaration has been consumed, returns true if the
+/// declaration is a function definition or prototype (such as `int *f(...)` or `int (*f(...))(int)`)
+/// rather than a global variable.
+fn is_function_declaration(tokens: &TokenCursor) -> bool {
+    let n = get_declarator_name_offset(tokens);
+    matches!(tokens.peek_nth(n), Some(Token::Identifier { .. }))
+        && tokens.peek_nth(n + 1) == Some(&Token::OpenParen)
+}
 
-    if tokens.next() != Some(&Token::CloseParen) {
-        err_display(
-            "expected `)` to end function arguments!",
-            tokens.get_last_ptr(),
-        )
+/// after the type at the beginning of a function declaration has been consumed, returns true if the
+/// parameter list is empty, as in `int f()`, rather than `int f(void)` or a list of parameters
+fn has_empty_param_list(tokens: &TokenCursor) -> bool {
+    tokens.peek_nth(get_declarator_name_offset(tokens) + 2) == Some(&Token::CloseParen)
+}
+
+/// returns the n for which `tokens.peek_nth(n)` is the name of the declarator beginning at the next token,
+/// skipping the pointers and parentheses before it
+fn get_declarator_name_offset(tokens: &TokenCursor) -> usize {
+    let mut n = 1;
+    while matches!(
+        tokens.peek_nth(n),
+        Some(Token::Star) | Some(Token::OpenParen)
+    ) {
+        n += 1;
     }
+    n
+}
 
+fn generate_function_ast(
+    tokens: &mut TokenCursor,
+    name: String,
+    pos: SourcePtr,
+    args: Vec<(Option<String>, VarType)>,
+    return_type: VarType,
+) -> Function {
+    let args: Vec<(String, VarType)> = args
+        .into_iter()
+        .map(|(arg_name, arg_type)| match arg_name {
+            Some(arg_name) => (arg_name, arg_type),
+            None => err_display(
+                format!("argument name omitted in definition of function {}", name),
+                tokens.get_last_ptr(),
+            ),
+        })
+        .collect();
+
+    enter_scope();
+    for (arg_name, _) in &args {
+        declare_var(arg_name);
+    }
     let body = generate_compound_stmt_ast(tokens);
+    exit_scope();
 
     Function {
-        name: function_name,
-        args: function_args,
+        name,
+        pos,
+        args,
         return_type,
         body,
     }
@@ -122,9 +257,24 @@ fn generate_compound_stmt_ast(tokens: &mut TokenCursor) -> Vec<Statement> {
     }
     let mut statements = Vec::new();
 
+    enter_scope();
     while tokens.peek().is_some() && *tokens.peek().unwrap() != Token::CloseBrace {
-        statements.push(generate_statement_ast(tokens));
+        parse_with_recovery(tokens, |tokens| {
+            // a typedef name followed by a colon is a label instead
+            if is_declaration_start(tokens.peek()) && tokens.peek_nth(2) != Some(&Token::Colon) {
+                statements.extend(parse_variable_declaration(tokens));
+                if tokens.next() != Some(&Token::Semicolon) {
+                    err_display(
+                        "expected semicolon after declaration",
+                        tokens.get_last_ptr(),
+                    )
+                }
+            } else {
+                statements.push(generate_statement_ast(tokens));
+            }
+        });
     }
+    exit_scope();
 
     if tokens.next() != Some(&Token::CloseBrace) {
         err_display(
@@ -142,20 +292,53 @@ fn generate_statement_ast(tokens: &mut TokenCursor) -> Statement {
 
     match tokens.peek() {
         Some(Token::Continue) => {
-            tokens.next(); // consume the return
-            stmt = Statement::Continue;
+            tokens.next(); // consume the "continue"
+            stmt = Statement::Continue(tokens.get_last_ptr());
         }
         Some(Token::Break) => {
             tokens.next(); // consume the "break"
-            stmt = Statement::Break;
+            stmt = Statement::Break(tokens.get_last_ptr());
+        }
+        Some(Token::Goto) => {
+            tokens.next(); // consume the "goto"
+            match tokens.next() {
+                Some(Token::Identifier { val }) => {
+                    stmt = Statement::Goto(val.clone(), tokens.get_last_ptr())
+                }
+                _ => err_display("expected label name after goto", tokens.get_last_ptr()),
+            }
+        }
+        Some(Token::Identifier { val }) if tokens.peek_nth(2) == Some(&Token::Colon) => {
+            let label = val.clone();
+            tokens.next(); // consume the label
+            let pos = tokens.get_last_ptr();
+            tokens.next(); // consume the colon
+            if tokens.peek() == Some(&Token::CloseBrace) {
+                err_display(
+                    format!("label {} must be followed by a statement", label),
+                    tokens.get_last_ptr(),
+                );
+            }
+            let inner_stmt = generate_statement_ast(tokens);
+
+            expect_trailing_semicolon = false;
+            stmt = Statement::Labeled(label, Box::new(inner_stmt), pos);
         }
         Some(Token::Return) => {
             tokens.next(); // consume the "return"
-            expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
-            stmt = Statement::Return(expr);
+            let pos = tokens.get_last_ptr();
+            if tokens.peek() == Some(&Token::Semicolon) {
+                stmt = Statement::Return(None, pos);
+            } else {
+                expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
+                stmt = Statement::Return(Some(expr), pos);
+            }
         }
-        Some(Token::Type(_)) => {
-            stmt = parse_variable_declaration(tokens);
+        t if is_declaration_start(t) => {
+            // a declaration as the body of an if statement or a loop is only visible inside of that body
+            enter_scope();
+            stmt = Statement::CompoundStmt(parse_variable_declaration(tokens));
+            exit_scope();
         }
         Some(Token::OpenBrace) => {
             let compound_stmt = generate_compound_stmt_ast(tokens);
@@ -204,6 +387,62 @@ fn generate_statement_ast(tokens: &mut TokenCursor) -> Statement {
             expect_trailing_semicolon = false;
             stmt = Statement::While(conditional, Box::new(body));
         }
+        Some(Token::Do) => {
+            tokens.next(); // consume the "do"
+
+            let body = generate_statement_ast(tokens);
+
+            if tokens.next() != Some(&Token::While) {
+                err_display(
+                    "expected `while` after body of do-while loop",
+                    tokens.get_last_ptr(),
+                );
+            }
+            if tokens.next() != Some(&Token::OpenParen) {
+                err_display("expected open paren", tokens.get_last_ptr());
+            }
+            let conditional = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
+            if tokens.next() != Some(&Token::CloseParen) {
+                err_display("expected close paren", tokens.get_last_ptr());
+            }
+
+            // unlike the other loops, a do-while loop ends with a semicolon
+            stmt = Statement::DoWhile(Box::new(body), conditional);
+        }
+        Some(Token::Switch) => {
+            tokens.next(); // consume the "switch"
+
+            if tokens.next() != Some(&Token::OpenParen) {
+                err_display("expected open paren", tokens.get_last_ptr());
+            }
+            let controlling_expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
+            if tokens.next() != Some(&Token::CloseParen) {
+                err_display("expected close paren", tokens.get_last_ptr());
+            }
+
+            let body = generate_statement_ast(tokens);
+
+            expect_trailing_semicolon = false;
+            stmt = Statement::Switch(controlling_expr, Box::new(body));
+        }
+        Some(Token::Case) => {
+            tokens.next(); // consume the "case"
+            expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
+            if tokens.next() != Some(&Token::Colon) {
+                err_display("expected colon after case label", tokens.get_last_ptr());
+            }
+            expect_trailing_semicolon = false;
+            stmt = Statement::Case(expr);
+        }
+        Some(Token::Default) => {
+            tokens.next(); // consume the "default"
+            let pos = tokens.get_last_ptr();
+            if tokens.next() != Some(&Token::Colon) {
+                err_display("expected colon after default label", tokens.get_last_ptr());
+            }
+            expect_trailing_semicolon = false;
+            stmt = Statement::Default(pos);
+        }
         Some(Token::Semicolon) => {
             stmt = Statement::Empty;
         }
@@ -2,7 +2,7 @@ use crate::{
     errors::display::err_display,
     parser::expr_parser::{generate_expr_ast, BinOpPrecedenceLevel},
     tokenizer::Token,
-    types::{FundT, VarType},
+    types::{struct_defs::get_struct_layout, FundT, VarType},
 };
 
 use super::{
@@ -10,22 +10,22 @@ use super::{
     token_cursor::TokenCursor,
 };
 
+/// parses an initializer list for an array or a struct, such as `{1, 2, 3}` or `{{1, 2}, {3, 4}}`.
+/// An array of unknown length, such as `int a[]`, may have any number of elements.
 pub fn generate_arr_init_expr_ast(tokens: &mut TokenCursor, expected_type: &VarType) -> Expr {
     let mut exprs = Vec::new();
+    let pos = tokens.peek_ptr();
 
-    let (max_num_elems, inner_expected_type) = match expected_type {
-        VarType::Arr(a, b) => (*b, a),
-        VarType::Fund(_) | VarType::Ptr(_) => err_display(
+    let max_num_elems = match expected_type {
+        VarType::Arr(_, 0) => usize::MAX,
+        VarType::Arr(_, len) => *len,
+        VarType::Struct(tag) => get_struct_layout(tag).unwrap().members.len(),
+        VarType::Fund(_) | VarType::Ptr(_) | VarType::Void | VarType::Func(_, _) => err_display(
             "array initializer expression nested too deep",
             tokens.get_last_ptr(),
         ),
     };
 
-    let nested_array_expected = match inner_expected_type.as_ref() {
-        VarType::Arr(_, _) => true,
-        VarType::Fund(_) | VarType::Ptr(_) => false,
-    };
-
     if let Some(Token::StringLiteral(s)) = tokens.peek() {
         let res = generate_arr_init_expr_from_str(s.clone(), tokens, expected_type);
         tokens.next();
@@ -40,22 +40,38 @@ pub fn generate_arr_init_expr_ast(tokens: &mut TokenCursor, expected_type: &VarT
     }
 
     while tokens.peek() != Some(&Token::CloseBrace) {
+        // once there are too many elements, keep parsing so the length error below can be reported
+        let inner_expected_type = get_initializer_element_type(expected_type, exprs.len())
+            .unwrap_or(VarType::Fund(FundT::Int));
+        let nested_array_expected = match inner_expected_type {
+            VarType::Arr(_, _) => true,
+            VarType::Fund(_)
+            | VarType::Ptr(_)
+            | VarType::Struct(_)
+            | VarType::Void
+            | VarType::Func(_, _) => false,
+        };
+
         match tokens.peek() {
+            Some(Token::OpenBrace) if matches!(inner_expected_type, VarType::Struct(_)) => {
+                exprs.push(generate_arr_init_expr_ast(tokens, &inner_expected_type));
+            }
+            Some(Token::StringLiteral(_)) if !nested_array_expected => {
+                // a string literal initializing a pointer, such as in `char *names[2] = {"a", "b"}`
+                exprs.push(generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment));
+            }
             Some(Token::OpenBrace) | Some(Token::StringLiteral(_)) => {
                 if !nested_array_expected {
                     err_display("array initializer too deep!", tokens.get_last_ptr());
                 }
 
-                exprs.push(generate_arr_init_expr_ast(tokens, inner_expected_type));
+                exprs.push(generate_arr_init_expr_ast(tokens, &inner_expected_type));
             }
             _ => {
                 if nested_array_expected {
                     err_display("array initializer too shallow!", tokens.get_last_ptr());
                 }
-                exprs.push(generate_expr_ast(
-                    tokens,
-                    BinOpPrecedenceLevel::lowest_level(),
-                ));
+                exprs.push(generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment));
             }
         }
 
@@ -82,47 +98,84 @@ pub fn generate_arr_init_expr_ast(tokens: &mut TokenCursor, expected_type: &VarT
         )
     }
 
-    Expr::new(ExprEnum::ArrInitExpr(exprs))
+    Expr::new(ExprEnum::ArrInitExpr(exprs), pos)
 }
 
 pub fn generate_arr_init_expr_from_str(
-    mut s: String,
+    mut s: Vec<u8>,
     tokens: &mut TokenCursor,
     expected_type: &VarType,
 ) -> Expr {
+    let pos = tokens.peek_ptr();
     let (max_num_elems, inner_expected_type) = match expected_type {
         VarType::Arr(a, b) => (*b, a),
-        VarType::Fund(_) | VarType::Ptr(_) => err_display(
-            "array initializer expression nested too deep",
-            tokens.get_last_ptr(),
+        VarType::Fund(_)
+        | VarType::Ptr(_)
+        | VarType::Struct(_)
+        | VarType::Void
+        | VarType::Func(_, _) => err_display(
+            "string initializer expression must initialize an array",
+            pos,
         ),
     };
 
-    if inner_expected_type.as_ref() != &VarType::Fund(FundT::Char) {
+    if !matches!(
+        inner_expected_type.as_ref(),
+        VarType::Fund(FundT::Char) | VarType::Fund(FundT::UChar)
+    ) {
         err_display(
             "string array initializer may only be used for variables of type char",
-            tokens.get_last_ptr(),
+            pos,
         );
     }
 
-    let mut exprs = Vec::new();
-
-    s.push('\0'); // append a null byte
-
-    for b in s.as_bytes() {
-        exprs.push(Expr::new(ExprEnum::Int(*b as i64)));
-    }
-
-    if exprs.len() > max_num_elems {
+    if max_num_elems != 0 && s.len() > max_num_elems {
         err_display(
             format!(
-                "array initializer too long  (max {}, found {})",
-                max_num_elems,
-                exprs.len()
+                "initializer string of length {} is too long for array of {} chars",
+                s.len(),
+                max_num_elems
             ),
-            tokens.get_last_ptr(),
+            pos,
         );
     }
+    // the null byte is only stored if there is room for it, so `char s[5] = "hello"` is not null terminated.
+    // Any remaining elements are zero initialized anyways.
+    if max_num_elems == 0 || s.len() < max_num_elems {
+        s.push(0);
+    }
+
+    let exprs = s
+        .iter()
+        .map(|b| Expr::new(ExprEnum::Int(*b as i64), pos))
+        .collect();
 
-    Expr::new(ExprEnum::ArrInitExpr(exprs))
+    Expr::new(ExprEnum::ArrInitExpr(exprs), pos)
+}
+
+/// returns the type of a variable after it has been initialized. An array of unknown length, such as
+/// `char s[] = "hello"` or `int a[] = {1, 2, 3}`, takes its length from the initializer.
+pub fn get_initialized_type(type_: VarType, init_expr: &Expr) -> VarType {
+    match (type_, &init_expr.content) {
+        (VarType::Arr(inner, 0), ExprEnum::ArrInitExpr(exprs)) => VarType::Arr(inner, exprs.len()),
+        (type_, _) => type_,
+    }
+}
+
+/// returns the type of the element at `index` of an initializer list for `aggregate_type`,
+/// or None if there is no such element.
+pub fn get_initializer_element_type(aggregate_type: &VarType, index: usize) -> Option<VarType> {
+    match aggregate_type {
+        VarType::Arr(inner, len) if index < *len || *len == 0 => Some(*inner.clone()),
+        VarType::Struct(tag) => get_struct_layout(tag)
+            .unwrap()
+            .members
+            .get(index)
+            .map(|member| member.type_.clone()),
+        VarType::Arr(_, _)
+        | VarType::Fund(_)
+        | VarType::Ptr(_)
+        | VarType::Void
+        | VarType::Func(_, _) => None,
+    }
 }
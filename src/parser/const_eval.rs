@@ -1,4 +1,7 @@
+use crate::errors::{check_bin_op_exprs::get_common_type, display::report_error};
 use crate::parser::expr_parser::ExprEnum;
+use crate::tokenizer::source_cursor::SourcePtr;
+use crate::types::{FundT, VarType};
 
 use super::{
     expr_parser::{BinOp, Expr, UnOp},
@@ -9,6 +12,7 @@ use super::{
 /// for example, the expression (-(3+7) * 5) will be replaced with -50, since
 /// the expression consists of only integers that can be simplified at compile time.
 pub fn eval_program_const_exprs(program: &mut Program) {
+    eval_compound_stmt_exprs(&mut program.global_vars);
     for function in &mut program.functions {
         eval_compound_stmt_exprs(&mut function.body);
     }
@@ -23,11 +27,18 @@ fn eval_compound_stmt_exprs(stmts: &mut Vec<Statement>) {
 fn eval_stmt_exprs(stmt: &mut Statement) {
     let mut exprs_to_eval = Vec::new();
     match stmt {
-        Statement::Continue | Statement::Empty | Statement::Break => {}
-        Statement::Return(expr) | Statement::Expr(expr) => {
+        Statement::Continue(_)
+        | Statement::Empty
+        | Statement::Break(_)
+        | Statement::Default(_)
+        | Statement::Goto(_, _)
+        | Statement::EnumConsts(_) => {}
+        Statement::Labeled(_, inner_stmt, _) => eval_stmt_exprs(inner_stmt),
+        Statement::Return(Some(expr), _) | Statement::Expr(expr) => {
             exprs_to_eval = vec![expr];
         }
-        Statement::Declare(_, optional_expr, _) => {
+        Statement::Return(None, _) => {}
+        Statement::Declare(_, optional_expr, _, _) => {
             if let Some(expr) = optional_expr {
                 exprs_to_eval = vec![expr];
             }
@@ -42,18 +53,28 @@ fn eval_stmt_exprs(stmt: &mut Statement) {
                 eval_stmt_exprs(not_taken_stmt);
             }
         }
-        Statement::While(expr, body_stmt) => {
+        Statement::While(expr, body_stmt) | Statement::DoWhile(body_stmt, expr) => {
             exprs_to_eval = vec![expr];
             eval_stmt_exprs(body_stmt);
         }
-        Statement::For(init_stmt, ctrl_expr, post_stmt, body_stmt) => {
+        Statement::Switch(expr, body_stmt) => {
+            eval_expr(expr);
+            eval_stmt_exprs(body_stmt);
+            check_switch_labels(body_stmt);
+        }
+        Statement::Case(expr) => {
+            exprs_to_eval = vec![expr];
+        }
+        Statement::For(init_stmts, ctrl_expr, post_stmt, body_stmt) => {
             if let Some(ctrl_expr) = ctrl_expr {
                 exprs_to_eval.push(ctrl_expr);
             }
             if let Some(post_stmt) = post_stmt {
                 exprs_to_eval.push(post_stmt);
             }
-            eval_stmt_exprs(init_stmt);
+            for stmt in init_stmts {
+                eval_stmt_exprs(stmt);
+            }
             eval_stmt_exprs(body_stmt);
         }
     }
@@ -63,6 +84,92 @@ fn eval_stmt_exprs(stmt: &mut Statement) {
     }
 }
 
+/// verifies that the case labels of a switch statement are distinct integer constants,
+/// and that there is at most one default label.
+fn check_switch_labels(body: &Statement) {
+    let (case_exprs, default_positions) = get_switch_labels(body);
+
+    let mut values = Vec::new();
+    for expr in case_exprs {
+        match expr.content {
+            ExprEnum::Int(value) => {
+                if values.contains(&value) {
+                    report_error(
+                        format!("duplicate case value {} in switch", value),
+                        expr.pos,
+                    );
+                }
+                values.push(value);
+            }
+            _ => report_error(
+                "case label does not reduce to an integer constant",
+                expr.pos,
+            ),
+        }
+    }
+
+    if let Some(pos) = default_positions.get(1) {
+        report_error("multiple default labels in one switch", *pos);
+    }
+}
+
+/// returns the expressions of the case labels that belong to a switch statement with the given body,
+/// in the order they appear, along with the positions of the default labels.
+/// Labels inside of nested switch statements belong to the nested switch, and are not included.
+pub fn get_switch_labels(body: &Statement) -> (Vec<&Expr>, Vec<SourcePtr>) {
+    let mut case_exprs = Vec::new();
+    let mut default_positions = Vec::new();
+    collect_switch_labels(body, &mut case_exprs, &mut default_positions);
+    (case_exprs, default_positions)
+}
+
+fn collect_switch_labels<'a>(
+    stmt: &'a Statement,
+    case_exprs: &mut Vec<&'a Expr>,
+    default_positions: &mut Vec<SourcePtr>,
+) {
+    match stmt {
+        Statement::Case(expr) => case_exprs.push(expr),
+        Statement::Default(pos) => default_positions.push(*pos),
+        Statement::CompoundStmt(stmts) => {
+            for stmt in stmts {
+                collect_switch_labels(stmt, case_exprs, default_positions);
+            }
+        }
+        Statement::If(_, taken_stmt, opt_not_taken_stmt) => {
+            collect_switch_labels(taken_stmt, case_exprs, default_positions);
+            if let Some(not_taken_stmt) = opt_not_taken_stmt {
+                collect_switch_labels(not_taken_stmt, case_exprs, default_positions);
+            }
+        }
+        Statement::While(_, body)
+        | Statement::DoWhile(body, _)
+        | Statement::For(_, _, _, body)
+        | Statement::Labeled(_, body, _) => {
+            collect_switch_labels(body, case_exprs, default_positions);
+        }
+        Statement::Switch(_, _)
+        | Statement::Goto(_, _)
+        | Statement::Continue(_)
+        | Statement::Break(_)
+        | Statement::Return(_, _)
+        | Statement::Declare(_, _, _, _)
+        | Statement::EnumConsts(_)
+        | Statement::Expr(_)
+        | Statement::Empty => {}
+    }
+}
+
+/// evaluates an expression that must be an integer constant while parsing, such as the value
+/// of an enumerator or the length of an array. Returns None if it is not an integer constant.
+pub fn eval_int_const_expr(mut expr: Expr) -> Option<i64> {
+    eval_expr(&mut expr);
+    match expr.content {
+        ExprEnum::Int(value) => Some(value),
+        _ => None,
+    }
+}
+
 fn eval_expr(expr: &mut Expr) {
     match &mut expr.content {
         ExprEnum::BinOp(op, expr_1, expr_2) => {
@@ -78,13 +185,29 @@ fn eval_expr(expr: &mut Expr) {
                 *expr = simplified_expr;
             }
         }
-        ExprEnum::Ternary(_, _, _) => {}
+        ExprEnum::Ternary(decision_expr, expr_1, expr_2) => {
+            eval_expr(decision_expr);
+            eval_expr(expr_1);
+            eval_expr(expr_2);
+        }
+        ExprEnum::Cast(target_type, inner_expr) => {
+            eval_expr(inner_expr);
+            if let Some(simplified_expr) = eval_cast(target_type, inner_expr) {
+                *expr = simplified_expr;
+            }
+        }
 
         ExprEnum::FunctionCall(_, args) => {
             for arg in args {
                 eval_expr(arg);
             }
         }
+        ExprEnum::IndirectCall(callee, args) => {
+            eval_expr(callee);
+            for arg in args {
+                eval_expr(arg);
+            }
+        }
         ExprEnum::ArrInitExpr(exprs) => {
             for expr in exprs {
                 eval_expr(expr);
@@ -92,6 +215,7 @@ fn eval_expr(expr: &mut Expr) {
         }
 
         ExprEnum::Int(_)
+        | ExprEnum::Float(_, _)
         | ExprEnum::Var(_)
         | ExprEnum::Deref(_)
         | ExprEnum::Ref(_)
@@ -100,35 +224,124 @@ fn eval_expr(expr: &mut Expr) {
         | ExprEnum::PrefixDec(_)
         | ExprEnum::PrefixInc(_)
         | ExprEnum::Sizeof(_)
-        | ExprEnum::StaticStrPtr(_) => {}
+        | ExprEnum::SizeofType(_)
+        | ExprEnum::StaticStrPtr(_)
+        | ExprEnum::FunctionPtr(_)
+        | ExprEnum::Member(_, _) => {}
     }
 }
 
 fn eval_unop(op: UnOp, expr: Expr) -> Option<Expr> {
     let val: i64 = match expr.content {
         ExprEnum::Int(v) => v,
+        ExprEnum::Float(v, fund_t) => {
+            let content = match op {
+                UnOp::Plus => ExprEnum::Float(v, fund_t),
+                UnOp::Negation => ExprEnum::Float(-v, fund_t),
+                UnOp::Not => ExprEnum::Int(bool_to_i64(v == 0.0)),
+                UnOp::BitwiseComplement => return None, // check_types rejects ~ on floating point values
+            };
+            let type_ = match content {
+                ExprEnum::Float(_, _) => expr.type_,
+                _ => None,
+            };
+            return Some(Expr {
+                content,
+                type_,
+                pos: expr.pos,
+                is_parenthesized: false,
+            });
+        }
         _ => return None,
     };
 
     let new_val = match op {
-        UnOp::Negation => -val,
+        UnOp::Plus => val,
+        UnOp::Negation => val.wrapping_neg(),
         UnOp::BitwiseComplement => !val,
         UnOp::Not => {
-            if val == 0 {
-                1
-            } else {
-                0
-            }
+            return Some(Expr {
+                content: ExprEnum::Int(bool_to_i64(val == 0)),
+                type_: Some(VarType::Fund(FundT::Int)),
+                pos: expr.pos,
+                is_parenthesized: false,
+            })
         }
     };
 
+    // the result has the promoted type of the operand, so `-1u` wraps around
+    let type_ = get_common_type(&expr.type_, &expr.type_);
+    let new_val = match &type_ {
+        Some(VarType::Fund(fund_t)) => convert_int(new_val, *fund_t),
+        _ => new_val,
+    };
     Some(Expr {
         content: ExprEnum::Int(new_val),
-        type_: expr.type_,
+        type_,
+        pos: expr.pos,
+        is_parenthesized: false,
     })
 }
 
+/// casting a constant to a fundamental type gives a constant, which keeps the type of the cast
+fn eval_cast(target_type: &VarType, expr: &Expr) -> Option<Expr> {
+    let fund_t = match target_type {
+        VarType::Fund(fund_t) => *fund_t,
+        _ => return None,
+    };
+    let val: i64 = match expr.content {
+        ExprEnum::Int(v) if fund_t.is_float() => {
+            return Some(Expr {
+                content: ExprEnum::Float(int_to_float(v, &expr.type_, fund_t), fund_t),
+                type_: Some(target_type.clone()),
+                pos: expr.pos,
+                is_parenthesized: false,
+            });
+        }
+        ExprEnum::Float(v, _) if fund_t.is_float() => {
+            return Some(Expr {
+                content: ExprEnum::Float(round_to_float_type(v, fund_t), fund_t),
+                type_: Some(target_type.clone()),
+                pos: expr.pos,
+                is_parenthesized: false,
+            });
+        }
+        ExprEnum::Int(v) => v,
+        // converting a floating point value to an integer truncates towards zero
+        ExprEnum::Float(v, _) if fund_t == FundT::ULong => v as u64 as i64,
+        ExprEnum::Float(v, _) => v as i64,
+        _ => return None,
+    };
+
+    Some(Expr {
+        content: ExprEnum::Int(convert_int(val, fund_t)),
+        type_: Some(target_type.clone()),
+        pos: expr.pos,
+        is_parenthesized: false,
+    })
+}
+
+/// truncates, sign extends or zero extends an integer constant to an integer type
+fn convert_int(val: i64, fund_t: FundT) -> i64 {
+    match fund_t {
+        FundT::Char => val as i8 as i64,
+        FundT::UChar => val as u8 as i64,
+        FundT::Short => val as i16 as i64,
+        FundT::UShort => val as u16 as i64,
+        FundT::Int => val as i32 as i64,
+        FundT::UInt => val as u32 as i64,
+        FundT::Long | FundT::ULong => val,
+        FundT::Float | FundT::Double => unreachable!(),
+    }
+}
+
 fn eval_binop(op: BinOp, expr_1: Expr, expr_2: Expr) -> Option<Expr> {
+    if matches!(expr_1.content, ExprEnum::Float(_, _))
+        || matches!(expr_2.content, ExprEnum::Float(_, _))
+    {
+        return eval_float_binop(op, expr_1, expr_2);
+    }
+
     let val_1: i64 = match expr_1.content {
         ExprEnum::Int(v) => v,
         _ => return None,
@@ -138,29 +351,153 @@ fn eval_binop(op: BinOp, expr_1: Expr, expr_2: Expr) -> Option<Expr> {
         _ => return None,
     };
 
+    // the usual arithmetic conversions, except that only the left operand of a shift is promoted.
+    // Untyped constants stay untyped, and are computed with 64 bits.
+    let is_shift = matches!(op, BinOp::ShiftLeft | BinOp::ShiftRight);
+    let type_ = if is_shift {
+        get_common_type(&expr_1.type_, &expr_1.type_)
+    } else {
+        get_common_type(&expr_1.type_, &expr_2.type_)
+    };
+    let (val_1, val_2, is_unsigned) = match &type_ {
+        Some(VarType::Fund(fund_t)) if is_shift => {
+            (convert_int(val_1, *fund_t), val_2, fund_t.is_unsigned())
+        }
+        Some(VarType::Fund(fund_t)) => (
+            convert_int(val_1, *fund_t),
+            convert_int(val_2, *fund_t),
+            fund_t.is_unsigned(),
+        ),
+        _ => (val_1, val_2, false),
+    };
+    let (unsigned_1, unsigned_2) = (val_1 as u64, val_2 as u64);
+
+    let new_val = match op {
+        BinOp::Multiply => val_1.wrapping_mul(val_2),
+        // division by zero is left for the program to do at runtime
+        BinOp::Divide if is_unsigned => unsigned_1.checked_div(unsigned_2)? as i64,
+        BinOp::Divide => val_1.checked_div(val_2)?,
+        BinOp::Modulus if is_unsigned => unsigned_1.checked_rem(unsigned_2)? as i64,
+        BinOp::Modulus => val_1.checked_rem(val_2)?,
+        BinOp::Plus => val_1.wrapping_add(val_2),
+        BinOp::Minus => val_1.wrapping_sub(val_2),
+        BinOp::BitwiseAnd => val_1 & val_2,
+        BinOp::BitwiseOr => val_1 | val_2,
+        BinOp::BitwiseXor => val_1 ^ val_2,
+        BinOp::ShiftLeft => val_1.wrapping_shl(val_2 as u32),
+        BinOp::ShiftRight if is_unsigned => unsigned_1.wrapping_shr(val_2 as u32) as i64,
+        BinOp::ShiftRight => val_1.wrapping_shr(val_2 as u32),
+        _ => {
+            // comparisons and logical operators give an untyped int
+            let new_val = match op {
+                BinOp::GreaterThan if is_unsigned => unsigned_1 > unsigned_2,
+                BinOp::GreaterThan => val_1 > val_2,
+                BinOp::GreaterThanEq if is_unsigned => unsigned_1 >= unsigned_2,
+                BinOp::GreaterThanEq => val_1 >= val_2,
+                BinOp::LessThan if is_unsigned => unsigned_1 < unsigned_2,
+                BinOp::LessThan => val_1 < val_2,
+                BinOp::LessThanEq if is_unsigned => unsigned_1 <= unsigned_2,
+                BinOp::LessThanEq => val_1 <= val_2,
+                BinOp::Equals => val_1 == val_2,
+                BinOp::NotEquals => val_1 != val_2,
+                BinOp::LogicalAnd => i64_to_bool(val_1) && i64_to_bool(val_2),
+                BinOp::LogicalOr => i64_to_bool(val_1) || i64_to_bool(val_2),
+                // neither assignments nor the comma operator are allowed in constant expressions
+                _ => return None,
+            };
+            return Some(Expr {
+                content: ExprEnum::Int(bool_to_i64(new_val)),
+                type_: Some(VarType::Fund(FundT::Int)),
+                pos: expr_1.pos,
+                is_parenthesized: false,
+            });
+        }
+    };
+
+    let new_val = match &type_ {
+        Some(VarType::Fund(fund_t)) => convert_int(new_val, *fund_t),
+        _ => new_val,
+    };
+    Some(Expr {
+        content: ExprEnum::Int(new_val),
+        type_,
+        pos: expr_1.pos,
+        is_parenthesized: false,
+    })
+}
+
+/// evaluates a binary operator where at least one operand is a floating point constant,
+/// and the other is a floating point or integer constant.
+fn eval_float_binop(op: BinOp, expr_1: Expr, expr_2: Expr) -> Option<Expr> {
+    // the operands are converted to the bigger floating point type
+    let fund_t = match (&expr_1.content, &expr_2.content) {
+        (ExprEnum::Float(_, FundT::Double), _) | (_, ExprEnum::Float(_, FundT::Double)) => {
+            FundT::Double
+        }
+        _ => FundT::Float,
+    };
+    let to_float = |expr: &Expr| match expr.content {
+        ExprEnum::Float(v, _) => Some(round_to_float_type(v, fund_t)),
+        ExprEnum::Int(v) => Some(int_to_float(v, &expr.type_, fund_t)),
+        _ => None,
+    };
+    let val_1 = to_float(&expr_1)?;
+    let val_2 = to_float(&expr_2)?;
+
     let new_val = match op {
         BinOp::Multiply => val_1 * val_2,
         BinOp::Divide => val_1 / val_2,
-        BinOp::Modulus => val_1 % val_2,
         BinOp::Plus => val_1 + val_2,
         BinOp::Minus => val_1 - val_2,
-        BinOp::GreaterThan => bool_to_i64(val_1 > val_2),
-        BinOp::GreaterThanEq => bool_to_i64(val_1 >= val_2),
-        BinOp::LessThan => bool_to_i64(val_1 < val_2),
-        BinOp::LessThanEq => bool_to_i64(val_1 <= val_2),
-        BinOp::Equals => bool_to_i64(val_1 == val_2),
-        BinOp::NotEquals => bool_to_i64(val_1 != val_2),
-        BinOp::LogicalAnd => bool_to_i64(i64_to_bool(val_1) && i64_to_bool(val_2)),
-        BinOp::LogicalOr => bool_to_i64(i64_to_bool(val_1) || i64_to_bool(val_2)),
-        BinOp::Assign => return None,
+        _ => {
+            let new_val = match op {
+                BinOp::GreaterThan => val_1 > val_2,
+                BinOp::GreaterThanEq => val_1 >= val_2,
+                BinOp::LessThan => val_1 < val_2,
+                BinOp::LessThanEq => val_1 <= val_2,
+                BinOp::Equals => val_1 == val_2,
+                BinOp::NotEquals => val_1 != val_2,
+                BinOp::LogicalAnd => val_1 != 0.0 && val_2 != 0.0,
+                BinOp::LogicalOr => val_1 != 0.0 || val_2 != 0.0,
+                // check_types rejects the integer only operators on floating point values
+                _ => return None,
+            };
+            return Some(Expr {
+                content: ExprEnum::Int(bool_to_i64(new_val)),
+                type_: Some(VarType::Fund(FundT::Int)),
+                pos: expr_1.pos,
+                is_parenthesized: false,
+            });
+        }
     };
 
     Some(Expr {
-        content: ExprEnum::Int(new_val),
-        type_: None,
+        content: ExprEnum::Float(round_to_float_type(new_val, fund_t), fund_t),
+        type_: Some(VarType::Fund(fund_t)),
+        pos: expr_1.pos,
+        is_parenthesized: false,
     })
 }
 
+/// converts an integer constant with the given type to a floating point type
+fn int_to_float(val: i64, int_type: &Option<VarType>, fund_t: FundT) -> f64 {
+    let is_unsigned = int_type.as_ref().is_some_and(|t| t.is_unsigned());
+    match (fund_t, is_unsigned) {
+        (FundT::Float, false) => val as f32 as f64,
+        (FundT::Float, true) => val as u64 as f32 as f64,
+        (_, false) => val as f64,
+        (_, true) => val as u64 as f64,
+    }
+}
+
+/// rounds a value to the precision of `float` if needed
+fn round_to_float_type(val: f64, fund_t: FundT) -> f64 {
+    match fund_t {
+        FundT::Float => val as f32 as f64,
+        _ => val,
+    }
+}
+
 fn bool_to_i64(b: bool) -> i64 {
     if b {
         1
@@ -1,17 +1,22 @@
+use std::fmt;
+
 use super::factor_parser::generate_factor_ast;
 use super::TokenCursor;
 use crate::errors::display::err_display;
-use crate::tokenizer::{operator::Op, Token};
-use crate::types::VarType;
+use crate::tokenizer::{operator::Op, source_cursor::SourcePtr, Token};
+use crate::types::{FundT, VarType};
 
 #[derive(Debug, Clone)]
 pub enum ExprEnum {
     Int(i64),
+    Float(f64, FundT), // a floating point constant, and whether it is a `float` or a `double`
     Var(String),
     UnOp(UnOp, Box<Expr>),
     BinOp(BinOp, Box<Expr>, Box<Expr>),
     Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
     FunctionCall(String, Vec<Expr>), // Vec<Expr> contains the arguments of the function
+    IndirectCall(Box<Expr>, Vec<Expr>), // a call through a function pointer
+    FunctionPtr(String),             // the name of a function, used as a value
     Deref(Box<Expr>),
     Ref(Box<Expr>),
     PostfixDec(Box<Expr>),
@@ -19,27 +24,35 @@ pub enum ExprEnum {
     PrefixDec(Box<Expr>),
     PrefixInc(Box<Expr>),
     Sizeof(Box<Expr>),
+    SizeofType(VarType),
     ArrInitExpr(Vec<Expr>),
-    StaticStrPtr(String),
+    StaticStrPtr(Vec<u8>),
+    Member(Box<Expr>, String), // struct member access, `a->b` is parsed as `(*a).b`
+    Cast(VarType, Box<Expr>),
 }
 
 #[derive(Debug, Clone)]
 pub struct Expr {
     pub content: ExprEnum,
     pub type_: Option<VarType>,
+    pub pos: SourcePtr, // where the expression is reported in error messages, such as the operator of a binop
+    pub is_parenthesized: bool, // written inside of parentheses, as in `(a = b)`, which silences -Wparentheses
 }
 
 impl Expr {
-    pub fn new(content: ExprEnum) -> Self {
+    pub fn new(content: ExprEnum, pos: SourcePtr) -> Self {
         Expr {
             content,
             type_: None,
+            pos,
+            is_parenthesized: false,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum UnOp {
+    Plus,
     Negation,
     BitwiseComplement,
     Not,
@@ -60,35 +73,91 @@ pub enum BinOp {
     NotEquals,
     LogicalAnd,
     LogicalOr,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    ShiftLeft,
+    ShiftRight,
     Assign,
+    Comma,
+}
+
+impl fmt::Display for UnOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            UnOp::Plus => "+",
+            UnOp::Negation => "-",
+            UnOp::BitwiseComplement => "~",
+            UnOp::Not => "!",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            BinOp::Multiply => "*",
+            BinOp::Divide => "/",
+            BinOp::Modulus => "%",
+            BinOp::Plus => "+",
+            BinOp::Minus => "-",
+            BinOp::GreaterThan => ">",
+            BinOp::GreaterThanEq => ">=",
+            BinOp::LessThan => "<",
+            BinOp::LessThanEq => "<=",
+            BinOp::Equals => "==",
+            BinOp::NotEquals => "!=",
+            BinOp::LogicalAnd => "&&",
+            BinOp::LogicalOr => "||",
+            BinOp::BitwiseAnd => "&",
+            BinOp::BitwiseOr => "|",
+            BinOp::BitwiseXor => "^",
+            BinOp::ShiftLeft => "<<",
+            BinOp::ShiftRight => ">>",
+            BinOp::Assign => "=",
+            BinOp::Comma => ",",
+        };
+        write!(f, "{}", symbol)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BinOpPrecedenceLevel {
     MulDiv,
     AddSub,
+    Shift,
     OrderingCmp,
     EqCmp,
+    BitwiseAnd,
+    BitwiseXor,
+    BitwiseOr,
     LogicalAnd,
     LogicalOr,
     Assignment,
+    Comma,
 }
 
 impl BinOpPrecedenceLevel {
     pub fn next_level(&self) -> Option<Self> {
         match self {
+            BinOpPrecedenceLevel::Comma => Some(BinOpPrecedenceLevel::Assignment),
             BinOpPrecedenceLevel::Assignment => Some(BinOpPrecedenceLevel::LogicalOr),
             BinOpPrecedenceLevel::LogicalOr => Some(BinOpPrecedenceLevel::LogicalAnd),
-            BinOpPrecedenceLevel::LogicalAnd => Some(BinOpPrecedenceLevel::EqCmp),
+            BinOpPrecedenceLevel::LogicalAnd => Some(BinOpPrecedenceLevel::BitwiseOr),
+            BinOpPrecedenceLevel::BitwiseOr => Some(BinOpPrecedenceLevel::BitwiseXor),
+            BinOpPrecedenceLevel::BitwiseXor => Some(BinOpPrecedenceLevel::BitwiseAnd),
+            BinOpPrecedenceLevel::BitwiseAnd => Some(BinOpPrecedenceLevel::EqCmp),
             BinOpPrecedenceLevel::EqCmp => Some(BinOpPrecedenceLevel::OrderingCmp),
-            BinOpPrecedenceLevel::OrderingCmp => Some(BinOpPrecedenceLevel::AddSub),
+            BinOpPrecedenceLevel::OrderingCmp => Some(BinOpPrecedenceLevel::Shift),
+            BinOpPrecedenceLevel::Shift => Some(BinOpPrecedenceLevel::AddSub),
             BinOpPrecedenceLevel::AddSub => Some(BinOpPrecedenceLevel::MulDiv),
             BinOpPrecedenceLevel::MulDiv => None,
         }
     }
 
     pub fn lowest_level() -> Self {
-        BinOpPrecedenceLevel::Assignment
+        BinOpPrecedenceLevel::Comma
     }
 }
 
@@ -107,10 +176,11 @@ pub fn generate_expr_ast(
 
     while tokens.peek().is_some() {
         if &Token::QuestionMark == tokens.peek().unwrap()
-            && curr_operator_precedence == BinOpPrecedenceLevel::lowest_level()
+            && curr_operator_precedence == BinOpPrecedenceLevel::Assignment
         {
-            // handle ternary case. Note that ternaries have the lowest precedence level, so we need to check the precedence level.
+            // handle ternary case. Note that ternaries have the same precedence level as assignment, so we need to check the precedence level.
             tokens.next();
+            let pos = tokens.get_last_ptr();
             let first_expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
             if tokens.next() != Some(&Token::Colon) {
                 err_display(
@@ -122,13 +192,12 @@ pub fn generate_expr_ast(
                 )
             }
 
-            let second_expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
+            let second_expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment);
 
-            return Expr::new(ExprEnum::Ternary(
-                Box::new(expr),
-                Box::new(first_expr),
-                Box::new(second_expr),
-            ));
+            return Expr::new(
+                ExprEnum::Ternary(Box::new(expr), Box::new(first_expr), Box::new(second_expr)),
+                pos,
+            );
         }
 
         // if the next token is a binary operator that is on the current precedence level:
@@ -138,6 +207,7 @@ pub fn generate_expr_ast(
             .to_binop_precedence_level(curr_operator_precedence)
         {
             let curr_token = tokens.next().unwrap().clone();
+            let pos = tokens.get_last_ptr();
             let next_expr;
 
             if curr_operator_precedence == BinOpPrecedenceLevel::Assignment {
@@ -148,11 +218,10 @@ pub fn generate_expr_ast(
                 } else {
                     next_expr = generate_factor_ast(tokens);
                 }
-                expr = Expr::new(ExprEnum::BinOp(
-                    next_op,
-                    Box::new(expr),
-                    Box::new(next_expr),
-                ));
+                expr = Expr::new(
+                    ExprEnum::BinOp(next_op, Box::new(expr), Box::new(next_expr)),
+                    pos,
+                );
             }
         } else {
             break;
@@ -167,58 +236,39 @@ fn generate_assignment_expr_ast(
     curr_token: Token,
 ) -> Expr {
     // ASSIGNMENT IS RIGHT ASSOCIATIVE, so we don't increment the operator precedence.
+    let pos = tokens.get_last_ptr();
     let next_expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment);
-    match curr_token {
-        Token::Op(Op::AssignmentEquals) => Expr::new(ExprEnum::BinOp(
-            BinOp::Assign,
-            Box::new(lhs_expr),
-            Box::new(next_expr),
-        )),
-        Token::Op(Op::PlusEquals) => Expr::new(ExprEnum::BinOp(
-            BinOp::Assign,
-            Box::new(lhs_expr.clone()),
-            Box::new(Expr::new(ExprEnum::BinOp(
-                BinOp::Plus,
-                Box::new(lhs_expr),
-                Box::new(next_expr),
-            ))),
-        )),
-        Token::Op(Op::MinusEquals) => Expr::new(ExprEnum::BinOp(
-            BinOp::Assign,
-            Box::new(lhs_expr.clone()),
-            Box::new(Expr::new(ExprEnum::BinOp(
-                BinOp::Minus,
-                Box::new(lhs_expr),
-                Box::new(next_expr),
-            ))),
-        )),
-        Token::Op(Op::MulEquals) => Expr::new(ExprEnum::BinOp(
-            BinOp::Assign,
-            Box::new(lhs_expr.clone()),
-            Box::new(Expr::new(ExprEnum::BinOp(
-                BinOp::Multiply,
-                Box::new(lhs_expr),
-                Box::new(next_expr),
-            ))),
-        )),
-        Token::Op(Op::DivEquals) => Expr::new(ExprEnum::BinOp(
+
+    // compound assignments such as `a += b` are desugared into `a = a + b`
+    let compound_op = match curr_token {
+        Token::Op(Op::AssignmentEquals) => {
+            return Expr::new(
+                ExprEnum::BinOp(BinOp::Assign, Box::new(lhs_expr), Box::new(next_expr)),
+                pos,
+            )
+        }
+        Token::Op(Op::PlusEquals) => BinOp::Plus,
+        Token::Op(Op::MinusEquals) => BinOp::Minus,
+        Token::Op(Op::MulEquals) => BinOp::Multiply,
+        Token::Op(Op::DivEquals) => BinOp::Divide,
+        Token::Op(Op::ModEquals) => BinOp::Modulus,
+        Token::Op(Op::AndEquals) => BinOp::BitwiseAnd,
+        Token::Op(Op::OrEquals) => BinOp::BitwiseOr,
+        Token::Op(Op::XorEquals) => BinOp::BitwiseXor,
+        Token::Op(Op::ShiftLeftEquals) => BinOp::ShiftLeft,
+        Token::Op(Op::ShiftRightEquals) => BinOp::ShiftRight,
+        _ => unreachable!(),
+    };
+
+    Expr::new(
+        ExprEnum::BinOp(
             BinOp::Assign,
             Box::new(lhs_expr.clone()),
-            Box::new(Expr::new(ExprEnum::BinOp(
-                BinOp::Divide,
-                Box::new(lhs_expr),
-                Box::new(next_expr),
-            ))),
-        )),
-        Token::Op(Op::ModEquals) => Expr::new(ExprEnum::BinOp(
-            BinOp::Modulus,
-            Box::new(lhs_expr.clone()),
-            Box::new(Expr::new(ExprEnum::BinOp(
-                BinOp::Plus,
-                Box::new(lhs_expr),
-                Box::new(next_expr),
-            ))),
-        )),
-        _ => unreachable!(),
-    }
+            Box::new(Expr::new(
+                ExprEnum::BinOp(compound_op, Box::new(lhs_expr), Box::new(next_expr)),
+                pos,
+            )),
+        ),
+        pos,
+    )
 }
@@ -1,24 +1,28 @@
 use crate::{
     errors::display::err_display,
     tokenizer::{operator::Op, Token},
+    types::{FundT, VarType},
 };
 
 use super::{
     expr_parser::{generate_expr_ast, BinOp, BinOpPrecedenceLevel, Expr, ExprEnum},
     global_strings::add_static_string,
+    scopes::{get_enum_const, is_func, is_var},
     token_cursor::TokenCursor,
+    types_parser::{is_type_specifier, parse_type_name},
 };
 
 pub fn generate_factor_ast(tokens: &mut TokenCursor) -> Expr {
+    let pos = tokens.peek_ptr();
     match tokens.peek() {
         Some(Token::Op(op)) if *op == Op::PlusPlus || *op == Op::MinusMinus => {
             let op = op.clone();
             tokens.next();
             let factor = generate_factor_ast(tokens);
             if op == Op::PlusPlus {
-                Expr::new(ExprEnum::PrefixInc(Box::new(factor)))
+                Expr::new(ExprEnum::PrefixInc(Box::new(factor)), pos)
             } else {
-                Expr::new(ExprEnum::PrefixDec(Box::new(factor)))
+                Expr::new(ExprEnum::PrefixDec(Box::new(factor)), pos)
             }
         }
         Some(token) if token.to_un_op().is_some() => {
@@ -26,37 +30,32 @@ pub fn generate_factor_ast(tokens: &mut TokenCursor) -> Expr {
             tokens.next();
             let factor = generate_factor_ast(tokens);
 
-            Expr::new(ExprEnum::UnOp(un_op, Box::new(factor)))
+            Expr::new(ExprEnum::UnOp(un_op, Box::new(factor)), pos)
         }
         Some(Token::Star) => {
             tokens.next();
             let factor = generate_factor_ast(tokens);
-            Expr::new(ExprEnum::Deref(Box::new(factor)))
+            Expr::new(ExprEnum::Deref(Box::new(factor)), pos)
         }
         Some(Token::Ampersand) => {
             tokens.next();
             let factor = generate_factor_ast(tokens);
-            Expr::new(ExprEnum::Ref(Box::new(factor)))
+            Expr::new(ExprEnum::Ref(Box::new(factor)), pos)
         }
         Some(Token::Identifier { val }) => {
             let val = val.clone();
             tokens.next();
 
-            let expr = if tokens.peek() == Some(&Token::OpenParen) {
+            let expr = if tokens.peek() == Some(&Token::OpenParen) && !is_var(&val) {
                 tokens.next(); // consume the open paren
-                let args = parse_function_args(tokens);
-                if tokens.next() != Some(&Token::CloseParen) {
-                    err_display(
-                        format!(
-                            "expected closing parenthesis, found {:?}",
-                            tokens.last().unwrap()
-                        ),
-                        tokens.get_last_ptr(),
-                    )
-                }
-                Expr::new(ExprEnum::FunctionCall(val, args))
+                let args = parse_call_args(tokens);
+                Expr::new(ExprEnum::FunctionCall(val, args), pos)
+            } else if let Some(value) = get_enum_const(&val) {
+                Expr::new(ExprEnum::Int(value), pos)
+            } else if is_func(&val) {
+                Expr::new(ExprEnum::FunctionPtr(val), pos)
             } else {
-                Expr::new(ExprEnum::Var(val))
+                Expr::new(ExprEnum::Var(val), pos)
             };
 
             attach_postfix_ops(tokens, expr)
@@ -65,29 +64,93 @@ pub fn generate_factor_ast(tokens: &mut TokenCursor) -> Expr {
             let val = val.clone();
             tokens.next();
             add_static_string(val.clone());
-            Expr::new(ExprEnum::StaticStrPtr(val.clone()))
+            Expr::new(ExprEnum::StaticStrPtr(val.clone()), pos)
         }
         Some(Token::Sizeof) => {
             tokens.next(); // consume the "sizeof"
-            assert_eq!(tokens.next(), Some(&Token::OpenParen));
-            let expr = Expr::new(ExprEnum::Sizeof(Box::new(generate_expr_ast(
-                tokens,
-                BinOpPrecedenceLevel::lowest_level(),
-            ))));
-            assert_eq!(tokens.next(), Some(&Token::CloseParen));
+            if tokens.next() != Some(&Token::OpenParen) {
+                err_display("expected `(` after sizeof", tokens.get_last_ptr())
+            }
+            let expr = if is_type_specifier(tokens.peek()) {
+                Expr::new(ExprEnum::SizeofType(parse_type_name(tokens)), pos)
+            } else {
+                Expr::new(
+                    ExprEnum::Sizeof(Box::new(generate_expr_ast(
+                        tokens,
+                        BinOpPrecedenceLevel::lowest_level(),
+                    ))),
+                    pos,
+                )
+            };
+            if tokens.next() != Some(&Token::CloseParen) {
+                err_display(
+                    format!(
+                        "expected closing parenthesis after sizeof, found {:?}",
+                        tokens.last()
+                    ),
+                    tokens.get_last_ptr(),
+                )
+            }
             expr
         }
 
-        Some(Token::IntLit { val }) => {
-            let val_i32 = str::parse(val).unwrap();
+        Some(Token::IntLit { val, type_ }) => {
+            let (val, type_) = (*val, *type_);
             tokens.next();
 
-            Expr::new(ExprEnum::Int(val_i32))
+            // literals which are too large for an int, or have a suffix, are casted to their type
+            match type_ {
+                FundT::Int => Expr::new(ExprEnum::Int(val), pos),
+                _ => Expr::new(
+                    ExprEnum::Cast(
+                        VarType::Fund(type_),
+                        Box::new(Expr::new(ExprEnum::Int(val), pos)),
+                    ),
+                    pos,
+                ),
+            }
+        }
+        Some(Token::FloatLit { val }) => {
+            // a literal with an `f` suffix is a float, otherwise it is a double
+            let parsed = match val.strip_suffix(['f', 'F']) {
+                Some(digits) => digits.parse::<f32>().map(|v| (v as f64, FundT::Float)),
+                None => val
+                    .trim_end_matches(['l', 'L'])
+                    .parse::<f64>()
+                    .map(|v| (v, FundT::Double)),
+            };
+            let (value, fund_t) = match parsed {
+                Ok(x) => x,
+                Err(_) => err_display(
+                    format!("invalid floating point literal {}", val),
+                    tokens.get_last_ptr(),
+                ),
+            };
+            tokens.next();
+
+            Expr::new(ExprEnum::Float(value, fund_t), pos)
+        }
+        Some(Token::OpenParen) if is_type_specifier(tokens.peek_nth(2)) => {
+            tokens.next(); // consume opening parenthesis
+            let type_ = parse_type_name(tokens);
+            if tokens.next() != Some(&Token::CloseParen) {
+                err_display(
+                    format!(
+                        "expected closing parenthesis after type in cast, found {:?}",
+                        tokens.last().unwrap()
+                    ),
+                    tokens.get_last_ptr(),
+                )
+            }
+            // a cast binds tighter than any binary operator, but looser than postfix operators
+            let factor = generate_factor_ast(tokens);
+            Expr::new(ExprEnum::Cast(type_, Box::new(factor)), pos)
         }
         Some(Token::OpenParen) => {
             tokens.next(); // consume opening parenthesis
 
-            let expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
+            let mut expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
+            expr.is_parenthesized = true;
 
             if tokens.next() != Some(&Token::CloseParen) {
                 err_display(
@@ -102,18 +165,48 @@ pub fn generate_factor_ast(tokens: &mut TokenCursor) -> Expr {
         }
         _ => err_display(
             format!("unexpected token: {:?}", tokens.peek()),
-            tokens.get_last_ptr(),
+            tokens.peek_ptr(),
         ),
     }
 }
 
 fn attach_postfix_ops(tokens: &mut TokenCursor, curr_expr: Expr) -> Expr {
+    let pos = tokens.peek_ptr();
     if tokens.peek() == Some(&Token::Op(Op::MinusMinus)) {
         tokens.next();
-        attach_postfix_ops(tokens, Expr::new(ExprEnum::PostfixDec(Box::new(curr_expr))))
+        attach_postfix_ops(
+            tokens,
+            Expr::new(ExprEnum::PostfixDec(Box::new(curr_expr)), pos),
+        )
     } else if tokens.peek() == Some(&Token::Op(Op::PlusPlus)) {
         tokens.next();
-        return attach_postfix_ops(tokens, Expr::new(ExprEnum::PostfixInc(Box::new(curr_expr))));
+        attach_postfix_ops(
+            tokens,
+            Expr::new(ExprEnum::PostfixInc(Box::new(curr_expr)), pos),
+        )
+    } else if tokens.peek() == Some(&Token::Dot) || tokens.peek() == Some(&Token::Arrow) {
+        let struct_expr = if tokens.next() == Some(&Token::Arrow) {
+            Expr::new(ExprEnum::Deref(Box::new(curr_expr)), pos)
+        } else {
+            curr_expr
+        };
+        let member_name = match tokens.next() {
+            Some(Token::Identifier { val }) => val.clone(),
+            _ => err_display("expected name of struct member", tokens.get_last_ptr()),
+        };
+
+        attach_postfix_ops(
+            tokens,
+            Expr::new(ExprEnum::Member(Box::new(struct_expr), member_name), pos),
+        )
+    } else if tokens.peek() == Some(&Token::OpenParen) {
+        // calling a function pointer, such as `fp(1, 2)` or `ops[i](1, 2)`
+        tokens.next(); // consume the open paren
+        let args = parse_call_args(tokens);
+        attach_postfix_ops(
+            tokens,
+            Expr::new(ExprEnum::IndirectCall(Box::new(curr_expr), args), pos),
+        )
     } else if tokens.peek() == Some(&Token::OpenBracket) {
         tokens.next();
         let arr_size = generate_expr_ast(tokens, BinOpPrecedenceLevel::lowest_level());
@@ -121,16 +214,33 @@ fn attach_postfix_ops(tokens: &mut TokenCursor, curr_expr: Expr) -> Expr {
             err_display("expected closing bracket", tokens.get_last_ptr());
         }
 
-        let equiv_deref_expr = Expr::new(ExprEnum::Deref(Box::new(Expr::new(ExprEnum::BinOp(
-            BinOp::Plus,
-            Box::new(curr_expr),
-            Box::new(arr_size),
-        )))));
+        let equiv_deref_expr = Expr::new(
+            ExprEnum::Deref(Box::new(Expr::new(
+                ExprEnum::BinOp(BinOp::Plus, Box::new(curr_expr), Box::new(arr_size)),
+                pos,
+            ))),
+            pos,
+        );
 
-        return attach_postfix_ops(tokens, equiv_deref_expr);
+        attach_postfix_ops(tokens, equiv_deref_expr)
     } else {
-        return curr_expr;
+        curr_expr
+    }
+}
+
+/// parses the arguments of a function call after the open paren, along with the closing paren
+fn parse_call_args(tokens: &mut TokenCursor) -> Vec<Expr> {
+    let args = parse_function_args(tokens);
+    if tokens.next() != Some(&Token::CloseParen) {
+        err_display(
+            format!(
+                "expected closing parenthesis, found {:?}",
+                tokens.last().unwrap()
+            ),
+            tokens.get_last_ptr(),
+        )
     }
+    args
 }
 
 fn parse_function_args(tokens: &mut TokenCursor) -> Vec<Expr> {
@@ -140,10 +250,8 @@ fn parse_function_args(tokens: &mut TokenCursor) -> Vec<Expr> {
         return Vec::new();
     }
     loop {
-        args.push(generate_expr_ast(
-            tokens,
-            BinOpPrecedenceLevel::lowest_level(),
-        ));
+        // each argument is an assignment expression, since commas separate the arguments
+        args.push(generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment));
         if tokens.peek() == Some(&Token::Comma) {
             tokens.next(); // consume the comma
         } else {
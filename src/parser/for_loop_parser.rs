@@ -1,4 +1,5 @@
-use super::types_parser::parse_variable_declaration;
+use super::scopes::{enter_scope, exit_scope};
+use super::types_parser::{is_type_specifier, parse_variable_declaration};
 use crate::errors::display::err_display;
 use crate::parser::expr_parser::{generate_expr_ast, BinOpPrecedenceLevel};
 use crate::parser::{generate_statement_ast, Statement, TokenCursor};
@@ -16,16 +17,29 @@ pub fn generate_for_loop_ast(tokens: &mut TokenCursor) -> Statement {
         )
     }
 
-    let initial_clause = if let Some(&Token::Type(_)) = tokens.peek() {
-        // initial clause is a declare statement
-        parse_variable_declaration(tokens)
+    // variables declared in the initial clause are only visible inside of the loop
+    enter_scope();
+    let initial_clause = if is_type_specifier(tokens.peek()) {
+        // initial clause declares one or more variables
+        let declarations = parse_variable_declaration(tokens);
+        if declarations.is_empty()
+            || declarations
+                .iter()
+                .any(|stmt| !matches!(stmt, Statement::Declare(_, _, _, _)))
+        {
+            err_display(
+                "the initial clause of a for loop may only declare variables",
+                tokens.get_last_ptr(),
+            )
+        }
+        declarations
     } else if tokens.peek() == Some(&Token::Semicolon) {
-        Statement::Empty
+        Vec::new()
     } else {
-        Statement::Expr(generate_expr_ast(
+        vec![Statement::Expr(generate_expr_ast(
             tokens,
             BinOpPrecedenceLevel::lowest_level(),
-        ))
+        ))]
     };
 
     if tokens.next() != Some(&Token::Semicolon) {
@@ -77,9 +91,10 @@ pub fn generate_for_loop_ast(tokens: &mut TokenCursor) -> Statement {
     }
 
     let loop_body = generate_statement_ast(tokens);
+    exit_scope();
 
     Statement::For(
-        Box::new(initial_clause),
+        initial_clause,
         controlling_expr,
         post_expr,
         Box::new(loop_body),
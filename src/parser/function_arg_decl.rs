@@ -1,36 +1,54 @@
-use std::collections::VecDeque;
+use crate::{errors::display::err_display, tokenizer::Token, types::VarType};
 
-use crate::{
-    errors::display::err_display,
-    tokenizer::{source_cursor::SourcePtr, Token},
-    types::VarType,
+use super::{
+    token_cursor::TokenCursor,
+    types_parser::{is_type_specifier, parse_declarator, parse_type_specifier},
 };
 
-use super::token_cursor::TokenCursor;
-
-pub fn parse_function_arg_decl(tokens: &mut TokenCursor) -> Vec<(String, VarType)> {
+/// parses the arguments of a function declaration. The names of arguments are optional,
+/// for example in the prototype `int f(int, char *);`
+pub fn parse_function_arg_decl(tokens: &mut TokenCursor) -> Vec<(Option<String>, VarType)> {
     let mut args = Vec::new();
 
     if tokens.peek() == Some(&Token::CloseParen) {
         return Vec::new();
     }
+    if tokens.peek() == Some(&Token::Void) && tokens.peek_nth(2) == Some(&Token::CloseParen) {
+        // `int f(void)` declares a function with no arguments
+        tokens.next();
+        return Vec::new();
+    }
 
     loop {
-        let mut token_buffer = VecDeque::new();
-        let mut paren_level = 0;
-        while tokens.peek().is_some()
-            && tokens.peek().unwrap() != &Token::Comma
-            && (paren_level > 0 || tokens.peek().unwrap() != &Token::CloseParen)
-        {
-            let next_token = tokens.next().unwrap().clone();
-            if next_token == Token::OpenParen {
-                paren_level += 1;
-            } else if next_token == Token::CloseParen {
-                paren_level -= 1;
-            }
-            token_buffer.push_back(next_token);
+        if !is_type_specifier(tokens.peek()) {
+            err_display(
+                format!(
+                    "expected type of argument to be specified, found {:?}",
+                    tokens.peek()
+                ),
+                tokens.get_last_ptr(),
+            )
+        }
+        let arg_type = parse_type_specifier(tokens);
+
+        let arg = match parse_declarator(tokens, arg_type) {
+            // arguments of array and function types are adjusted to pointers
+            (name, VarType::Arr(inner, _)) => (name, VarType::Ptr(inner)),
+            (name, type_ @ VarType::Func(_, _)) => (name, VarType::Ptr(Box::new(type_))),
+            arg => arg,
+        };
+        if arg.1 == VarType::Void {
+            err_display("argument has incomplete type void", tokens.get_last_ptr())
+        }
+        if let VarType::Struct(_) = arg.1 {
+            err_display(
+                format!(
+                    "passing structs by value is not supported, pass a pointer to {} instead",
+                    arg.1
+                ),
+                tokens.get_last_ptr(),
+            )
         }
-        let arg = parse_type_declaration(token_buffer, tokens.get_last_ptr());
         args.push(arg);
         if tokens.peek() == Some(&Token::Comma) {
             tokens.next(); // consume the comma
@@ -40,69 +58,3 @@ pub fn parse_function_arg_decl(tokens: &mut TokenCursor) -> Vec<(String, VarType
     }
     args
 }
-
-fn parse_type_declaration(mut tokens: VecDeque<Token>, location: SourcePtr) -> (String, VarType) {
-    let mut type_;
-    if let Some(Token::Type(t)) = tokens.front() {
-        type_ = VarType::Fund(*t);
-        tokens.pop_front();
-    } else {
-        err_display(
-            format!(
-                "expected type of argument to be specified, found {:?}",
-                tokens.front()
-            ),
-            location,
-        )
-    }
-
-    loop {
-        if tokens.is_empty() {
-            err_display("expected identifier name", location)
-        } else if tokens.len() == 1 {
-            match tokens.pop_back() {
-                Some(Token::Identifier { val }) => {
-                    if let VarType::Arr(inner, _) = type_ {
-                        type_ = VarType::Ptr(inner);
-                    }
-                    return (val.clone(), type_);
-                }
-                _ => err_display("expected identifier name", location),
-            }
-        } else if tokens.front() == Some(&Token::OpenParen)
-            && tokens.back() == Some(&Token::CloseParen)
-        {
-            tokens.pop_back();
-            tokens.pop_front();
-        } else if tokens.front() == Some(&Token::Star) {
-            tokens.pop_front();
-            type_ = VarType::Ptr(Box::new(type_));
-        } else if tokens.back() == Some(&Token::CloseBracket) {
-            tokens.pop_back();
-
-            let arr_size: usize;
-            if let Some(Token::IntLit { val }) = tokens.back() {
-                arr_size = str::parse(val).unwrap();
-                tokens.pop_back();
-
-                if let Some(Token::OpenBracket) = tokens.pop_back() {
-                    // do nothing, just consume the close bracket
-                } else {
-                    err_display("error parsing array type, OpenBracket not found", location)
-                }
-                type_ = VarType::Arr(Box::new(type_), arr_size);
-            } else {
-                if let Some(Token::OpenBracket) = tokens.pop_back() {
-                    // do nothing, just consume the close bracket
-                } else {
-                    err_display("error parsing array type, OpenBracket not found", location)
-                }
-                type_ = VarType::Ptr(Box::new(type_));
-            }
-        } else {
-            dbg!(type_);
-            dbg!(tokens);
-            err_display("error parsing declaration type", location);
-        }
-    }
-}
@@ -1,17 +1,20 @@
-static mut STATIC_STRINGS: Vec<String> = Vec::new(); // it's Vec
-pub fn add_static_string(s: String) {
+use std::ptr::addr_of_mut;
+
+static mut STATIC_STRINGS: Vec<Vec<u8>> = Vec::new(); // it's Vec
+pub fn add_static_string(s: Vec<u8>) {
     unsafe {
         // SAFETY: no race conditions in this single threaded program.
-        if !STATIC_STRINGS.contains(&s) {
-            STATIC_STRINGS.push(s);
+        let static_strings = &mut *addr_of_mut!(STATIC_STRINGS);
+        if !static_strings.contains(&s) {
+            static_strings.push(s);
         }
     }
 }
 
-pub fn get_string_label(s: &String) -> String {
+pub fn get_string_label(s: &[u8]) -> String {
     unsafe {
         // SAFETY: no race conditions in this single threaded program.
-        for (index, string) in STATIC_STRINGS.iter().enumerate() {
+        for (index, string) in (*addr_of_mut!(STATIC_STRINGS)).iter().enumerate() {
             if string == s {
                 return format!(".globl_str_{}", index);
             }
@@ -27,10 +30,10 @@ pub fn generate_global_strings_asm() -> String {
         let mut result = String::new();
 
         // SAFETY: no race conditions in this single threaded program.
-        for (index, string_val) in STATIC_STRINGS.iter().enumerate() {
+        for (index, string_val) in (*addr_of_mut!(STATIC_STRINGS)).iter().enumerate() {
             let string_label = format!(".globl_str_{}", index);
             let mut string_bytes = String::new();
-            for b in string_val.as_bytes() {
+            for b in string_val {
                 string_bytes.push_str(&b.to_string());
                 string_bytes.push_str(", ");
             }
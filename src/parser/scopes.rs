@@ -0,0 +1,195 @@
+use std::{collections::HashMap, ptr::addr_of_mut};
+
+use crate::{
+    tokenizer::source_cursor::SourcePtr,
+    types::{struct_defs::get_unique_struct_tag, VarType},
+};
+
+/// what an ordinary identifier (any identifier other than a struct tag, struct member or label)
+/// refers to, as far as the parser needs to know.
+enum OrdinaryIdent {
+    Var,
+    Func,
+    EnumConst(i64),
+    Typedef(VarType),
+}
+
+// one map for each scope level, the innermost scope being the last.
+static mut SCOPES: Vec<HashMap<String, OrdinaryIdent>> = Vec::new();
+/// what a struct or enum tag refers to
+#[derive(Clone)]
+pub enum Tag {
+    Struct(String), // the unique tag which the struct is registered with in struct_defs
+    Enum,
+}
+
+// struct and enum tags share a namespace of their own, with one map for each scope level
+static mut TAG_SCOPES: Vec<HashMap<String, Tag>> = Vec::new();
+// the enumerators declared since the last call to take_new_enum_consts
+static mut NEW_ENUM_CONSTS: Vec<(String, SourcePtr)> = Vec::new();
+
+pub fn enter_scope() {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(SCOPES)).push(HashMap::new());
+        (*addr_of_mut!(TAG_SCOPES)).push(HashMap::new());
+    }
+}
+
+pub fn exit_scope() {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(SCOPES)).pop();
+        (*addr_of_mut!(TAG_SCOPES)).pop();
+    }
+}
+
+/// returns the number of scopes which are currently open, so that they can be closed by `exit_scopes_until`
+pub fn get_scope_depth() -> usize {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(SCOPES)).len()
+    }
+}
+
+/// closes the scopes which were opened after the given depth,
+/// such as those left open by a declaration which was abandoned because of an error.
+pub fn exit_scopes_until(depth: usize) {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(SCOPES)).truncate(depth);
+        (*addr_of_mut!(TAG_SCOPES)).truncate(depth);
+    }
+}
+
+fn declare_ident(name: &str, ident: OrdinaryIdent) {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let scopes = &mut *addr_of_mut!(SCOPES);
+        scopes.last_mut().unwrap().insert(name.to_owned(), ident);
+    }
+}
+
+/// declares a variable in the current scope, hiding any enumerator or typedef name
+/// with the same name from an enclosing scope.
+pub fn declare_var(name: &str) {
+    declare_ident(name, OrdinaryIdent::Var);
+}
+
+/// declares a function, so that uses of its name evaluate to a pointer to the function.
+pub fn declare_func(name: &str) {
+    declare_ident(name, OrdinaryIdent::Func);
+}
+
+/// returns true if the name refers to a variable in the current scope.
+pub fn is_var(name: &str) -> bool {
+    matches!(find_ident(name), Some(OrdinaryIdent::Var))
+}
+
+/// returns true if the name refers to a function in the current scope, rather than to a variable hiding it.
+pub fn is_func(name: &str) -> bool {
+    matches!(find_ident(name), Some(OrdinaryIdent::Func))
+}
+
+/// declares an enumerator in the current scope.
+/// Redeclarations are not reported here, check_vars reports them along with variables.
+pub fn declare_enum_const(name: &str, value: i64, pos: SourcePtr) {
+    declare_ident(name, OrdinaryIdent::EnumConst(value));
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(NEW_ENUM_CONSTS)).push((name.to_owned(), pos));
+    }
+}
+
+/// returns the value of the enumerator with the given name, or None if the name
+/// does not refer to an enumerator in the current scope.
+pub fn get_enum_const(name: &str) -> Option<i64> {
+    match find_ident(name) {
+        Some(OrdinaryIdent::EnumConst(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// declares a typedef name in the current scope, which stands for the given type.
+pub fn declare_typedef(name: &str, type_: VarType) {
+    declare_ident(name, OrdinaryIdent::Typedef(type_));
+}
+
+/// returns the type that the typedef name stands for, or None if the name
+/// does not refer to a typedef in the current scope.
+pub fn get_typedef(name: &str) -> Option<VarType> {
+    match find_ident(name) {
+        Some(OrdinaryIdent::Typedef(type_)) => Some(type_.clone()),
+        _ => None,
+    }
+}
+
+/// returns what the name refers to in the innermost scope which declares it
+fn find_ident(name: &str) -> Option<&'static OrdinaryIdent> {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let scopes = &*addr_of_mut!(SCOPES);
+        scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+}
+
+/// returns true if the name has been declared in the innermost scope, as anything.
+pub fn is_declared_in_current_scope(name: &str) -> bool {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let scopes = &*addr_of_mut!(SCOPES);
+        scopes.last().unwrap().contains_key(name)
+    }
+}
+
+/// returns the names and locations of the enumerators declared since the last call,
+/// so that the declaration which defined them can record them in the AST.
+pub fn take_new_enum_consts() -> Vec<(String, SourcePtr)> {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        std::mem::take(&mut *addr_of_mut!(NEW_ENUM_CONSTS))
+    }
+}
+
+/// declares a struct tag in the current scope, hiding any struct or enum with the same tag from an
+/// enclosing scope, and returns the unique tag which the struct is registered with.
+pub fn declare_struct_tag(tag: &str) -> String {
+    let unique_tag = get_unique_struct_tag(tag);
+    declare_tag(tag, Tag::Struct(unique_tag.clone()));
+    unique_tag
+}
+
+/// declares an enum tag in the current scope, hiding any struct or enum with the same tag from an enclosing scope
+pub fn declare_enum_tag(tag: &str) {
+    declare_tag(tag, Tag::Enum);
+}
+
+fn declare_tag(name: &str, tag: Tag) {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let tag_scopes = &mut *addr_of_mut!(TAG_SCOPES);
+        tag_scopes.last_mut().unwrap().insert(name.to_owned(), tag);
+    }
+}
+
+/// returns what the tag refers to in the innermost scope which declares it, or None if it has not been declared.
+pub fn find_tag(tag: &str) -> Option<Tag> {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let tag_scopes = &*addr_of_mut!(TAG_SCOPES);
+        tag_scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(tag))
+            .cloned()
+    }
+}
+
+/// returns what the tag refers to if it was declared in the current scope
+pub fn find_tag_in_current_scope(tag: &str) -> Option<Tag> {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let tag_scopes = &*addr_of_mut!(TAG_SCOPES);
+        tag_scopes.last().unwrap().get(tag).cloned()
+    }
+}
@@ -14,16 +14,68 @@ impl TokenCursor {
     pub fn peek(&self) -> Option<&Token> {
         self.contents.get(self.index).map(|(token, _)| token)
     }
-    // pub fn peek_nth(&self, n: usize) -> Option<&Token> {
-    //     // peek_nth(1) is equivalent to peek()
-    //     self.contents
-    //         .get(self.index + n - 1)
-    //         .map(|(token, _)| token)
-    // }
+    pub fn peek_nth(&self, n: usize) -> Option<&Token> {
+        // peek_nth(1) is equivalent to peek()
+        self.contents
+            .get(self.index + n - 1)
+            .map(|(token, _)| token)
+    }
 
-    // pub fn peek_ptr(&self) -> Option<&SourcePtr> {
-    //     self.contents.get(self.index).map(|(_, src_ptr)| src_ptr)
-    // }
+    /// returns the location of the next token, or of the last token at the end of the file
+    pub fn peek_ptr(&self) -> SourcePtr {
+        match self.contents.get(self.index) {
+            Some((_, src_ptr)) => *src_ptr,
+            None => self.get_last_ptr(),
+        }
+    }
+
+    /// the number of tokens consumed so far, which can be passed to `skip_past_statement`
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    /// after an error, moves the cursor to the end of the statement or declaration that begins at `start`,
+    /// so that parsing can continue with the next one. The statement ends at a `;` which is not inside of
+    /// parentheses or braces, or at a `}` closing a brace opened by the statement (along with a `;` after it,
+    /// or an else branch).
+    /// A `}` which was not opened by the statement closes the enclosing block, so it is not consumed.
+    pub fn skip_past_statement(&mut self, start: usize) {
+        self.index = start;
+        let mut paren_depth = 0;
+        let mut brace_depth = 0;
+        while let Some(token) = self.peek() {
+            match token {
+                Token::OpenParen => paren_depth += 1,
+                Token::CloseParen if paren_depth > 0 => paren_depth -= 1,
+                Token::OpenBrace => brace_depth += 1,
+                Token::CloseBrace if brace_depth == 0 => break,
+                Token::CloseBrace if brace_depth == 1 => {
+                    self.next();
+                    brace_depth = 0;
+                    match self.peek() {
+                        Some(Token::Semicolon) => {
+                            self.next();
+                            break;
+                        }
+                        // the else branch of an if statement belongs to the same statement
+                        Some(Token::Else) => {}
+                        _ => break,
+                    }
+                }
+                Token::CloseBrace => brace_depth -= 1,
+                Token::Semicolon if paren_depth == 0 && brace_depth == 0 => {
+                    self.next();
+                    break;
+                }
+                _ => {}
+            }
+            self.next();
+        }
+        // always skip at least one token, so that the parser cannot get stuck on a stray `}`
+        if self.index == start && self.peek().is_some() {
+            self.next();
+        }
+    }
 
     // pub fn peek_nth_ptr(&self, n: usize) -> Option<&SourcePtr> {
     //     self.contents
@@ -1,105 +1,645 @@
-use std::collections::VecDeque;
-
 use crate::{
     errors::display::err_display,
     tokenizer::{operator::Op, source_cursor::SourcePtr, Token},
-    types::VarType,
+    types::{
+        struct_defs::{declare_struct, define_struct, get_new_anonymous_struct_tag},
+        FundT, VarType,
+    },
 };
 
 use super::{
-    arr_initializer_expr::generate_arr_init_expr_ast,
+    arr_initializer_expr::{generate_arr_init_expr_ast, get_initialized_type},
+    const_eval::eval_int_const_expr,
     expr_parser::{generate_expr_ast, BinOpPrecedenceLevel},
+    function_arg_decl::parse_function_arg_decl,
+    scopes::{
+        declare_enum_const, declare_enum_tag, declare_struct_tag, declare_typedef, declare_var,
+        find_tag, find_tag_in_current_scope, get_typedef, is_declared_in_current_scope,
+        take_new_enum_consts, Tag,
+    },
     token_cursor::TokenCursor,
     Statement,
 };
 
-pub fn parse_variable_declaration(tokens: &mut TokenCursor) -> Statement {
-    let fund_t;
-    if let Some(Token::Type(t)) = tokens.next() {
-        fund_t = VarType::Fund(*t);
+/// returns true if the token can begin a type, such as `int`, `struct` or a typedef name
+pub fn is_type_specifier(token: Option<&Token>) -> bool {
+    match token {
+        Some(Token::Type(_))
+        | Some(Token::Signed)
+        | Some(Token::Unsigned)
+        | Some(Token::Void)
+        | Some(Token::Struct)
+        | Some(Token::Enum) => true,
+        Some(Token::Identifier { val }) => get_typedef(val).is_some(),
+        _ => false,
+    }
+}
+
+/// returns true if the token can begin a declaration, which is either a type or `typedef`
+pub fn is_declaration_start(token: Option<&Token>) -> bool {
+    token == Some(&Token::Typedef) || is_type_specifier(token)
+}
+
+/// parses the type at the beginning of a declaration, for example the `int` in `int *a[3]`.
+/// A struct definition, such as `struct point { int x; int y; }`, is also a type specifier.
+pub fn parse_type_specifier(tokens: &mut TokenCursor) -> VarType {
+    match tokens.next() {
+        Some(Token::Type(t)) => VarType::Fund(*t),
+        Some(Token::Signed) => match tokens.peek() {
+            Some(Token::Type(t)) if !t.is_float() => {
+                let t = *t;
+                tokens.next();
+                VarType::Fund(t)
+            }
+            Some(Token::Type(_))
+            | Some(Token::Signed)
+            | Some(Token::Unsigned)
+            | Some(Token::Void)
+            | Some(Token::Struct)
+            | Some(Token::Enum) => err_display(
+                format!(
+                    "`signed` cannot be combined with {:?}",
+                    tokens.peek().unwrap()
+                ),
+                tokens.get_last_ptr(),
+            ),
+            // `signed` on its own means `signed int`
+            _ => VarType::Fund(FundT::Int),
+        },
+        Some(Token::Unsigned) => match tokens.peek() {
+            Some(Token::Type(t)) if !t.is_float() => {
+                let t = t.to_unsigned();
+                tokens.next();
+                VarType::Fund(t)
+            }
+            Some(Token::Type(_))
+            | Some(Token::Signed)
+            | Some(Token::Unsigned)
+            | Some(Token::Void)
+            | Some(Token::Struct)
+            | Some(Token::Enum) => err_display(
+                format!(
+                    "`unsigned` cannot be combined with {:?}",
+                    tokens.peek().unwrap()
+                ),
+                tokens.get_last_ptr(),
+            ),
+            // `unsigned` on its own means `unsigned int`
+            _ => VarType::Fund(FundT::UInt),
+        },
+        Some(Token::Void) => VarType::Void,
+        Some(Token::Struct) => parse_struct_specifier(tokens),
+        Some(Token::Enum) => parse_enum_specifier(tokens),
+        Some(Token::Identifier { val }) if get_typedef(val).is_some() => get_typedef(val).unwrap(),
+        _ => err_display(
+            format!("expected type, found {:?}", tokens.last()),
+            tokens.get_last_ptr(),
+        ),
+    }
+}
+
+fn parse_struct_specifier(tokens: &mut TokenCursor) -> VarType {
+    let optional_tag = match tokens.peek() {
+        Some(Token::Identifier { val }) => {
+            let tag = val.clone();
+            tokens.next();
+            Some(tag)
+        }
+        Some(Token::OpenBrace) => None,
+        _ => err_display(
+            "expected struct tag or `{` after `struct`",
+            tokens.get_last_ptr(),
+        ),
+    };
+
+    if tokens.peek() != Some(&Token::OpenBrace) {
+        // a use of a struct tag which is not in scope declares the struct in the current scope
+        let tag = optional_tag.unwrap();
+        let unique_tag = match find_tag(&tag) {
+            Some(Tag::Struct(unique_tag)) => unique_tag,
+            Some(Tag::Enum) => err_display(
+                format!("{} is declared as an enum, not a struct", tag),
+                tokens.get_last_ptr(),
+            ),
+            None => declare_struct_tag(&tag),
+        };
+        declare_struct(&unique_tag);
+        return VarType::Struct(unique_tag);
+    }
+    tokens.next(); // consume the '{'
+
+    // a definition hides any struct with the same tag from an enclosing scope,
+    // but completes a struct which was only declared in the current scope
+    let tag = match optional_tag {
+        Some(tag) => match find_tag_in_current_scope(&tag) {
+            Some(Tag::Enum) => err_display(
+                format!("{} is declared as an enum, not a struct", tag),
+                tokens.get_last_ptr(),
+            ),
+            Some(Tag::Struct(unique_tag)) => {
+                if VarType::Struct(unique_tag.clone()).is_complete() {
+                    err_display(
+                        format!("redefinition of struct {}", tag),
+                        tokens.get_last_ptr(),
+                    );
+                }
+                unique_tag
+            }
+            None => declare_struct_tag(&tag),
+        },
+        None => get_new_anonymous_struct_tag(),
+    };
+    // declare the struct before parsing the members, so that members may point to the struct itself
+    declare_struct(&tag);
+
+    let mut members: Vec<(String, VarType)> = Vec::new();
+    while tokens.peek() != Some(&Token::CloseBrace) {
+        if !is_type_specifier(tokens.peek()) {
+            err_display(
+                format!(
+                    "expected struct member declaration, found {:?}",
+                    tokens.peek()
+                ),
+                tokens.get_last_ptr(),
+            );
+        }
+        let base_type = parse_type_specifier(tokens);
+
+        loop {
+            let (member_name, member_type) = parse_type_declaration(tokens, base_type.clone());
+
+            if !member_type.is_complete() {
+                err_display(
+                    format!("struct member {} has incomplete type", member_name),
+                    tokens.get_last_ptr(),
+                );
+            }
+            if members.iter().any(|(name, _)| name == &member_name) {
+                err_display(
+                    format!("duplicate member {} in struct {}", member_name, tag),
+                    tokens.get_last_ptr(),
+                );
+            }
+            members.push((member_name, member_type));
+
+            if tokens.peek() == Some(&Token::Comma) {
+                tokens.next(); // consume the comma
+            } else {
+                break;
+            }
+        }
+
+        if tokens.next() != Some(&Token::Semicolon) {
+            err_display(
+                "expected semicolon after struct member",
+                tokens.get_last_ptr(),
+            );
+        }
+    }
+    tokens.next(); // consume the '}'
+
+    if members.is_empty() {
+        err_display(
+            format!("struct {} has no members", tag),
+            tokens.get_last_ptr(),
+        );
+    }
+    define_struct(&tag, members);
+
+    VarType::Struct(tag)
+}
+
+/// parses an enum specifier after the `enum` keyword, such as `color { RED, GREEN = 5, BLUE }`.
+/// Each enumerator is declared in the current scope, and enum types are treated as `int`.
+fn parse_enum_specifier(tokens: &mut TokenCursor) -> VarType {
+    let tag = match tokens.peek() {
+        Some(Token::Identifier { val }) => {
+            let tag = val.clone();
+            tokens.next();
+            Some(tag)
+        }
+        Some(Token::OpenBrace) => None,
+        _ => err_display(
+            "expected enum tag or `{` after `enum`",
+            tokens.get_last_ptr(),
+        ),
+    };
+
+    if tokens.peek() != Some(&Token::OpenBrace) {
+        let tag = tag.unwrap();
+        match find_tag(&tag) {
+            Some(Tag::Enum) => {}
+            Some(Tag::Struct(_)) => err_display(
+                format!("{} is declared as a struct, not an enum", tag),
+                tokens.get_last_ptr(),
+            ),
+            None => err_display(
+                format!("enum {} is not defined", tag),
+                tokens.get_last_ptr(),
+            ),
+        }
+        return VarType::Fund(FundT::Int);
+    }
+    tokens.next(); // consume the '{'
+
+    // a definition hides any tag from an enclosing scope, but not one declared in the current scope
+    if let Some(tag) = tag {
+        match find_tag_in_current_scope(&tag) {
+            Some(Tag::Enum) => err_display(
+                format!("redefinition of enum {}", tag),
+                tokens.get_last_ptr(),
+            ),
+            Some(Tag::Struct(_)) => err_display(
+                format!("{} is declared as a struct, not an enum", tag),
+                tokens.get_last_ptr(),
+            ),
+            None => declare_enum_tag(&tag),
+        }
+    }
+
+    // an enumerator without a value is one more than the previous enumerator
+    let mut value: i64 = 0;
+    loop {
+        let name = match tokens.next() {
+            Some(Token::Identifier { val }) => val.clone(),
+            _ => err_display("expected enumerator name", tokens.get_last_ptr()),
+        };
+        let pos = tokens.get_last_ptr();
+        if tokens.peek() == Some(&Token::Op(Op::AssignmentEquals)) {
+            tokens.next(); // consume the '='
+            let expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment);
+            value = match eval_int_const_expr(expr) {
+                Some(value) => value,
+                None => err_display(
+                    format!("value of enumerator {} is not an integer constant", name),
+                    tokens.get_last_ptr(),
+                ),
+            };
+        }
+        if i32::try_from(value).is_err() {
+            err_display(
+                format!("value of enumerator {} does not fit in an int", name),
+                tokens.get_last_ptr(),
+            );
+        }
+        declare_enum_const(&name, value, pos);
+        value += 1;
+
+        match tokens.next() {
+            Some(Token::Comma) => {
+                if tokens.peek() == Some(&Token::CloseBrace) {
+                    tokens.next(); // a trailing comma is allowed
+                    break;
+                }
+            }
+            Some(Token::CloseBrace) => break,
+            _ => err_display(
+                "expected `,` or `}` after enumerator",
+                tokens.get_last_ptr(),
+            ),
+        }
+    }
+
+    VarType::Fund(FundT::Int)
+}
+
+/// parses a declaration, such as `int *a[3] = {0}`, `enum color { RED, GREEN };` or `typedef char *str;`.
+/// Any enumerators that are defined are recorded with a Statement::EnumConsts before the variable.
+pub fn parse_variable_declaration(tokens: &mut TokenCursor) -> Vec<Statement> {
+    let is_typedef = tokens.peek() == Some(&Token::Typedef);
+    if is_typedef {
+        tokens.next(); // consume the "typedef"
+    }
+    let is_tag_type = matches!(tokens.peek(), Some(Token::Struct) | Some(Token::Enum));
+    let fund_t = parse_type_specifier(tokens);
+
+    let mut result = Vec::new();
+    let enum_consts = take_new_enum_consts();
+    if !enum_consts.is_empty() {
+        result.push(Statement::EnumConsts(enum_consts));
+    }
+
+    if is_tag_type && tokens.peek() == Some(&Token::Semicolon) {
+        // only declares (or defines) a struct or enum, such as `struct point { int x; int y; };`
+        return result;
+    }
+
+    if is_typedef {
+        loop {
+            parse_typedef_declarator(tokens, fund_t.clone());
+            if tokens.peek() != Some(&Token::Comma) {
+                break;
+            }
+            tokens.next(); // consume the comma
+        }
     } else {
-        err_display("expected fundamental type first", tokens.get_last_ptr());
+        result.extend(parse_init_declarator_list(tokens, fund_t));
     }
+    result
+}
 
-    let mut token_buffer = VecDeque::new();
-    while tokens.peek().is_some()
-        && tokens.peek().unwrap() != &Token::Semicolon
-        && tokens.peek().unwrap() != &Token::Op(Op::AssignmentEquals)
-    {
-        token_buffer.push_back(tokens.next().unwrap().clone());
+/// parses comma separated declarators which share a type specifier, such as the `a, *b = &a` in `int a, *b = &a;`.
+/// Each declarator becomes its own declaration, in order, so that initializers may refer to earlier variables.
+pub fn parse_init_declarator_list(tokens: &mut TokenCursor, fund_t: VarType) -> Vec<Statement> {
+    let mut declarations = vec![parse_init_declarator(tokens, fund_t.clone())];
+    while tokens.peek() == Some(&Token::Comma) {
+        tokens.next(); // consume the comma
+        declarations.push(parse_init_declarator(tokens, fund_t.clone()));
     }
+    declarations
+}
 
-    let (decl_identifier, type_) =
-        parse_type_declaration(token_buffer, tokens.get_last_ptr(), fund_t);
+/// parses the rest of a typedef after the type specifier, for example the `*str` in `typedef char *str;`,
+/// and declares the typedef name in the current scope.
+fn parse_typedef_declarator(tokens: &mut TokenCursor, fund_t: VarType) {
+    let (name, type_) = parse_type_declaration(tokens, fund_t);
+    if is_declared_in_current_scope(&name) {
+        err_display(
+            format!("redeclaration of {} as a typedef name", name),
+            tokens.get_last_ptr(),
+        );
+    }
+    declare_typedef(&name, type_);
+}
+
+/// parses the rest of a variable declaration after the type specifier,
+/// for example the `*a[3] = {0}` in `int *a[3] = {0}`.
+fn parse_init_declarator(tokens: &mut TokenCursor, fund_t: VarType) -> Statement {
+    let pos = tokens.peek_ptr();
+    let (decl_identifier, type_) = parse_type_declaration(tokens, fund_t);
+
+    if let VarType::Func(_, _) = type_ {
+        err_display(
+            format!(
+                "function {} may only be declared at the top level, by a simple declarator",
+                decl_identifier
+            ),
+            tokens.get_last_ptr(),
+        );
+    }
+    // the length of an array such as `int a[] = {1, 2, 3}` is determined by its initializer
+    let is_initialized_array = matches!(&type_, VarType::Arr(inner, 0) if inner.is_complete())
+        && tokens.peek() == Some(&Token::Op(Op::AssignmentEquals));
+    if !type_.is_complete() && !is_initialized_array {
+        err_display(
+            format!("variable {} has incomplete type {}", decl_identifier, type_),
+            tokens.get_last_ptr(),
+        );
+    }
+    if get_typedef(&decl_identifier).is_some() && is_declared_in_current_scope(&decl_identifier) {
+        err_display(
+            format!("typedef name {} redeclared as a variable", decl_identifier),
+            tokens.get_last_ptr(),
+        );
+    }
+    // the variable is in scope in its own initializer
+    declare_var(&decl_identifier);
 
     let mut optional_expr = None;
     if tokens.peek() == Some(&Token::Op(Op::AssignmentEquals)) {
         tokens.next(); // consume the '='
         match type_ {
             VarType::Fund(_) | VarType::Ptr(_) => {
-                optional_expr = Some(generate_expr_ast(
-                    tokens,
-                    BinOpPrecedenceLevel::lowest_level(),
-                ))
+                optional_expr = Some(generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment))
+            }
+            VarType::Struct(_) if tokens.peek() != Some(&Token::OpenBrace) => {
+                // initialized by copying another struct
+                optional_expr = Some(generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment))
+            }
+            VarType::Arr(_, _) | VarType::Struct(_) => {
+                optional_expr = Some(generate_arr_init_expr_ast(tokens, &type_))
             }
-            VarType::Arr(_, _) => optional_expr = Some(generate_arr_init_expr_ast(tokens, &type_)),
+            // variables of type void and of function types were rejected above
+            VarType::Void | VarType::Func(_, _) => unreachable!(),
         }
     }
-    Statement::Declare(decl_identifier, optional_expr, type_)
+
+    let type_ = match &optional_expr {
+        Some(expr) => get_initialized_type(type_, expr),
+        None => type_,
+    };
+    if !type_.is_complete() {
+        err_display(
+            format!("array {} is initialized with no elements", decl_identifier),
+            tokens.get_last_ptr(),
+        );
+    }
+    Statement::Declare(decl_identifier, optional_expr, type_, pos)
 }
 
-fn parse_type_declaration(
-    mut tokens: VecDeque<Token>,
-    location: SourcePtr,
-    fund_t: VarType,
-) -> (String, VarType) {
-    // since the type syntax in C must be read from inside out, we will read a bunch of
-    // tokens into a VecDeque and then peel off from both ends,
-    // prioritizing pointers before arrays
+/// parses a type without an identifier, such as the `struct point *` in `sizeof(struct point *)`
+/// or the `int (*)(int)` in `(int (*)(int)) p`.
+pub fn parse_type_name(tokens: &mut TokenCursor) -> VarType {
+    let fund_t = parse_type_specifier(tokens);
+
+    match parse_declarator(tokens, fund_t) {
+        (None, type_) => type_,
+        (Some(name), _) => err_display(
+            format!("unexpected identifier {} in type name", name),
+            tokens.get_last_ptr(),
+        ),
+    }
+}
+
+/// parses a declarator which must declare a name, such as the `*names[4]` in `char *names[4]`.
+pub fn parse_type_declaration(tokens: &mut TokenCursor, fund_t: VarType) -> (String, VarType) {
+    match parse_declarator(tokens, fund_t) {
+        (Some(name), type_) => (name, type_),
+        (None, _) => err_display("expected identifier name", tokens.get_last_ptr()),
+    }
+}
+
+/// the ways in which a declarator derives a type from the type it is applied to
+#[derive(Clone)]
+enum Derivation {
+    Ptr,
+    Arr(usize),                           // the length is 0 for arrays of unknown length
+    Func(Vec<(Option<String>, VarType)>), // the parameters, with their names if they are given
+}
+
+/// parses a declarator, returning the declared name (None for an abstract declarator, as in a type name)
+/// and the declared type. For example, `int (*fp)(int, int)` declares fp as a pointer
+/// to a function taking two ints and returning an int.
+pub fn parse_declarator(tokens: &mut TokenCursor, fund_t: VarType) -> (Option<String>, VarType) {
+    let (name, derivations) = parse_declarator_derivations(tokens);
 
     let mut type_ = fund_t;
+    for derivation in derivations {
+        type_ = apply_derivation(tokens, type_, derivation);
+    }
+    (name.map(|(name, _)| name), type_)
+}
 
+/// parses the declarator of a function definition or prototype, returning the name of the function and
+/// where it is, its parameters and its return type. For example, `(*get(void))(int)` in `int (*get(void))(int)`
+/// declares get as a function taking no parameters and returning a pointer to a function taking an int.
+pub fn parse_function_declarator(
+    tokens: &mut TokenCursor,
+    fund_t: VarType,
+) -> (String, SourcePtr, Vec<(Option<String>, VarType)>, VarType) {
+    let (name, mut derivations) = parse_declarator_derivations(tokens);
+    let Some((name, pos)) = name else {
+        err_display(
+            "function name must be an identifier!",
+            tokens.get_last_ptr(),
+        );
+    };
+    // the suffix closest to the name is applied last, and it declares the parameters of the function itself
+    let Some(Derivation::Func(params)) = derivations.pop() else {
+        err_display(
+            format!("expected `(` to begin the arguments of function {}", name),
+            pos,
+        );
+    };
+
+    let mut return_type = fund_t;
+    for derivation in derivations {
+        return_type = apply_derivation(tokens, return_type, derivation);
+    }
+    match return_type {
+        VarType::Arr(_, _) | VarType::Func(_, _) => err_display(
+            format!("declaration of a function returning {}", return_type),
+            pos,
+        ),
+        VarType::Struct(_) => err_display(
+            format!(
+                "returning structs by value is not supported, return a pointer to {} instead",
+                return_type
+            ),
+            pos,
+        ),
+        _ => {}
+    }
+    (name, pos, params, return_type)
+}
+
+/// derives a pointer, array or function type from `type_`
+fn apply_derivation(tokens: &TokenCursor, type_: VarType, derivation: Derivation) -> VarType {
+    match derivation {
+        Derivation::Ptr => VarType::Ptr(Box::new(type_)),
+        Derivation::Arr(_) if matches!(type_, VarType::Func(_, _)) => err_display(
+            "declaration of an array of functions",
+            tokens.get_last_ptr(),
+        ),
+        Derivation::Arr(_) if matches!(type_, VarType::Arr(_, 0)) => err_display(
+            "error parsing array type: only the outermost array may have an unknown length",
+            tokens.get_last_ptr(),
+        ),
+        Derivation::Arr(len) => VarType::Arr(Box::new(type_), len),
+        Derivation::Func(_) if matches!(type_, VarType::Arr(_, _) | VarType::Func(_, _)) => {
+            err_display(
+                format!("declaration of a function returning {}", type_),
+                tokens.get_last_ptr(),
+            )
+        }
+        Derivation::Func(_) if matches!(type_, VarType::Struct(_)) => err_display(
+            format!(
+                "returning structs by value is not supported, return a pointer to {} instead",
+                type_
+            ),
+            tokens.get_last_ptr(),
+        ),
+        Derivation::Func(params) => VarType::Func(
+            Box::new(type_),
+            params.into_iter().map(|(_, t)| t).collect(),
+        ),
+    }
+}
+
+/// returns the name declared by a declarator, along with its derivations in the order
+/// they are applied to the type specifier. Pointers bind more loosely than array and function suffixes,
+/// so in `*names[4]` the array is applied first, and then the pointer.
+fn parse_declarator_derivations(
+    tokens: &mut TokenCursor,
+) -> (Option<(String, SourcePtr)>, Vec<Derivation>) {
+    let mut derivations = Vec::new();
+    while tokens.peek() == Some(&Token::Star) {
+        tokens.next();
+        derivations.push(Derivation::Ptr);
+    }
+
+    let (name, inner_derivations) = match tokens.peek() {
+        Some(Token::Identifier { val }) => {
+            let name = val.clone();
+            tokens.next();
+            (Some((name, tokens.get_last_ptr())), Vec::new())
+        }
+        Some(Token::OpenParen) if is_nested_declarator(tokens.peek_nth(2)) => {
+            tokens.next(); // consume the '('
+            let inner = parse_declarator_derivations(tokens);
+            if tokens.next() != Some(&Token::CloseParen) {
+                err_display("expected `)` in declarator", tokens.get_last_ptr());
+            }
+            inner
+        }
+        _ => (None, Vec::new()),
+    };
+
+    let mut suffixes = Vec::new();
     loop {
-        if tokens.is_empty() {
-            err_display("expected identifier name", location)
-        } else if tokens.len() == 1 {
-            match tokens.pop_back() {
-                Some(Token::Identifier { val }) => return (val.clone(), type_),
-                _ => err_display("expected identifier name", location),
+        match tokens.peek() {
+            Some(Token::OpenBracket) => {
+                tokens.next(); // consume the '['
+                suffixes.push(Derivation::Arr(parse_array_length(tokens)));
+                if tokens.next() != Some(&Token::CloseBracket) {
+                    err_display(
+                        "error parsing array type, CloseBracket not found",
+                        tokens.get_last_ptr(),
+                    );
+                }
             }
-        } else if tokens.front() == Some(&Token::OpenParen)
-            && tokens.back() == Some(&Token::CloseParen)
-        {
-            tokens.pop_back();
-            tokens.pop_front();
-        } else if tokens.front() == Some(&Token::Star) {
-            tokens.pop_front();
-            type_ = VarType::Ptr(Box::new(type_));
-        } else if tokens.back() == Some(&Token::CloseBracket) {
-            tokens.pop_back();
-
-            let arr_size: usize;
-            if let Some(Token::IntLit { val }) = tokens.pop_back() {
-                arr_size = str::parse(&val).unwrap();
-                if arr_size == 0 {
-                    err_display("error parsing array type: zero length", location)
+            Some(Token::OpenParen) => {
+                tokens.next(); // consume the '('
+                let params = parse_function_arg_decl(tokens);
+                if tokens.next() != Some(&Token::CloseParen) {
+                    err_display(
+                        "expected `)` to end function parameters",
+                        tokens.get_last_ptr(),
+                    );
                 }
-            } else {
-                err_display(
-                    "error parsing array type, integer length not found",
-                    location,
-                )
+                suffixes.push(Derivation::Func(params));
             }
+            _ => break,
+        }
+    }
 
-            if let Some(Token::OpenBracket) = tokens.pop_back() {
-                // do nothing, just consume the close bracket
-            } else {
-                err_display("error parsing array type, OpenBracket not found", location)
-            }
+    // the suffix closest to the name is applied last, as in `int a[2][3]`, an array of 2 arrays of 3 ints
+    derivations.extend(suffixes.into_iter().rev());
+    derivations.extend(inner_derivations);
+    (name, derivations)
+}
 
-            type_ = VarType::Arr(Box::new(type_), arr_size);
-        } else {
-            err_display("error parsing declaration type", location);
-        }
+/// returns true if a `(` followed by the given token begins a parenthesized declarator, such as the
+/// `(*fp)` in `int (*fp)(int)`, instead of the parameters of a function, such as in `int (int)`.
+fn is_nested_declarator(token: Option<&Token>) -> bool {
+    match token {
+        Some(Token::Star) | Some(Token::OpenParen) | Some(Token::OpenBracket) => true,
+        Some(Token::Identifier { val }) => get_typedef(val).is_none(),
+        _ => false,
+    }
+}
+
+/// evaluates the length of an array type, which may be any integer constant expression.
+/// Returns 0 if the length is omitted, as in `int a[]`.
+fn parse_array_length(tokens: &mut TokenCursor) -> usize {
+    if tokens.peek() == Some(&Token::CloseBracket) {
+        return 0;
+    }
+    let expr = generate_expr_ast(tokens, BinOpPrecedenceLevel::Assignment);
+
+    match eval_int_const_expr(expr) {
+        Some(0) => err_display(
+            "error parsing array type: zero length",
+            tokens.get_last_ptr(),
+        ),
+        Some(length) if length < 0 => err_display(
+            "error parsing array type: negative length",
+            tokens.get_last_ptr(),
+        ),
+        Some(length) => length as usize,
+        None => err_display(
+            "error parsing array type: length is not an integer constant",
+            tokens.get_last_ptr(),
+        ),
     }
 }
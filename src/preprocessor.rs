@@ -0,0 +1,400 @@
+mod if_expr;
+pub mod line_map;
+mod macros;
+mod pp_token;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    errors::display::{err_display, err_display_no_source},
+    tokenizer::source_cursor::SourcePtr,
+};
+
+use self::{
+    if_expr::eval_if_condition,
+    line_map::{add_output_line, add_source_file, get_num_output_lines},
+    macros::{expand_macros, is_macro_name, parse_define, ExpansionEnv, Macro},
+    pp_token::{get_pp_tokens, pp_tokens_to_string, PpToken, PpTokenKind},
+};
+
+const MAX_INCLUDE_DEPTH: usize = 200;
+
+struct PreprocessorEnv {
+    macros: HashMap<String, Macro>,
+    include_dirs: Vec<PathBuf>,
+    include_depth: usize,
+    output: Vec<String>, // the lines of preprocessed source code
+}
+
+/// the state of an `#if` ... `#endif` group in the file being preprocessed
+struct Conditional {
+    is_active: bool,        // whether the lines of the current branch are kept
+    has_taken_branch: bool, // whether any branch of the group has been kept so far
+    is_parent_active: bool, // whether the group itself is inside of a kept branch
+    has_else: bool,
+    output_line: usize, // the line of the `#if`, for reporting a missing `#endif`
+}
+
+/// expands macros, includes files and removes comments and inactive conditional branches.
+/// Each line of the result comes from a single line of some source file, so that
+/// `line_map` can map the line numbers of tokens back to the original source code.
+pub fn preprocess(
+    file_name: &str,
+    contents: &str,
+    include_dirs: &[String],
+    defines: &[String],
+) -> String {
+    let mut env = PreprocessorEnv {
+        macros: HashMap::new(),
+        include_dirs: include_dirs.iter().map(PathBuf::from).collect(),
+        include_depth: 0,
+        output: Vec::new(),
+    };
+
+    for define in defines {
+        // `-D NAME` defines NAME as 1, and `-D NAME=VALUE` defines it as VALUE
+        let (name, value) = define.split_once('=').unwrap_or((define, "1"));
+        let (name, macro_) = parse_define(&get_pp_tokens(&format!("{} {}", name, value)));
+        env.macros.insert(name, macro_);
+    }
+
+    preprocess_file(file_name, contents, &mut env);
+    env.output.join("\n") + "\n"
+}
+
+/// reports an error on the line which is currently being preprocessed
+pub fn pp_err_display<S: Into<String>>(msg: S) -> ! {
+    match get_num_output_lines() {
+        0 => err_display_no_source(msg),
+        num_lines => err_display(
+            msg,
+            SourcePtr {
+                line: num_lines,
+                col: 1,
+            },
+        ),
+    }
+}
+
+fn preprocess_file(file_name: &str, contents: &str, env: &mut PreprocessorEnv) {
+    let file_index = add_source_file(file_name);
+    let lines = get_logical_lines(contents, file_index);
+    let mut conditionals: Vec<Conditional> = Vec::new();
+    let mut line_index = 0;
+
+    while line_index < lines.len() {
+        let (text, line) = &lines[line_index];
+        line_index += 1;
+        add_output_line(file_index, *line);
+        let tokens = get_pp_tokens(text);
+
+        if tokens.first().is_some_and(|token| token.is_punct("#")) {
+            // directives are replaced by an empty line
+            env.output.push(String::new());
+            handle_directive(&tokens[1..], file_name, *line, &mut conditionals, env);
+            continue;
+        }
+        if !conditionals.last().map_or(true, |c| c.is_active) {
+            env.output.push(String::new());
+            continue;
+        }
+
+        let expansion_env = ExpansionEnv {
+            macros: &env.macros,
+            file_name,
+            line: *line,
+        };
+        if !tokens
+            .iter()
+            .any(|token| is_macro_name(token, &expansion_env))
+        {
+            // lines without macros are kept as they are, so that columns still match the source code
+            env.output.push(text.clone());
+            continue;
+        }
+
+        // the arguments of a function-like macro invocation may continue onto the following lines
+        let mut tokens = tokens;
+        while has_unterminated_invocation(&tokens, &env.macros)
+            && line_index < lines.len()
+            && !lines[line_index].0.trim_start().starts_with('#')
+        {
+            let mut next_tokens = get_pp_tokens(&lines[line_index].0);
+            if let Some(first) = next_tokens.first_mut() {
+                first.has_leading_space = true;
+            }
+            tokens.extend(next_tokens);
+            line_index += 1;
+        }
+
+        let expanded = expand_macros(tokens, &expansion_env);
+        env.output.push(pp_tokens_to_string(&expanded));
+    }
+
+    if let Some(conditional) = conditionals.first() {
+        err_display(
+            "unterminated conditional directive",
+            SourcePtr {
+                line: conditional.output_line,
+                col: 1,
+            },
+        );
+    }
+}
+
+/// splits source code into logical lines, along with the line number each one starts on.
+/// A backslash at the end of a line joins it with the next line, and comments are replaced by a space.
+fn get_logical_lines(contents: &str, file_index: usize) -> Vec<(String, usize)> {
+    let chars: Vec<char> = contents.chars().filter(|c| *c != '\r').collect();
+    let mut lines = Vec::new();
+    let mut curr_line = String::new();
+    let mut start_line = 1;
+    let mut line = 1;
+    let mut quote: Option<char> = None; // the quote character of the string or char literal we are in
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+
+        if c == '\\' && next == Some('\n') {
+            index += 2;
+            line += 1;
+            continue;
+        }
+        if c == '\n' {
+            lines.push((std::mem::take(&mut curr_line), start_line));
+            line += 1;
+            start_line = line;
+            quote = None;
+            index += 1;
+            continue;
+        }
+
+        if let Some(quote_char) = quote {
+            curr_line.push(c);
+            match next {
+                // the escaped character can't end the literal
+                Some(escaped) if c == '\\' => {
+                    curr_line.push(escaped);
+                    index += 1;
+                }
+                _ if c == quote_char => quote = None,
+                _ => {}
+            }
+            index += 1;
+        } else if c == '"' || c == '\'' {
+            quote = Some(c);
+            curr_line.push(c);
+            index += 1;
+        } else if c == '/' && next == Some('/') {
+            while index < chars.len() && chars[index] != '\n' {
+                if chars[index] == '\\' && chars.get(index + 1) == Some(&'\n') {
+                    line += 1;
+                    index += 1;
+                }
+                index += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            let comment_line = line;
+            index += 2;
+            while !(chars.get(index) == Some(&'*') && chars.get(index + 1) == Some(&'/')) {
+                match chars.get(index) {
+                    Some('\n') => line += 1,
+                    Some(_) => {}
+                    None => {
+                        add_output_line(file_index, comment_line);
+                        pp_err_display("unterminated comment");
+                    }
+                }
+                index += 1;
+            }
+            index += 2;
+            curr_line.push(' ');
+        } else {
+            curr_line.push(c);
+            index += 1;
+        }
+    }
+
+    if !curr_line.is_empty() {
+        lines.push((curr_line, start_line));
+    }
+    lines
+}
+
+/// returns whether the tokens end in the middle of the arguments of a function-like macro
+fn has_unterminated_invocation(tokens: &[PpToken], macros: &HashMap<String, Macro>) -> bool {
+    let mut depth = 0;
+    let mut is_in_invocation = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        if !is_in_invocation {
+            is_in_invocation = token.is_identifier()
+                && macros
+                    .get(&token.text)
+                    .is_some_and(|macro_| macro_.params.is_some())
+                && tokens.get(index + 1).is_some_and(|next| next.is_punct("("));
+        } else if token.is_punct("(") {
+            depth += 1;
+        } else if token.is_punct(")") {
+            depth -= 1;
+            is_in_invocation = depth > 0;
+        }
+    }
+
+    is_in_invocation
+}
+
+fn handle_directive(
+    tokens: &[PpToken],
+    file_name: &str,
+    line: usize,
+    conditionals: &mut Vec<Conditional>,
+    env: &mut PreprocessorEnv,
+) {
+    let is_active = conditionals.last().map_or(true, |c| c.is_active);
+    let (name, args) = match tokens.first() {
+        None => return, // the null directive, a line with only a `#`
+        Some(token) if token.is_identifier() => (token.text.as_str(), &tokens[1..]),
+        Some(token) if is_active => {
+            pp_err_display(format!("invalid preprocessing directive #{}", token.text))
+        }
+        Some(_) => return,
+    };
+    let expansion_env = ExpansionEnv {
+        macros: &env.macros,
+        file_name,
+        line,
+    };
+
+    match name {
+        "if" | "ifdef" | "ifndef" => {
+            let condition = is_active
+                && match name {
+                    "ifdef" => env.macros.contains_key(get_macro_name(args)),
+                    "ifndef" => !env.macros.contains_key(get_macro_name(args)),
+                    _ => eval_if_condition(args, &expansion_env),
+                };
+            conditionals.push(Conditional {
+                is_active: condition,
+                has_taken_branch: condition,
+                is_parent_active: is_active,
+                has_else: false,
+                output_line: get_num_output_lines(),
+            });
+        }
+        "elif" => {
+            let conditional = match conditionals.last_mut() {
+                Some(conditional) => conditional,
+                None => pp_err_display("#elif without #if"),
+            };
+            if conditional.has_else {
+                pp_err_display("#elif after #else");
+            }
+            // the condition is only evaluated if no earlier branch was taken
+            let condition = conditional.is_parent_active
+                && !conditional.has_taken_branch
+                && eval_if_condition(args, &expansion_env);
+            conditional.is_active = condition;
+            conditional.has_taken_branch |= condition;
+        }
+        "else" => {
+            let conditional = match conditionals.last_mut() {
+                Some(conditional) => conditional,
+                None => pp_err_display("#else without #if"),
+            };
+            if conditional.has_else {
+                pp_err_display("#else after #else");
+            }
+            conditional.has_else = true;
+            conditional.is_active = conditional.is_parent_active && !conditional.has_taken_branch;
+            conditional.has_taken_branch = true;
+        }
+        "endif" => {
+            if conditionals.pop().is_none() {
+                pp_err_display("#endif without #if");
+            }
+        }
+        // the other directives are ignored in branches which are not taken
+        _ if !is_active => {}
+        "define" => {
+            let (name, macro_) = parse_define(args);
+            if env
+                .macros
+                .get(&name)
+                .is_some_and(|existing| *existing != macro_)
+            {
+                pp_err_display(format!("macro {} redefined", name));
+            }
+            env.macros.insert(name, macro_);
+        }
+        "undef" => {
+            env.macros.remove(get_macro_name(args));
+        }
+        "include" => include_file(args, file_name, env),
+        "error" => pp_err_display(format!("#error {}", pp_tokens_to_string(args))),
+        "pragma" => {}
+        _ => pp_err_display(format!("invalid preprocessing directive #{}", name)),
+    }
+}
+
+fn get_macro_name(args: &[PpToken]) -> &str {
+    match args.first() {
+        Some(token) if token.is_identifier() => &token.text,
+        _ => pp_err_display("macro names must be identifiers"),
+    }
+}
+
+/// `#include "file"` searches the directory of the current file first, and then the `-I` directories.
+/// `#include <file>` only searches the `-I` directories.
+fn include_file(args: &[PpToken], file_name: &str, env: &mut PreprocessorEnv) {
+    let (include_name, is_quoted) = match args.first() {
+        Some(token) if token.kind == PpTokenKind::StringLiteral && token.text.len() >= 2 => {
+            (token.text[1..token.text.len() - 1].to_owned(), true)
+        }
+        Some(token) if token.is_punct("<") => {
+            let close_index = match args.iter().position(|token| token.is_punct(">")) {
+                Some(close_index) => close_index,
+                None => pp_err_display("missing terminating > character in #include"),
+            };
+            (pp_tokens_to_string(&args[1..close_index]), false)
+        }
+        _ => pp_err_display("#include expects \"FILENAME\" or <FILENAME>"),
+    };
+
+    let mut search_dirs = Vec::new();
+    if is_quoted {
+        search_dirs.push(
+            Path::new(file_name)
+                .parent()
+                .unwrap_or(Path::new(""))
+                .to_path_buf(),
+        );
+    }
+    search_dirs.extend(env.include_dirs.iter().cloned());
+
+    let path = match search_dirs
+        .iter()
+        .map(|dir| dir.join(&include_name))
+        .find(|path| path.is_file())
+    {
+        Some(path) => path,
+        None => pp_err_display(format!("could not find include file {}", include_name)),
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => pp_err_display(format!("error reading include file {}", include_name)),
+    };
+
+    if env.include_depth >= MAX_INCLUDE_DEPTH {
+        pp_err_display("#include nested too deeply");
+    }
+    env.include_depth += 1;
+    preprocess_file(&path.to_string_lossy(), &contents, env);
+    env.include_depth -= 1;
+}
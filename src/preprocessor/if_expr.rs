@@ -0,0 +1,323 @@
+use super::{
+    macros::{expand_macros, ExpansionEnv},
+    pp_err_display,
+    pp_token::{PpToken, PpTokenKind},
+};
+
+/// evaluates the condition of an `#if` or `#elif` directive
+pub fn eval_if_condition(tokens: &[PpToken], expansion_env: &ExpansionEnv) -> bool {
+    // `defined` operators are replaced before macros are expanded, so that
+    // the names they test are not expanded themselves
+    let mut replaced = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        if !(token.is_identifier() && token.text == "defined") {
+            replaced.push(token.clone());
+            index += 1;
+            continue;
+        }
+
+        let has_parens = tokens.get(index + 1).is_some_and(|t| t.is_punct("("));
+        let name_index = if has_parens { index + 2 } else { index + 1 };
+        let name = match tokens.get(name_index) {
+            Some(name) if name.is_identifier() => &name.text,
+            _ => pp_err_display("operator \"defined\" requires an identifier"),
+        };
+        if has_parens && !tokens.get(name_index + 1).is_some_and(|t| t.is_punct(")")) {
+            pp_err_display("missing `)` after \"defined\"");
+        }
+
+        let is_defined =
+            expansion_env.macros.contains_key(name) || name == "__FILE__" || name == "__LINE__";
+        replaced.push(PpToken::new(
+            PpTokenKind::Number,
+            (is_defined as i64).to_string(),
+            token.has_leading_space,
+        ));
+        index = if has_parens {
+            name_index + 2
+        } else {
+            name_index + 1
+        };
+    }
+
+    let expanded = expand_macros(replaced, expansion_env);
+    if expanded.is_empty() {
+        pp_err_display("#if with no expression");
+    }
+
+    let mut parser = IfExprParser {
+        tokens: &expanded,
+        index: 0,
+    };
+    let value = parser.parse_ternary(true);
+    if parser.index != expanded.len() {
+        pp_err_display(format!(
+            "unexpected token \"{}\" in #if expression",
+            expanded[parser.index].text
+        ));
+    }
+    value.is_true()
+}
+
+/// the value of an `#if` expression, which is computed as an `intmax_t`, or as a `uintmax_t` if it is unsigned
+#[derive(Clone, Copy)]
+struct IfValue {
+    value: i64,
+    is_unsigned: bool,
+}
+
+impl IfValue {
+    fn signed(value: i64) -> IfValue {
+        IfValue {
+            value,
+            is_unsigned: false,
+        }
+    }
+
+    fn from_bool(value: bool) -> IfValue {
+        IfValue::signed(value as i64)
+    }
+
+    fn is_true(self) -> bool {
+        self.value != 0
+    }
+}
+
+/// a recursive descent parser which evaluates the expression as it parses it.
+/// `is_evaluated` is false for operands which are skipped by `&&`, `||` and `?:`,
+/// so that dividing by zero in them is not an error.
+struct IfExprParser<'a> {
+    tokens: &'a [PpToken],
+    index: usize,
+}
+
+// binary operators from the lowest precedence level to the highest
+const BIN_OP_LEVELS: [&[&str]; 9] = [
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+    &[],
+];
+
+impl<'a> IfExprParser<'a> {
+    fn peek_punct(&self, punct: &str) -> bool {
+        self.tokens
+            .get(self.index)
+            .is_some_and(|token| token.is_punct(punct))
+    }
+
+    fn expect_punct(&mut self, punct: &str) {
+        if !self.peek_punct(punct) {
+            pp_err_display(format!("expected `{}` in #if expression", punct));
+        }
+        self.index += 1;
+    }
+
+    fn parse_ternary(&mut self, is_evaluated: bool) -> IfValue {
+        let condition = self.parse_logical_or(is_evaluated);
+        if !self.peek_punct("?") {
+            return condition;
+        }
+        self.index += 1;
+        let if_true = self.parse_ternary(is_evaluated && condition.is_true());
+        self.expect_punct(":");
+        let if_false = self.parse_ternary(is_evaluated && !condition.is_true());
+        // the result has the common type of both branches
+        IfValue {
+            value: if condition.is_true() {
+                if_true.value
+            } else {
+                if_false.value
+            },
+            is_unsigned: if_true.is_unsigned || if_false.is_unsigned,
+        }
+    }
+
+    fn parse_logical_or(&mut self, is_evaluated: bool) -> IfValue {
+        let mut value = self.parse_logical_and(is_evaluated);
+        while self.peek_punct("||") {
+            self.index += 1;
+            let rhs = self.parse_logical_and(is_evaluated && !value.is_true());
+            value = IfValue::from_bool(value.is_true() || rhs.is_true());
+        }
+        value
+    }
+
+    fn parse_logical_and(&mut self, is_evaluated: bool) -> IfValue {
+        let mut value = self.parse_bin_op(0, is_evaluated);
+        while self.peek_punct("&&") {
+            self.index += 1;
+            let rhs = self.parse_bin_op(0, is_evaluated && value.is_true());
+            value = IfValue::from_bool(value.is_true() && rhs.is_true());
+        }
+        value
+    }
+
+    fn parse_bin_op(&mut self, level: usize, is_evaluated: bool) -> IfValue {
+        if BIN_OP_LEVELS[level].is_empty() {
+            return self.parse_unary(is_evaluated);
+        }
+
+        let mut lhs = self.parse_bin_op(level + 1, is_evaluated);
+        while let Some(op) = BIN_OP_LEVELS[level].iter().find(|op| self.peek_punct(op)) {
+            self.index += 1;
+            let rhs = self.parse_bin_op(level + 1, is_evaluated);
+            lhs = eval_bin_op(op, lhs, rhs, is_evaluated);
+        }
+        lhs
+    }
+
+    fn parse_unary(&mut self, is_evaluated: bool) -> IfValue {
+        let token = match self.tokens.get(self.index) {
+            Some(token) => token,
+            None => pp_err_display("unexpected end of #if expression"),
+        };
+        self.index += 1;
+
+        match token.kind {
+            PpTokenKind::Punctuator => match token.text.as_str() {
+                "+" => self.parse_unary(is_evaluated),
+                "-" => {
+                    let operand = self.parse_unary(is_evaluated);
+                    IfValue {
+                        value: operand.value.wrapping_neg(),
+                        ..operand
+                    }
+                }
+                "~" => {
+                    let operand = self.parse_unary(is_evaluated);
+                    IfValue {
+                        value: !operand.value,
+                        ..operand
+                    }
+                }
+                "!" => IfValue::from_bool(!self.parse_unary(is_evaluated).is_true()),
+                "(" => {
+                    let value = self.parse_ternary(is_evaluated);
+                    self.expect_punct(")");
+                    value
+                }
+                _ => pp_err_display(format!(
+                    "unexpected token \"{}\" in #if expression",
+                    token.text
+                )),
+            },
+            PpTokenKind::Number => parse_number(&token.text),
+            PpTokenKind::CharLiteral => IfValue::signed(parse_char(&token.text)),
+            // identifiers which are left after expanding macros evaluate to 0
+            PpTokenKind::Identifier => IfValue::signed(0),
+            PpTokenKind::StringLiteral | PpTokenKind::Placemarker => {
+                pp_err_display(format!("unexpected token {} in #if expression", token.text))
+            }
+        }
+    }
+}
+
+/// evaluates a binary operator. The operands are converted to a common type, which is unsigned if either
+/// of them is, except for shifts, whose result has the type of the left operand.
+fn eval_bin_op(op: &str, lhs: IfValue, rhs: IfValue, is_evaluated: bool) -> IfValue {
+    let is_unsigned = match op {
+        "<<" | ">>" => lhs.is_unsigned,
+        _ => lhs.is_unsigned || rhs.is_unsigned,
+    };
+    let (a, b) = (lhs.value, rhs.value);
+    let value = match op {
+        "==" => return IfValue::from_bool(a == b),
+        "!=" => return IfValue::from_bool(a != b),
+        "<" | ">" | "<=" | ">=" => {
+            let ordering = if is_unsigned {
+                (a as u64).cmp(&(b as u64))
+            } else {
+                a.cmp(&b)
+            };
+            return IfValue::from_bool(match op {
+                "<" => ordering.is_lt(),
+                ">" => ordering.is_gt(),
+                "<=" => ordering.is_le(),
+                _ => ordering.is_ge(),
+            });
+        }
+        "|" => a | b,
+        "^" => a ^ b,
+        "&" => a & b,
+        "<<" => a.wrapping_shl(b as u32),
+        ">>" if is_unsigned => (a as u64).wrapping_shr(b as u32) as i64,
+        ">>" => a.wrapping_shr(b as u32),
+        "+" => a.wrapping_add(b),
+        "-" => a.wrapping_sub(b),
+        "*" => a.wrapping_mul(b),
+        "/" | "%" if b == 0 => {
+            if is_evaluated {
+                pp_err_display("division by zero in #if expression");
+            }
+            0
+        }
+        "/" if is_unsigned => ((a as u64) / (b as u64)) as i64,
+        "%" if is_unsigned => ((a as u64) % (b as u64)) as i64,
+        "/" => a.wrapping_div(b),
+        "%" => a.wrapping_rem(b),
+        _ => unreachable!(),
+    };
+    IfValue { value, is_unsigned }
+}
+
+/// parses an integer constant, which is unsigned if it has a `u` suffix or is too big for an `intmax_t`
+fn parse_number(text: &str) -> IfValue {
+    let digits = text.trim_end_matches(['u', 'U', 'l', 'L']);
+    let has_unsigned_suffix = text[digits.len()..].contains(['u', 'U']);
+    let (digits, radix) = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        (hex, 16)
+    } else if let Some(binary) = digits
+        .strip_prefix("0b")
+        .or_else(|| digits.strip_prefix("0B"))
+    {
+        (binary, 2)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (&digits[1..], 8)
+    } else {
+        (digits, 10)
+    };
+
+    match u64::from_str_radix(digits, radix) {
+        Ok(value) => IfValue {
+            value: value as i64,
+            is_unsigned: has_unsigned_suffix || value > i64::MAX as u64,
+        },
+        Err(_) => pp_err_display(format!("invalid integer \"{}\" in #if expression", text)),
+    }
+}
+
+fn parse_char(text: &str) -> i64 {
+    if text.len() < 2 || !text.ends_with('\'') {
+        pp_err_display(format!("invalid character constant {} in #if", text));
+    }
+    let inner = &text[1..text.len() - 1];
+    let mut chars = inner.chars();
+    let value = match (chars.next(), chars.next()) {
+        (Some('\\'), Some(escaped)) => match escaped {
+            'n' => '\n',
+            't' => '\t',
+            '0' => '\0',
+            '\\' => '\\',
+            '\'' => '\'',
+            '"' => '"',
+            _ => pp_err_display(format!("invalid character constant {} in #if", text)),
+        },
+        (Some(c), None) => c,
+        _ => pp_err_display(format!("invalid character constant {} in #if", text)),
+    };
+    if chars.next().is_some() {
+        pp_err_display(format!("invalid character constant {} in #if", text));
+    }
+    value as i64
+}
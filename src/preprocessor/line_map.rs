@@ -0,0 +1,43 @@
+use std::ptr::addr_of_mut;
+
+/// the name of every file read by the preprocessor
+static mut SOURCE_FILES: Vec<String> = Vec::new();
+/// for each line of the preprocessed output, the index of the file it came from,
+/// and its line number within that file
+static mut LINE_MAP: Vec<(usize, usize)> = Vec::new();
+
+pub fn add_source_file(file_name: &str) -> usize {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let source_files = &mut *addr_of_mut!(SOURCE_FILES);
+        source_files.push(file_name.to_owned());
+        source_files.len() - 1
+    }
+}
+
+/// records where the next line of the preprocessed output originally came from
+pub fn add_output_line(file_index: usize, line: usize) {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(LINE_MAP)).push((file_index, line));
+    }
+}
+
+pub fn get_num_output_lines() -> usize {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(LINE_MAP)).len()
+    }
+}
+
+/// maps a line number of the preprocessed output (starting from 1) back to
+/// the name of the file and the line number it was originally written on.
+pub fn get_original_location(output_line: usize) -> Option<(String, usize)> {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let line_map = &*addr_of_mut!(LINE_MAP);
+        let (file_index, line) = line_map.get(output_line.max(1) - 1).or(line_map.last())?;
+        let source_files = &*addr_of_mut!(SOURCE_FILES);
+        Some((source_files[*file_index].clone(), *line))
+    }
+}
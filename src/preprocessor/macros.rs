@@ -0,0 +1,400 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{
+    pp_err_display,
+    pp_token::{get_pp_tokens, PpToken, PpTokenKind},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct Macro {
+    pub params: Option<Vec<String>>, // None for object-like macros
+    pub is_variadic: bool,
+    pub body: Vec<PpToken>,
+}
+
+/// parses the tokens of a `#define` directive which come after `define`
+pub fn parse_define(tokens: &[PpToken]) -> (String, Macro) {
+    let name = match tokens.first() {
+        Some(token) if token.is_identifier() => token.text.clone(),
+        _ => pp_err_display("macro names must be identifiers"),
+    };
+    if name == "defined" {
+        pp_err_display("\"defined\" cannot be used as a macro name");
+    }
+
+    let mut index = 1;
+    let mut params = None;
+    let mut is_variadic = false;
+
+    // a macro is only function-like if the `(` comes immediately after its name
+    if tokens
+        .get(1)
+        .is_some_and(|token| token.is_punct("(") && !token.has_leading_space)
+    {
+        let mut param_names: Vec<String> = Vec::new();
+        index = 2;
+        if tokens.get(index).is_some_and(|token| token.is_punct(")")) {
+            index += 1;
+        } else {
+            loop {
+                match tokens.get(index) {
+                    Some(token) if token.is_punct("...") => {
+                        is_variadic = true;
+                        index += 1;
+                    }
+                    Some(token) if token.is_identifier() => {
+                        if param_names.contains(&token.text) {
+                            pp_err_display(format!(
+                                "duplicate macro parameter {} in macro {}",
+                                token.text, name
+                            ));
+                        }
+                        param_names.push(token.text.clone());
+                        index += 1;
+                    }
+                    _ => pp_err_display(format!("expected parameter name in macro {}", name)),
+                }
+                match tokens.get(index) {
+                    Some(token) if token.is_punct(")") => {
+                        index += 1;
+                        break;
+                    }
+                    Some(token) if token.is_punct(",") && !is_variadic => index += 1,
+                    _ => pp_err_display(format!(
+                        "expected `,` or `)` in parameter list of macro {}",
+                        name
+                    )),
+                }
+            }
+        }
+        params = Some(param_names);
+    }
+
+    let mut body = tokens[index..].to_vec();
+    if let Some(first) = body.first_mut() {
+        first.has_leading_space = false;
+    }
+
+    if body.first().is_some_and(|token| token.is_punct("##"))
+        || body.last().is_some_and(|token| token.is_punct("##"))
+    {
+        pp_err_display("'##' cannot appear at either end of a macro expansion");
+    }
+    if let Some(param_names) = &params {
+        for (index, token) in body.iter().enumerate() {
+            let is_param = |token: &PpToken| {
+                param_names.contains(&token.text) || (is_variadic && token.text == "__VA_ARGS__")
+            };
+            if token.is_punct("#") && !body.get(index + 1).is_some_and(is_param) {
+                pp_err_display("'#' is not followed by a macro parameter");
+            }
+        }
+    }
+
+    (
+        name,
+        Macro {
+            params,
+            is_variadic,
+            body,
+        },
+    )
+}
+
+/// the macros which are defined, and the location of the line being expanded,
+/// which `__FILE__` and `__LINE__` refer to
+pub struct ExpansionEnv<'a> {
+    pub macros: &'a HashMap<String, Macro>,
+    pub file_name: &'a str,
+    pub line: usize,
+}
+
+/// returns whether a token would be replaced when expanding macros
+pub fn is_macro_name(token: &PpToken, expansion_env: &ExpansionEnv) -> bool {
+    token.is_identifier()
+        && (expansion_env.macros.contains_key(&token.text)
+            || token.text == "__FILE__"
+            || token.text == "__LINE__")
+}
+
+/// expands all macros in a sequence of tokens. Each token remembers the macros it was
+/// produced by (its hide set), so that a macro never expands inside of its own expansion.
+pub fn expand_macros(tokens: Vec<PpToken>, expansion_env: &ExpansionEnv) -> Vec<PpToken> {
+    let mut input: VecDeque<PpToken> = tokens.into();
+    let mut output = Vec::new();
+
+    while let Some(token) = input.pop_front() {
+        if !token.is_identifier() || token.hide_set.contains(&token.text) {
+            output.push(token);
+            continue;
+        }
+
+        match token.text.as_str() {
+            "__FILE__" => {
+                let text = format!("\"{}\"", escape_string(expansion_env.file_name));
+                output.push(PpToken::new(
+                    PpTokenKind::StringLiteral,
+                    text,
+                    token.has_leading_space,
+                ));
+                continue;
+            }
+            "__LINE__" => {
+                let text = expansion_env.line.to_string();
+                output.push(PpToken::new(
+                    PpTokenKind::Number,
+                    text,
+                    token.has_leading_space,
+                ));
+                continue;
+            }
+            _ => {}
+        }
+
+        let macro_ = match expansion_env.macros.get(&token.text) {
+            Some(macro_) => macro_,
+            None => {
+                output.push(token);
+                continue;
+            }
+        };
+
+        let mut replacement = match &macro_.params {
+            None => {
+                let mut hide_set = token.hide_set.clone();
+                hide_set.push(token.text.clone());
+                substitute(macro_, &[], &hide_set, expansion_env)
+            }
+            Some(params) => {
+                // the name of a function-like macro is left alone if it is not being invoked
+                if !input.front().is_some_and(|next| next.is_punct("(")) {
+                    output.push(token);
+                    continue;
+                }
+                let (args, close_paren) = collect_args(&mut input, &token.text);
+                let args = match_args_to_params(args, params, macro_.is_variadic, &token.text);
+
+                let mut hide_set: Vec<String> = token
+                    .hide_set
+                    .iter()
+                    .filter(|name| close_paren.hide_set.contains(name))
+                    .cloned()
+                    .collect();
+                hide_set.push(token.text.clone());
+                substitute(macro_, &args, &hide_set, expansion_env)
+            }
+        };
+
+        // the expansion is rescanned along with the rest of the tokens
+        if let Some(first) = replacement.first_mut() {
+            first.has_leading_space = token.has_leading_space;
+        }
+        for replacement_token in replacement.into_iter().rev() {
+            input.push_front(replacement_token);
+        }
+    }
+
+    output
+}
+
+/// collects the comma separated arguments of a function-like macro invocation,
+/// returning them along with the closing parenthesis.
+fn collect_args(input: &mut VecDeque<PpToken>, macro_name: &str) -> (Vec<Vec<PpToken>>, PpToken) {
+    input.pop_front(); // consume the `(`
+    let mut args = vec![Vec::new()];
+    let mut depth = 0;
+
+    loop {
+        let token = match input.pop_front() {
+            Some(token) => token,
+            None => pp_err_display(format!(
+                "unterminated argument list invoking macro {}",
+                macro_name
+            )),
+        };
+        if token.is_punct(")") && depth == 0 {
+            return (args, token);
+        }
+        if token.is_punct(",") && depth == 0 {
+            args.push(Vec::new());
+            continue;
+        }
+        if token.is_punct("(") {
+            depth += 1;
+        } else if token.is_punct(")") {
+            depth -= 1;
+        }
+        args.last_mut().unwrap().push(token);
+    }
+}
+
+/// checks the number of arguments of a macro invocation. For a variadic macro, the extra arguments
+/// are joined back together (with their commas) into a single argument for `__VA_ARGS__`.
+fn match_args_to_params(
+    mut args: Vec<Vec<PpToken>>,
+    params: &[String],
+    is_variadic: bool,
+    macro_name: &str,
+) -> Vec<Vec<PpToken>> {
+    // `f()` passes no arguments to a macro without parameters, rather than one empty argument
+    if params.is_empty() && args.len() == 1 && args[0].is_empty() {
+        args.clear();
+    }
+
+    if is_variadic && args.len() >= params.len() {
+        let mut variadic_arg = Vec::new();
+        for (index, arg) in args.drain(params.len()..).enumerate() {
+            if index > 0 {
+                variadic_arg.push(PpToken::new(PpTokenKind::Punctuator, ",".to_owned(), false));
+            }
+            variadic_arg.extend(arg);
+        }
+        args.push(variadic_arg);
+    } else if args.len() != params.len() {
+        pp_err_display(format!(
+            "macro {} expects {} arguments, but {} were given",
+            macro_name,
+            params.len(),
+            args.len()
+        ));
+    }
+
+    args
+}
+
+/// replaces the parameters in the body of a macro with its arguments,
+/// and applies the `#` and `##` operators.
+fn substitute(
+    macro_: &Macro,
+    args: &[Vec<PpToken>],
+    hide_set: &[String],
+    expansion_env: &ExpansionEnv,
+) -> Vec<PpToken> {
+    let body = &macro_.body;
+    let get_arg = |token: &PpToken| -> Option<&Vec<PpToken>> {
+        let params = macro_.params.as_ref()?;
+        if !token.is_identifier() {
+            return None;
+        }
+        if macro_.is_variadic && token.text == "__VA_ARGS__" {
+            return args.last();
+        }
+        let index = params.iter().position(|param| *param == token.text)?;
+        Some(&args[index])
+    };
+
+    let mut result: Vec<PpToken> = Vec::new();
+    let mut index = 0;
+
+    while index < body.len() {
+        let token = &body[index];
+
+        if macro_.params.is_some() && token.is_punct("#") {
+            let arg = get_arg(&body[index + 1]).unwrap(); // checked when the macro was defined
+            result.push(stringize(arg, token.has_leading_space));
+            index += 2;
+            continue;
+        }
+
+        if token.is_punct("##") {
+            // the operand on the right of `##` is pasted without being expanded first
+            let rhs_token = &body[index + 1];
+            let rhs = match get_arg(rhs_token) {
+                Some(arg) if arg.is_empty() => vec![placemarker()],
+                Some(arg) => arg.clone(),
+                None => vec![rhs_token.clone()],
+            };
+            let lhs = result.pop().unwrap();
+            result.push(paste(&lhs, &rhs[0]));
+            result.extend(rhs[1..].iter().cloned());
+            index += 2;
+            continue;
+        }
+
+        if let Some(arg) = get_arg(token) {
+            let mut arg_tokens = if body.get(index + 1).is_some_and(|next| next.is_punct("##")) {
+                // the operand on the left of `##` is not expanded either
+                if arg.is_empty() {
+                    vec![placemarker()]
+                } else {
+                    arg.clone()
+                }
+            } else {
+                expand_macros(arg.clone(), expansion_env)
+            };
+            if let Some(first) = arg_tokens.first_mut() {
+                first.has_leading_space = token.has_leading_space;
+            }
+            result.extend(arg_tokens);
+            index += 1;
+            continue;
+        }
+
+        result.push(token.clone());
+        index += 1;
+    }
+
+    result
+        .into_iter()
+        .filter(|token| token.kind != PpTokenKind::Placemarker)
+        .map(|mut token| {
+            for name in hide_set {
+                if !token.hide_set.contains(name) {
+                    token.hide_set.push(name.clone());
+                }
+            }
+            token
+        })
+        .collect()
+}
+
+fn placemarker() -> PpToken {
+    PpToken::new(PpTokenKind::Placemarker, String::new(), false)
+}
+
+/// the `#` operator turns the tokens of a macro argument into a string literal
+fn stringize(arg: &[PpToken], has_leading_space: bool) -> PpToken {
+    let mut text = String::from("\"");
+    for (index, token) in arg.iter().enumerate() {
+        if index > 0 && token.has_leading_space {
+            text.push(' ');
+        }
+        match token.kind {
+            PpTokenKind::StringLiteral | PpTokenKind::CharLiteral => {
+                text.push_str(&escape_string(&token.text))
+            }
+            _ => text.push_str(&token.text),
+        }
+    }
+    text.push('"');
+    PpToken::new(PpTokenKind::StringLiteral, text, has_leading_space)
+}
+
+/// the `##` operator joins two tokens into a single token
+fn paste(lhs: &PpToken, rhs: &PpToken) -> PpToken {
+    if lhs.kind == PpTokenKind::Placemarker {
+        return PpToken {
+            has_leading_space: lhs.has_leading_space,
+            ..rhs.clone()
+        };
+    }
+    if rhs.kind == PpTokenKind::Placemarker {
+        return lhs.clone();
+    }
+
+    let text = format!("{}{}", lhs.text, rhs.text);
+    let mut pasted = get_pp_tokens(&text);
+    if pasted.len() != 1 {
+        pp_err_display(format!(
+            "pasting \"{}\" and \"{}\" does not give a valid preprocessing token",
+            lhs.text, rhs.text
+        ));
+    }
+    let mut token = pasted.remove(0);
+    token.has_leading_space = lhs.has_leading_space;
+    token
+}
+
+fn escape_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
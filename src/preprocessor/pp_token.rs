@@ -0,0 +1,147 @@
+/// preprocessing tokens keep their original spelling, since the preprocessor
+/// turns them back into source code after expanding macros.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PpTokenKind {
+    Identifier,
+    Number,
+    StringLiteral,
+    CharLiteral,
+    Punctuator,
+    Placemarker, // stands in for an empty macro argument next to a `##` operator
+}
+
+#[derive(Debug, Clone)]
+pub struct PpToken {
+    pub kind: PpTokenKind,
+    pub text: String,
+    pub has_leading_space: bool,
+    // names of the macros which this token came from, which may not be expanded again
+    pub hide_set: Vec<String>,
+}
+
+impl PartialEq for PpToken {
+    fn eq(&self, other: &Self) -> bool {
+        // two macro definitions are the same if their tokens are spelled and spaced the same
+        self.kind == other.kind
+            && self.text == other.text
+            && self.has_leading_space == other.has_leading_space
+    }
+}
+
+impl PpToken {
+    pub fn new(kind: PpTokenKind, text: String, has_leading_space: bool) -> Self {
+        PpToken {
+            kind,
+            text,
+            has_leading_space,
+            hide_set: Vec::new(),
+        }
+    }
+
+    pub fn is_punct(&self, punct: &str) -> bool {
+        self.kind == PpTokenKind::Punctuator && self.text == punct
+    }
+
+    pub fn is_identifier(&self) -> bool {
+        self.kind == PpTokenKind::Identifier
+    }
+}
+
+// punctuators made up of more than one character, longest first
+const MULTI_CHAR_PUNCTUATORS: [&str; 23] = [
+    "...", "<<=", ">>=", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "+=",
+    "-=", "*=", "/=", "%=", "&=", "|=", "^=", "##",
+];
+
+/// splits a logical line (with comments already removed) into preprocessing tokens
+pub fn get_pp_tokens(line: &str) -> Vec<PpToken> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    let mut has_leading_space = false;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let start = index;
+
+        let kind = if c.is_ascii_whitespace() {
+            has_leading_space = true;
+            index += 1;
+            continue;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            while index < chars.len()
+                && (chars[index].is_ascii_alphanumeric() || chars[index] == '_')
+            {
+                index += 1;
+            }
+            PpTokenKind::Identifier
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(index + 1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            // a preprocessing number also includes the suffixes and exponents of a literal
+            index += 1;
+            while index < chars.len() {
+                if "eEpP".contains(chars[index])
+                    && chars.get(index + 1).is_some_and(|c| *c == '+' || *c == '-')
+                {
+                    index += 2;
+                } else if chars[index].is_ascii_alphanumeric()
+                    || chars[index] == '_'
+                    || chars[index] == '.'
+                {
+                    index += 1;
+                } else {
+                    break;
+                }
+            }
+            PpTokenKind::Number
+        } else if c == '"' || c == '\'' {
+            index += 1;
+            while index < chars.len() && chars[index] != c {
+                if chars[index] == '\\' {
+                    index += 1;
+                }
+                index += 1;
+            }
+            // an unterminated literal is left for the tokenizer to report
+            index = (index + 1).min(chars.len());
+            if c == '"' {
+                PpTokenKind::StringLiteral
+            } else {
+                PpTokenKind::CharLiteral
+            }
+        } else {
+            let rest: String = chars[index..].iter().take(3).collect();
+            let len = MULTI_CHAR_PUNCTUATORS
+                .iter()
+                .find(|punct| rest.starts_with(*punct))
+                .map_or(1, |punct| punct.len());
+            index += len;
+            PpTokenKind::Punctuator
+        };
+
+        let text = chars[start..index].iter().collect();
+        tokens.push(PpToken::new(kind, text, has_leading_space));
+        has_leading_space = false;
+    }
+
+    tokens
+}
+
+/// turns preprocessing tokens back into source code, keeping tokens which were
+/// written next to each other in the source code together.
+pub fn pp_tokens_to_string(tokens: &[PpToken]) -> String {
+    let mut result = String::new();
+    for (index, token) in tokens.iter().enumerate() {
+        if index > 0 {
+            let prev = &tokens[index - 1];
+            // tokens produced by a macro are always separated, so that they can't join
+            // with their neighbours into a different token
+            if token.has_leading_space || !token.hide_set.is_empty() || !prev.hide_set.is_empty() {
+                result.push(' ');
+            }
+        }
+        result.push_str(&token.text);
+    }
+    result
+}
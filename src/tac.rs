@@ -1,35 +1,60 @@
 use std::collections::HashMap;
 use std::fmt;
 pub mod array_init_expr;
+pub mod cfg;
+pub mod dce;
 pub mod expr;
+pub mod global_vars;
 pub mod loops;
 pub mod prefix_postfix_inc_dec;
+pub mod sccp;
+pub mod ssa;
+pub mod structs;
+pub mod switch;
 pub mod tac_func;
 pub mod tac_instr;
 
-use crate::errors::check_funcs::check_funcs;
-use crate::errors::check_types::check_types;
-use crate::errors::check_vars::check_vars;
 use crate::parser::Function;
-use crate::parser::{expr_parser::Expr, Program, Statement};
+use crate::parser::{
+    expr_parser::{Expr, ExprEnum},
+    Program, Statement,
+};
 use crate::types::{VarSize, VarType};
 
 use self::array_init_expr::{gen_arr_init_expr_tac, gen_opt_arr_init_expr_tac};
-use self::expr::ValTarget;
-use self::tac_func::TacFunc;
+use self::expr::{gen_conversion_tac, get_type_size, ValTarget};
+use self::global_vars::add_global_var;
+use self::structs::gen_struct_copy_tac;
+use self::switch::{gen_case_label_tac, gen_default_label_tac, gen_switch_tac, SwitchLabels};
+use self::tac_func::{TacBody, TacFunc};
 use self::{
     expr::generate_expr_tac,
-    loops::{gen_for_loop_tac, gen_while_loop_tac, generate_break_tac, generate_continue_tac},
+    loops::{
+        gen_do_while_loop_tac, gen_for_loop_tac, gen_while_loop_tac, generate_break_tac,
+        generate_continue_tac,
+    },
     tac_instr::TacInstr,
 };
 
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
-pub struct Identifier(usize, VarSize); // an identifier for a temporary in TAC, represents a offset from RBP
+pub struct Identifier(usize, VarSize, TempKind); // an identifier for a temporary in TAC, represents a offset from RBP.
+
+/// how the bits of a temporary are interpreted
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum TempKind {
+    Signed,
+    Unsigned,
+    Float, // a float (for dwords) or a double (for quads)
+}
 
 impl fmt::Debug for Identifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let suffix = self.1.to_letter();
-        write!(f, "{}{}", suffix, self.0)
+        match self.2 {
+            TempKind::Signed => write!(f, "{}{}", suffix, self.0),
+            TempKind::Unsigned => write!(f, "u{}{}", suffix, self.0),
+            TempKind::Float => write!(f, "f{}{}", suffix, self.0),
+        }
     }
 }
 
@@ -46,27 +71,58 @@ impl Identifier {
     pub fn get_size(&self) -> VarSize {
         self.1
     }
+
+    pub fn is_unsigned(&self) -> bool {
+        self.2 == TempKind::Unsigned
+    }
+
+    pub fn is_float(&self) -> bool {
+        self.2 == TempKind::Float
+    }
+
+    pub fn get_kind(&self) -> TempKind {
+        self.2
+    }
 }
 
 #[derive(Clone)]
 pub enum TacVal {
     Lit(i64, VarSize),
+    FloatLit(f64, VarSize), // a float literal for dwords, a double literal for quads
     Var(Identifier),
 }
 
 impl TacVal {
     pub fn get_size(&self) -> VarSize {
         match self {
-            TacVal::Lit(_, size) => *size,
+            TacVal::Lit(_, size) | TacVal::FloatLit(_, size) => *size,
             TacVal::Var(ident) => ident.1,
         }
     }
+
+    /// literals are converted to the type of the operation they are used in,
+    /// so only variables carry their signedness.
+    pub fn is_unsigned(&self) -> bool {
+        match self {
+            TacVal::Lit(_, _) | TacVal::FloatLit(_, _) => false,
+            TacVal::Var(ident) => ident.is_unsigned(),
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        match self {
+            TacVal::Lit(_, _) => false,
+            TacVal::FloatLit(_, _) => true,
+            TacVal::Var(ident) => ident.is_float(),
+        }
+    }
 }
 
 impl fmt::Debug for TacVal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TacVal::Lit(val, var_size) => write!(f, "{}{}", val, var_size.to_letter()),
+            TacVal::FloatLit(val, var_size) => write!(f, "{:?}f{}", val, var_size.to_letter()),
             TacVal::Var(ident) => write!(f, "{:?}", ident),
         }
     }
@@ -80,15 +136,33 @@ pub struct CodeEnv {
     pub loop_label_end: Option<String>,
     pub loop_label_begin: Option<String>,
     pub is_main: bool,
+    pub func_name: String,
+
+    // the labels of the innermost switch statement, used for case and default labels
+    pub switch_labels: Option<SwitchLabels>,
+
+    // a map of function name to the types of the function's parameters, which arguments are converted to
+    pub func_param_types_map: HashMap<String, Vec<VarType>>,
+
+    // the return type of the function, which returned values are converted to
+    pub return_type: VarType,
 }
 
 impl CodeEnv {
-    fn new(is_main: bool) -> Self {
+    fn new(
+        func_name: &str,
+        return_type: &VarType,
+        func_param_types_map: HashMap<String, Vec<VarType>>,
+    ) -> Self {
         CodeEnv {
             var_map_list: Vec::new(),
             loop_label_end: None,
             loop_label_begin: None,
-            is_main,
+            is_main: func_name == "main",
+            func_name: func_name.to_owned(),
+            switch_labels: None,
+            func_param_types_map,
+            return_type: return_type.clone(),
         }
     }
 }
@@ -100,10 +174,29 @@ fn get_new_temp_name(size: VarSize) -> Identifier {
     unsafe {
         // Safety: no race conditions because this compiler is single-threaded
         TEMP_STORAGE_NUMBER += 1;
-        Identifier(TEMP_STORAGE_NUMBER - 1, size)
+        Identifier(TEMP_STORAGE_NUMBER - 1, size, TempKind::Signed)
     }
 }
 
+/// creates a new temporary of the same size and kind as the given one
+fn get_new_temp_like(ident: Identifier) -> Identifier {
+    let mut new_ident = get_new_temp_name(ident.1);
+    new_ident.2 = ident.2;
+    new_ident
+}
+
+/// creates a temporary which can hold a value of the given type.
+/// Unlike `get_new_temp_name`, the temporary will be unsigned or floating point if the type is.
+fn get_new_temp_name_for_type(t: &VarType) -> Identifier {
+    let mut ident = get_new_temp_name(get_type_size(t).unwrap());
+    if t.is_unsigned() {
+        ident.2 = TempKind::Unsigned;
+    } else if t.is_float() {
+        ident.2 = TempKind::Float;
+    }
+    ident
+}
+
 fn get_new_label_number() -> usize {
     unsafe {
         // Safety: no race conditions because this compiler is single-threaded
@@ -114,28 +207,39 @@ fn get_new_label_number() -> usize {
 
 /// This function takes as input a program AST,
 /// and as output will generate the TAC IR (three-address-code intermediate representation)
-pub fn generate_tac(mut program: Program) -> Vec<TacFunc> {
-    check_funcs(&program);
-    check_vars(&program);
-    check_types(&mut program); // check types will also evaluate sizeof, thus we need mut
+pub fn generate_tac(program: Program) -> Vec<TacFunc> {
+    for global_var in &program.global_vars {
+        add_global_var(global_var);
+    }
+
+    let mut func_param_types_map = HashMap::new();
+    for function in &program.functions {
+        func_param_types_map.insert(function.name.clone(), function.param_types());
+    }
 
     let mut tac_funcs = Vec::new();
 
     for function in program.functions {
-        tac_funcs.push(generate_function_tac(&function));
+        tac_funcs.push(generate_function_tac(
+            &function,
+            func_param_types_map.clone(),
+        ));
     }
 
     tac_funcs
 }
 
-fn generate_function_tac(function: &Function) -> TacFunc {
-    let mut code_env = CodeEnv::new(function.name == "main");
+fn generate_function_tac(
+    function: &Function,
+    func_param_types_map: HashMap<String, Vec<VarType>>,
+) -> TacFunc {
+    let mut code_env = CodeEnv::new(&function.name, &function.return_type, func_param_types_map);
     let mut this_scopes_variable_map: HashMap<String, Identifier> = HashMap::new();
     let mut body = Vec::new();
     let mut args = Vec::new();
 
     for (arg_name, arg_type) in function.args.iter() {
-        let var_temp_loc = get_new_temp_name(arg_type.to_size().unwrap());
+        let var_temp_loc = get_new_temp_name_for_type(arg_type);
         this_scopes_variable_map.insert(arg_name.clone(), var_temp_loc);
         args.push((var_temp_loc, arg_type.clone()));
     }
@@ -163,14 +267,18 @@ fn generate_function_tac(function: &Function) -> TacFunc {
                 None,
             ))
         } else {
-            body.push(TacInstr::Return(TacVal::Lit(0, VarSize::default())));
+            let (_, zero) = match &function.return_type {
+                t @ VarType::Fund(_) => gen_conversion_tac(TacVal::Lit(0, VarSize::Quad), t),
+                _ => (vec![], TacVal::Lit(0, VarSize::default())),
+            };
+            body.push(TacInstr::Return(zero));
         }
     }
 
     TacFunc {
         name: function.name.clone(),
         args,
-        body,
+        body: TacBody::Instrs(body),
     }
 }
 
@@ -191,8 +299,21 @@ fn generate_compound_stmt_tac(stmts: &Vec<Statement>, code_env: &mut CodeEnv) ->
 
 fn generate_statement_tac(statement: &Statement, code_env: &mut CodeEnv) -> Vec<TacInstr> {
     match statement {
-        Statement::Return(expr) => {
-            let (mut result, expr_val) = generate_expr_tac(expr, code_env, ValTarget::Generate);
+        Statement::Return(opt_expr, _) => {
+            let (mut result, expr_val) = match opt_expr {
+                Some(expr) => generate_expr_tac(expr, code_env, ValTarget::Generate),
+                // returning from a void function, the value in rax is ignored by the caller
+                None => (vec![], TacVal::Lit(0, VarSize::default())),
+            };
+            // the returned value is converted to the return type of the function
+            let expr_val = match &code_env.return_type {
+                t @ VarType::Fund(_) if opt_expr.is_some() => {
+                    let (instrs, converted_val) = gen_conversion_tac(expr_val, t);
+                    result.extend(instrs);
+                    converted_val
+                }
+                _ => expr_val,
+            };
             if code_env.is_main {
                 result.push(TacInstr::Call("exit".to_owned(), vec![expr_val], None));
             } else {
@@ -200,7 +321,7 @@ fn generate_statement_tac(statement: &Statement, code_env: &mut CodeEnv) -> Vec<
             }
             result
         }
-        Statement::Declare(var_name, opt_value, t) => {
+        Statement::Declare(var_name, opt_value, t, _) => {
             generate_declaration_tac(var_name, opt_value, t, code_env)
         }
 
@@ -208,7 +329,8 @@ fn generate_statement_tac(statement: &Statement, code_env: &mut CodeEnv) -> Vec<
             let (result, _) = generate_expr_tac(expr, code_env, ValTarget::None);
             result
         }
-        Statement::Empty => {
+        // uses of enumerators were already replaced by their values
+        Statement::Empty | Statement::EnumConsts(_) => {
             vec![]
         }
         Statement::CompoundStmt(stmts) => generate_compound_stmt_tac(stmts, code_env),
@@ -216,10 +338,20 @@ fn generate_statement_tac(statement: &Statement, code_env: &mut CodeEnv) -> Vec<
             generate_if_statement_tac(condition, taken, not_taken.as_deref(), code_env)
         }
         Statement::While(condition, body) => gen_while_loop_tac(condition, body, code_env),
-        Statement::Break => generate_break_tac(code_env),
-        Statement::Continue => generate_continue_tac(code_env),
-        Statement::For(initial_expr, control_expr, post_expr, body) => gen_for_loop_tac(
-            initial_expr,
+        Statement::DoWhile(body, condition) => gen_do_while_loop_tac(body, condition, code_env),
+        Statement::Switch(ctrl_expr, body) => gen_switch_tac(ctrl_expr, body, code_env),
+        Statement::Case(expr) => gen_case_label_tac(expr, code_env),
+        Statement::Default(pos) => gen_default_label_tac(*pos, code_env),
+        Statement::Labeled(label, inner_stmt, _) => {
+            let mut result = vec![TacInstr::Label(get_user_label_name(label, code_env))];
+            result.extend(generate_statement_tac(inner_stmt, code_env));
+            result
+        }
+        Statement::Goto(label, _) => vec![TacInstr::Jmp(get_user_label_name(label, code_env))],
+        Statement::Break(pos) => generate_break_tac(*pos, code_env),
+        Statement::Continue(pos) => generate_continue_tac(*pos, code_env),
+        Statement::For(initial_stmts, control_expr, post_expr, body) => gen_for_loop_tac(
+            initial_stmts,
             control_expr.as_ref(),
             post_expr.as_ref(),
             body,
@@ -228,6 +360,12 @@ fn generate_statement_tac(statement: &Statement, code_env: &mut CodeEnv) -> Vec<
     }
 }
 
+/// labels written by the user are namespaced by their function. Since `.` cannot appear in a C identifier,
+/// they cannot collide with the labels generated by the compiler, or the labels of other functions.
+fn get_user_label_name(label: &str, code_env: &CodeEnv) -> String {
+    format!("user.{}.{}", code_env.func_name, label)
+}
+
 fn resolve_variable_to_temp_name(name: &String, code_env: &CodeEnv) -> Identifier {
     for var_map in code_env.var_map_list.iter().rev() {
         if let Some(name) = var_map.get(name) {
@@ -238,6 +376,15 @@ fn resolve_variable_to_temp_name(name: &String, code_env: &CodeEnv) -> Identifie
     unreachable!()
 }
 
+/// returns true if the variable name refers to a global variable, that is,
+/// there is no local variable with the same name in scope.
+fn is_global_variable(name: &String, code_env: &CodeEnv) -> bool {
+    !code_env
+        .var_map_list
+        .iter()
+        .any(|var_map| var_map.contains_key(name))
+}
+
 fn generate_if_statement_tac(
     condition: &Expr,
     taken: &Statement,
@@ -278,7 +425,7 @@ fn generate_declaration_tac(
                     var_name
                 );
             }
-            let var_temp_loc = get_new_temp_name(t.to_size().unwrap());
+            let var_temp_loc = get_new_temp_name_for_type(t);
 
             match opt_value {
                 Some(expr) => {
@@ -301,37 +448,52 @@ fn generate_declaration_tac(
                 }
             }
         }
-        VarType::Arr(inner_type, num_elements) => {
+        VarType::Arr(_, _) | VarType::Struct(_) => {
             let var_map_list = &mut code_env.var_map_list;
             let last_elem_index = var_map_list.len() - 1;
             let this_scopes_variable_map = var_map_list.get_mut(last_elem_index).unwrap();
             let arr_ptr_identifier = get_new_temp_name(VarSize::Quad);
             this_scopes_variable_map.insert(var_name.clone(), arr_ptr_identifier);
 
-            let num_bytes = inner_type.num_bytes() * num_elements;
-
             let mut result = Vec::new();
-            result.push(TacInstr::MemChunk(arr_ptr_identifier, num_bytes, None));
-
-            if let Some(arr_init_expr) = opt_value {
-                if let Some(res) = gen_opt_arr_init_expr_tac(
-                    inner_type,
-                    *num_elements,
-                    arr_init_expr,
-                    arr_ptr_identifier,
-                ) {
-                    return res;
+            result.push(TacInstr::MemChunk(arr_ptr_identifier, t.num_bytes(), None));
+
+            match opt_value {
+                Some(
+                    arr_init_expr @ Expr {
+                        content: ExprEnum::ArrInitExpr(_),
+                        ..
+                    },
+                ) => {
+                    if let Some(res) =
+                        gen_opt_arr_init_expr_tac(t, arr_init_expr, arr_ptr_identifier)
+                    {
+                        return res;
+                    }
+                    result.extend(gen_arr_init_expr_tac(
+                        t,
+                        arr_init_expr,
+                        arr_ptr_identifier,
+                        code_env,
+                    ));
+                }
+                Some(expr) => {
+                    // a struct initialized by copying another struct
+                    let (instrs, tac_val) = generate_expr_tac(expr, code_env, ValTarget::Generate);
+                    result.extend(instrs);
+                    match tac_val {
+                        TacVal::Var(src_ptr) => result.extend(gen_struct_copy_tac(
+                            arr_ptr_identifier,
+                            src_ptr,
+                            t.num_bytes(),
+                        )),
+                        TacVal::Lit(_, _) | TacVal::FloatLit(_, _) => unreachable!(),
+                    }
                 }
-                let ptr_to_arr = get_new_temp_name(VarSize::Quad);
-                result.push(TacInstr::Copy(ptr_to_arr, TacVal::Var(arr_ptr_identifier)));
-                result.extend(gen_arr_init_expr_tac(
-                    inner_type,
-                    arr_init_expr,
-                    ptr_to_arr,
-                    code_env,
-                ));
+                None => {}
             }
             result
         }
+        VarType::Void | VarType::Func(_, _) => unreachable!(), // the parser rejects variables of incomplete types
     }
 }
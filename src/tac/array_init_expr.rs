@@ -1,23 +1,28 @@
 use crate::{
-    parser::expr_parser::{BinOp, Expr, ExprEnum},
-    types::{VarSize, VarType},
+    codegen::float::get_float_bits,
+    parser::{
+        arr_initializer_expr::get_initializer_element_type,
+        expr_parser::{BinOp, Expr, ExprEnum},
+    },
+    types::{struct_defs::get_struct_layout, VarSize, VarType},
 };
 
 use super::{
-    expr::{generate_expr_tac, get_type_size, ValTarget},
-    get_new_temp_name,
+    expr::{gen_conversion_tac, generate_expr_tac, ValTarget},
+    get_new_temp_name, get_new_temp_name_for_type,
+    structs::gen_struct_copy_tac,
     tac_instr::TacInstr,
     CodeEnv, Identifier, TacVal,
 };
 
 pub fn gen_arr_init_expr_tac(
-    arr_type: &VarType,
+    aggregate_type: &VarType,
     arr_init_expr: &Expr,
     ptr_to_arr: Identifier,
     code_env: &CodeEnv,
 ) -> Vec<TacInstr> {
-    // generates an array initializer expression by evaluating each expression (at runtime)
-    // and storing each value into the array.
+    // generates an array (or struct) initializer expression by evaluating each expression (at runtime)
+    // and storing each value into its element of the array.
     let mut result = Vec::new();
 
     let exprs = match &arr_init_expr.content {
@@ -25,101 +30,117 @@ pub fn gen_arr_init_expr_tac(
         _ => unreachable!(),
     };
 
-    let arr_type_size = arr_type.num_bytes() as i64;
-
-    for expr in exprs {
-        match &expr.content {
-            ExprEnum::ArrInitExpr(_) => {
-                let inner_type = match arr_type {
-                    VarType::Arr(inner, _) => inner,
-                    VarType::Fund(_) | VarType::Ptr(_) => unreachable!(),
-                };
-
-                let new_ptr = get_new_temp_name(VarSize::Quad);
-                result.push(TacInstr::Copy(new_ptr, TacVal::Var(ptr_to_arr)));
+    for (index, expr) in exprs.iter().enumerate() {
+        let element_type = get_initializer_element_type(aggregate_type, index).unwrap();
+        let element_ptr = get_new_temp_name(VarSize::Quad);
+        result.push(TacInstr::BinOp(
+            element_ptr,
+            TacVal::Var(ptr_to_arr),
+            TacVal::Lit(
+                get_initializer_element_offset(aggregate_type, index) as i64,
+                VarSize::Quad,
+            ),
+            BinOp::Plus,
+        ));
 
-                let instrs = gen_arr_init_expr_tac(inner_type, expr, new_ptr, code_env);
+        match (&expr.content, &element_type) {
+            (ExprEnum::ArrInitExpr(_), _) => {
+                let instrs = gen_arr_init_expr_tac(&element_type, expr, element_ptr, code_env);
                 result.extend(instrs);
             }
+            (_, VarType::Struct(_)) => {
+                // initializing a struct member of an array or struct by copying another struct
+                let (expr_instrs, tac_val) = generate_expr_tac(expr, code_env, ValTarget::Generate);
+                result.extend(expr_instrs);
+                match tac_val {
+                    TacVal::Var(src_ptr) => result.extend(gen_struct_copy_tac(
+                        element_ptr,
+                        src_ptr,
+                        element_type.num_bytes(),
+                    )),
+                    TacVal::Lit(_, _) | TacVal::FloatLit(_, _) => unreachable!(),
+                }
+            }
             _ => {
-                let size = get_type_size(arr_type).unwrap();
-                let val_tmp = get_new_temp_name(size);
+                let val_tmp = get_new_temp_name_for_type(&element_type);
                 let (expr_instrs, tac_val) =
                     generate_expr_tac(expr, code_env, ValTarget::Ident(val_tmp));
                 result.extend(expr_instrs);
-                result.push(TacInstr::DerefStore(ptr_to_arr, tac_val));
+                result.push(TacInstr::DerefStore(element_ptr, tac_val));
             }
         }
-
-        result.push(TacInstr::BinOp(
-            ptr_to_arr,
-            TacVal::Var(ptr_to_arr),
-            TacVal::Lit(arr_type_size, VarSize::Quad),
-            BinOp::Plus,
-        ))
     }
 
     result
 }
 
 pub fn gen_opt_arr_init_expr_tac(
-    arr_type: &VarType,
-    num_elements: usize,
+    aggregate_type: &VarType,
     arr_init_expr: &Expr,
     ptr_to_arr: Identifier,
 ) -> Option<Vec<TacInstr>> {
-    if let Some(bytes) = gen_arr_init_expr_bytes(arr_type, num_elements, arr_init_expr) {
-        let mut result = Vec::new();
-
-        let element_size = arr_type.num_bytes();
-        result.push(TacInstr::MemChunk(
-            ptr_to_arr,
-            num_elements * element_size,
-            Some(bytes),
-        ));
-
-        Some(result)
-    } else {
-        None
-    }
+    // if every element of the initializer is a constant, the memory can be initialized directly
+    let bytes = gen_arr_init_expr_bytes(aggregate_type, arr_init_expr)?;
+
+    Some(vec![TacInstr::MemChunk(
+        ptr_to_arr,
+        aggregate_type.num_bytes(),
+        Some(bytes),
+    )])
 }
 
-fn gen_arr_init_expr_bytes(
-    arr_type: &VarType,
-    num_elements: usize,
-    arr_init_expr: &Expr,
-) -> Option<Vec<u8>> {
+fn gen_arr_init_expr_bytes(aggregate_type: &VarType, arr_init_expr: &Expr) -> Option<Vec<u8>> {
     let exprs = match &arr_init_expr.content {
         ExprEnum::ArrInitExpr(x) => x,
         _ => unreachable!(),
     };
 
-    let mut bytes = Vec::new();
-    let element_size = arr_type.num_bytes();
-
-    for expr in exprs {
-        match expr.content {
-            ExprEnum::Int(value) => match element_size {
-                1 => bytes.extend((value as i8).to_le_bytes()),
-                2 => bytes.extend((value as i16).to_le_bytes()),
-                4 => bytes.extend((value as i32).to_le_bytes()),
-                8 => bytes.extend(value.to_le_bytes()),
-                _ => return None,
-            },
-            ExprEnum::ArrInitExpr(_) => {
-                let (inner_type, inner_num_elements) = match arr_type {
-                    VarType::Arr(a, b) => (a, b),
-                    _ => unreachable!(),
-                };
-
-                let inner_bytes = gen_arr_init_expr_bytes(inner_type, *inner_num_elements, expr)?;
-                bytes.extend(inner_bytes);
-            }
-            _ => return None,
-        }
-    }
-    while bytes.len() < num_elements * element_size {
-        bytes.push(0);
+    // any elements (and padding) not mentioned in the initializer are zero
+    let mut bytes = vec![0; aggregate_type.num_bytes()];
+
+    for (index, expr) in exprs.iter().enumerate() {
+        let element_type = get_initializer_element_type(aggregate_type, index).unwrap();
+        let offset = get_initializer_element_offset(aggregate_type, index);
+
+        let element_bytes = match &expr.content {
+            ExprEnum::ArrInitExpr(_) => gen_arr_init_expr_bytes(&element_type, expr)?,
+            _ => get_constant_bytes(expr, &element_type)?,
+        };
+
+        bytes[offset..offset + element_bytes.len()].copy_from_slice(&element_bytes);
     }
+
     Some(bytes)
 }
+
+/// returns the bytes of an integer or floating point constant converted to the given type,
+/// or None if the expression is not such a constant.
+pub fn get_constant_bytes(expr: &Expr, type_: &VarType) -> Option<Vec<u8>> {
+    let (instrs, val) = match (&expr.content, type_) {
+        (ExprEnum::Int(value), VarType::Fund(_)) => {
+            gen_conversion_tac(TacVal::Lit(*value, VarSize::Quad), type_)
+        }
+        (ExprEnum::Float(value, fund_t), VarType::Fund(_)) => {
+            gen_conversion_tac(TacVal::FloatLit(*value, fund_t.to_size()), type_)
+        }
+        (ExprEnum::Int(value), VarType::Ptr(_)) => (vec![], TacVal::Lit(*value, VarSize::Quad)),
+        _ => return None,
+    };
+    assert!(instrs.is_empty()); // constants are converted at compile time
+
+    let bytes = match val {
+        TacVal::Lit(value, _) => value.to_le_bytes(),
+        TacVal::FloatLit(value, size) => get_float_bits(value, size).to_le_bytes(),
+        TacVal::Var(_) => unreachable!(),
+    };
+    Some(bytes[..type_.num_bytes()].to_vec())
+}
+
+/// returns the offset in bytes of the element at `index` of an array or struct
+pub fn get_initializer_element_offset(aggregate_type: &VarType, index: usize) -> usize {
+    match aggregate_type {
+        VarType::Arr(inner, _) => inner.num_bytes() * index,
+        VarType::Struct(tag) => get_struct_layout(tag).unwrap().members[index].offset,
+        VarType::Fund(_) | VarType::Ptr(_) | VarType::Void | VarType::Func(_, _) => unreachable!(),
+    }
+}
@@ -0,0 +1,349 @@
+use std::{collections::HashMap, fmt};
+
+use super::tac_instr::TacInstr;
+
+/// the index of a basic block in `Cfg::blocks`
+pub type BlockId = usize;
+
+/// the block which execution of the function starts in, which is never jumped to
+pub const ENTRY_BLOCK: BlockId = 0;
+
+/// A `TacBasicBlock` represents a chain of instructions which will be executed in order,
+/// uninterrupted by branches. The basic block allows optimizations to be performed.
+/// A basic block can only be jumped to at its start, which is where its label is if it has one,
+/// and it ends with a branch or a return, or just before the label of the next block.
+pub struct TacBasicBlock {
+    pub instrs: Vec<TacInstr>,
+}
+
+impl TacBasicBlock {
+    /// returns the label at the start of the block, if it has one
+    pub fn get_label(&self) -> Option<&String> {
+        match self.instrs.first() {
+            Some(TacInstr::Label(label)) => Some(label),
+            _ => None,
+        }
+    }
+}
+
+/// The control flow graph of a function, which splits its instructions into basic blocks,
+/// with an edge from each block to each block that execution can continue in after it.
+pub struct Cfg {
+    pub blocks: Vec<TacBasicBlock>, // in the order that the instructions are laid out, so that blocks can fall through to the next one
+    pub preds: Vec<Vec<BlockId>>,
+    pub succs: Vec<Vec<BlockId>>,
+}
+
+/// The dominator tree of a control flow graph.
+/// A block dominates another if every path from the entry block to the other block goes through it.
+pub struct DomTree {
+    pub idom: Vec<Option<BlockId>>, // the immediate dominator of each block, None for the entry block and unreachable blocks
+    pub children: Vec<Vec<BlockId>>, // the blocks which each block is the immediate dominator of
+    is_reachable: Vec<bool>,
+}
+
+/// A natural loop, made of a header block which dominates the rest of the loop,
+/// and the blocks which can reach a jump back to the header without leaving the loop.
+#[derive(Debug)]
+pub struct Loop {
+    pub header: BlockId,
+    pub blocks: Vec<BlockId>, // sorted, including the header
+}
+
+impl Cfg {
+    /// splits the instructions of a function into basic blocks, and finds the edges between them
+    pub fn new(instrs: Vec<TacInstr>) -> Cfg {
+        let mut blocks = Vec::new();
+        let mut curr_block = Vec::new();
+        if let Some(TacInstr::Label(_)) = instrs.first() {
+            // the label may be jumped to, such as the start of a loop, so an empty entry block is added before it
+            blocks.push(TacBasicBlock { instrs: Vec::new() });
+        }
+
+        for instr in instrs {
+            if matches!(instr, TacInstr::Label(_)) && !curr_block.is_empty() {
+                blocks.push(TacBasicBlock {
+                    instrs: std::mem::take(&mut curr_block),
+                });
+            }
+            let ends_block = is_block_end(&instr);
+            curr_block.push(instr);
+            if ends_block {
+                blocks.push(TacBasicBlock {
+                    instrs: std::mem::take(&mut curr_block),
+                });
+            }
+        }
+        if !curr_block.is_empty() || blocks.is_empty() {
+            blocks.push(TacBasicBlock { instrs: curr_block });
+        }
+
+        let mut cfg = Cfg {
+            blocks,
+            preds: Vec::new(),
+            succs: Vec::new(),
+        };
+        cfg.compute_edges();
+        cfg
+    }
+
+    /// finds the predecessors and successors of each block again,
+    /// which must be done after changing the branches at the end of any block
+    pub fn compute_edges(&mut self) {
+        let label_to_block: HashMap<&String, BlockId> = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(id, block)| block.get_label().map(|label| (label, id)))
+            .collect();
+        let get_block = |label: &String| {
+            *label_to_block
+                .get(label)
+                .unwrap_or_else(|| panic!("jump to a label which does not exist: {}", label))
+        };
+
+        let num_blocks = self.blocks.len();
+        let mut succs = Vec::new();
+        for (id, block) in self.blocks.iter().enumerate() {
+            let next_block = Some(id + 1).filter(|next| *next < num_blocks);
+            let mut block_succs: Vec<BlockId> = match block.instrs.last() {
+                Some(TacInstr::Jmp(label)) => vec![get_block(label)],
+                Some(TacInstr::JmpZero(label, _)) | Some(TacInstr::JmpNotZero(label, _)) => {
+                    next_block.into_iter().chain([get_block(label)]).collect()
+                }
+                Some(TacInstr::JmpTable(_, _, labels)) => labels.iter().map(get_block).collect(),
+                Some(instr) if is_block_end(instr) => Vec::new(), // returns and calls to exit
+                _ => next_block.into_iter().collect(),
+            };
+            block_succs.sort();
+            block_succs.dedup();
+            succs.push(block_succs);
+        }
+
+        let mut preds = vec![Vec::new(); num_blocks];
+        for (id, block_succs) in succs.iter().enumerate() {
+            for succ in block_succs {
+                preds[*succ].push(id);
+            }
+        }
+
+        self.preds = preds;
+        self.succs = succs;
+    }
+
+    /// iterates over the instructions of every block, in the order they are laid out
+    pub fn instrs(&self) -> impl Iterator<Item = &TacInstr> {
+        self.blocks.iter().flat_map(|block| block.instrs.iter())
+    }
+
+    /// returns the blocks which are reachable from the entry block, in reverse postorder,
+    /// which visits each block before its successors, other than along the edges of loops
+    pub fn reverse_postorder(&self) -> Vec<BlockId> {
+        let mut visited = vec![false; self.blocks.len()];
+        let mut postorder = Vec::new();
+        // each entry is a block and the index of the next successor of it to visit
+        let mut stack = vec![(ENTRY_BLOCK, 0)];
+        visited[ENTRY_BLOCK] = true;
+
+        while let Some((block, succ_index)) = stack.pop() {
+            match self.succs[block].get(succ_index) {
+                Some(succ) => {
+                    stack.push((block, succ_index + 1));
+                    if !visited[*succ] {
+                        visited[*succ] = true;
+                        stack.push((*succ, 0));
+                    }
+                }
+                None => postorder.push(block),
+            }
+        }
+
+        postorder.reverse();
+        postorder
+    }
+
+    /// computes the dominator tree, using the algorithm from
+    /// "A Simple, Fast Dominance Algorithm" by Cooper, Harvey and Kennedy
+    pub fn dominators(&self) -> DomTree {
+        let rpo = self.reverse_postorder();
+        let mut rpo_index = vec![usize::MAX; self.blocks.len()];
+        for (index, block) in rpo.iter().enumerate() {
+            rpo_index[*block] = index;
+        }
+        let is_reachable: Vec<bool> = rpo_index.iter().map(|i| *i != usize::MAX).collect();
+
+        // the entry block is its own immediate dominator until the end,
+        // so that walking up the tree stops there
+        let mut idom: Vec<Option<BlockId>> = vec![None; self.blocks.len()];
+        idom[ENTRY_BLOCK] = Some(ENTRY_BLOCK);
+
+        let intersect = |idom: &Vec<Option<BlockId>>, mut a: BlockId, mut b: BlockId| {
+            while a != b {
+                while rpo_index[a] > rpo_index[b] {
+                    a = idom[a].unwrap();
+                }
+                while rpo_index[b] > rpo_index[a] {
+                    b = idom[b].unwrap();
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in rpo.iter().skip(1) {
+                let new_idom = self.preds[*block]
+                    .iter()
+                    .filter(|pred| idom[**pred].is_some())
+                    .copied()
+                    .reduce(|a, b| intersect(&idom, a, b));
+                if new_idom.is_some() && idom[*block] != new_idom {
+                    idom[*block] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        idom[ENTRY_BLOCK] = None;
+
+        let mut children = vec![Vec::new(); self.blocks.len()];
+        for (block, block_idom) in idom.iter().enumerate() {
+            if let Some(parent) = block_idom {
+                children[*parent].push(block);
+            }
+        }
+
+        DomTree {
+            idom,
+            children,
+            is_reachable,
+        }
+    }
+
+    /// returns the dominance frontier of each block, which is the set of blocks where the
+    /// block's dominance ends: blocks which are not strictly dominated by the block, but have a predecessor which is.
+    /// These are the blocks where SSA form needs phi instructions for the variables assigned in the block.
+    pub fn dominance_frontiers(&self, dom_tree: &DomTree) -> Vec<Vec<BlockId>> {
+        let mut frontiers = vec![Vec::new(); self.blocks.len()];
+        for (block, block_preds) in self.preds.iter().enumerate() {
+            if block_preds.len() < 2 || !dom_tree.is_reachable(block) {
+                continue;
+            }
+            // every block from a predecessor up to the immediate dominator of the join point has it in its frontier
+            for pred in block_preds
+                .iter()
+                .filter(|pred| dom_tree.is_reachable(**pred))
+            {
+                let mut runner = *pred;
+                while Some(runner) != dom_tree.idom[block] {
+                    if !frontiers[runner].contains(&block) {
+                        frontiers[runner].push(block);
+                    }
+                    // the immediate dominator of the join point dominates the predecessor, so it is reached before the entry block
+                    runner = dom_tree.idom[runner].unwrap();
+                }
+            }
+        }
+        frontiers
+    }
+
+    /// finds the natural loops of the function, which are formed by the edges which jump back to a block
+    /// that dominates the block jumped from. Loops which share a header are merged into one loop.
+    /// Loops which can be entered in the middle, using a goto, are not natural loops and are not found.
+    /// No pass uses the loops yet, so they are only found to annotate the control flow graph printed with `-d`.
+    pub fn loops(&self, dom_tree: &DomTree) -> Vec<Loop> {
+        let mut loops: Vec<Loop> = Vec::new();
+
+        for (tail, block_succs) in self.succs.iter().enumerate() {
+            for header in block_succs {
+                if !dom_tree.is_reachable(tail) || !dom_tree.dominates(*header, tail) {
+                    continue;
+                }
+                // the blocks in the loop are found by walking backwards from the tail until the header
+                let mut blocks = vec![*header];
+                let mut worklist = vec![tail];
+                while let Some(block) = worklist.pop() {
+                    if !blocks.contains(&block) && dom_tree.is_reachable(block) {
+                        blocks.push(block);
+                        worklist.extend(&self.preds[block]);
+                    }
+                }
+
+                match loops.iter_mut().find(|l| l.header == *header) {
+                    Some(existing_loop) => existing_loop.blocks.extend(blocks),
+                    None => loops.push(Loop {
+                        header: *header,
+                        blocks,
+                    }),
+                }
+            }
+        }
+
+        for l in &mut loops {
+            l.blocks.sort();
+            l.blocks.dedup();
+        }
+        loops
+    }
+}
+
+impl DomTree {
+    /// returns true if every path from the entry block to block `b` goes through block `a`.
+    /// Every block dominates itself.
+    pub fn dominates(&self, a: BlockId, b: BlockId) -> bool {
+        let mut curr = Some(b);
+        while let Some(block) = curr {
+            if block == a {
+                return true;
+            }
+            curr = self.idom[block];
+        }
+        false
+    }
+
+    /// returns false for blocks which can never be executed, such as the code after a return
+    pub fn is_reachable(&self, block: BlockId) -> bool {
+        self.is_reachable[block]
+    }
+}
+
+/// returns true if the instruction ends a basic block, because it may jump somewhere else or never returns
+pub fn is_block_end(instr: &TacInstr) -> bool {
+    match instr {
+        TacInstr::Jmp(_)
+        | TacInstr::JmpZero(_, _)
+        | TacInstr::JmpNotZero(_, _)
+        | TacInstr::JmpTable(_, _, _)
+        | TacInstr::Return(_) => true,
+        TacInstr::Call(func_name, _, _) => func_name == "exit",
+        _ => false,
+    }
+}
+
+impl fmt::Debug for Cfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dom_tree = self.dominators();
+        let loops = self.loops(&dom_tree);
+
+        for (id, block) in self.blocks.iter().enumerate() {
+            write!(
+                f,
+                "block {}: preds {:?}, succs {:?}",
+                id, self.preds[id], self.succs[id]
+            )?;
+            match dom_tree.idom[id] {
+                Some(idom) => write!(f, ", idom {}", idom)?,
+                None if !dom_tree.is_reachable(id) => write!(f, ", unreachable")?,
+                None => {}
+            }
+            if let Some(l) = loops.iter().find(|l| l.header == id) {
+                write!(f, ", header of loop {:?}", l.blocks)?;
+            }
+            writeln!(f)?;
+            for instr in &block.instrs {
+                writeln!(f, "    {:?}", instr)?;
+            }
+        }
+        Ok(())
+    }
+}
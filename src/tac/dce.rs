@@ -0,0 +1,113 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    cfg::{BlockId, Cfg},
+    tac_func::TacFunc,
+    tac_instr::TacInstr,
+    Identifier,
+};
+
+/// removes the blocks which can never be executed, and the instructions whose results are never used
+/// and which do nothing else. This must be run on a function in SSA form, so that a temporary
+/// which is not read anywhere in the function is never read after any of its assignments.
+pub fn eliminate_dead_code(func: &mut TacFunc) {
+    let cfg = func.get_cfg_mut();
+    remove_unreachable_blocks(cfg);
+    remove_unused_instrs(cfg);
+}
+
+fn remove_unreachable_blocks(cfg: &mut Cfg) {
+    let mut is_reachable = vec![false; cfg.blocks.len()];
+    for block in cfg.reverse_postorder() {
+        is_reachable[block] = true;
+    }
+
+    // the remaining blocks keep their order, so a block which falls through to the next one still does,
+    // since the next block is one of its successors and so is reachable too
+    let mut new_ids: Vec<Option<BlockId>> = Vec::new();
+    let mut num_kept = 0;
+    for block_is_reachable in &is_reachable {
+        new_ids.push(block_is_reachable.then_some(num_kept));
+        num_kept += *block_is_reachable as usize;
+    }
+
+    cfg.blocks = std::mem::take(&mut cfg.blocks)
+        .into_iter()
+        .zip(is_reachable)
+        .filter_map(|(block, is_reachable)| is_reachable.then_some(block))
+        .collect();
+    for block in &mut cfg.blocks {
+        for instr in &mut block.instrs {
+            if let TacInstr::Phi(_, args) = instr {
+                *args = std::mem::take(args)
+                    .into_iter()
+                    .filter_map(|(pred, val)| new_ids[pred].map(|new_pred| (new_pred, val)))
+                    .collect();
+            }
+        }
+    }
+    cfg.compute_edges();
+}
+
+fn remove_unused_instrs(cfg: &mut Cfg) {
+    // the temporaries read by instructions which do something other than assign a temporary are used,
+    // as are the temporaries read when computing a used temporary, and temporaries which may be read through pointers.
+    // Marking them this way removes the temporaries which are only used by each other, such as unused loop counters.
+    let mut reads_of_assignment: HashMap<Identifier, Vec<Identifier>> = HashMap::new();
+    let mut worklist = Vec::new();
+    for instr in cfg.instrs() {
+        match instr.get_written_identifier() {
+            Some(ident) if has_no_side_effects(instr) => reads_of_assignment
+                .entry(ident)
+                .or_default()
+                .extend(instr.get_read_identifiers()),
+            _ => worklist.extend(instr.get_read_identifiers()),
+        }
+        if let TacInstr::Ref(_, ident) = instr {
+            worklist.push(*ident);
+        }
+    }
+
+    let mut used = HashSet::new();
+    while let Some(ident) = worklist.pop() {
+        if used.insert(ident) {
+            if let Some(reads) = reads_of_assignment.get(&ident) {
+                worklist.extend(reads);
+            }
+        }
+    }
+
+    for block in &mut cfg.blocks {
+        block.instrs.retain(|instr| {
+            !has_no_side_effects(instr)
+                || instr
+                    .get_written_identifier()
+                    .map_or(true, |ident| used.contains(&ident))
+        });
+    }
+}
+
+/// returns true if the instruction does nothing other than assign its temporary
+fn has_no_side_effects(instr: &TacInstr) -> bool {
+    match instr {
+        TacInstr::BinOp(..)
+        | TacInstr::UnOp(..)
+        | TacInstr::Copy(..)
+        | TacInstr::Deref(..)
+        | TacInstr::Ref(..)
+        | TacInstr::MemChunk(..)
+        | TacInstr::StaticStrPtr(..)
+        | TacInstr::GlobalVarPtr(..)
+        | TacInstr::FunctionPtr(..)
+        | TacInstr::Phi(..) => true,
+        TacInstr::Return(_)
+        | TacInstr::DerefStore(..)
+        | TacInstr::Label(_)
+        | TacInstr::Jmp(_)
+        | TacInstr::JmpZero(..)
+        | TacInstr::JmpNotZero(..)
+        | TacInstr::JmpTable(..)
+        | TacInstr::Call(..)
+        | TacInstr::CallIndirect(..) => false,
+    }
+}
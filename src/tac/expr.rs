@@ -1,13 +1,18 @@
 use crate::{
-    parser::expr_parser::{BinOp, Expr, ExprEnum},
+    errors::check_bin_op_exprs::get_common_type,
+    parser::expr_parser::{BinOp, Expr, ExprEnum, UnOp},
     tac::get_new_label_number,
     types::VarType,
 };
 
 use super::{
-    get_new_temp_name,
+    get_new_temp_name, get_new_temp_name_for_type,
+    global_vars::{gen_global_var_ptr_tac, gen_global_var_tac},
+    is_global_variable,
     prefix_postfix_inc_dec::{gen_prefix_postfix_inc_dec, Operation},
-    resolve_variable_to_temp_name, CodeEnv, Identifier, TacInstr, TacVal, VarSize,
+    resolve_variable_to_temp_name,
+    structs::{gen_member_addr_tac, gen_member_tac, gen_struct_copy_tac},
+    CodeEnv, Identifier, TacInstr, TacVal, VarSize,
 };
 
 #[derive(PartialEq, Clone, Copy, Debug)]
@@ -29,7 +34,23 @@ pub fn generate_expr_tac(
     // should also ignore the TacVal returned, since the caller is evaluating the expr for side
     // effects only anyway
 
+    if let ValTarget::Ident(ident) = target {
+        // storing an integer into a floating point temporary (or the other way around) requires a conversion,
+        // as does storing a float into a double.
+        let is_float = expr.type_.as_ref().is_some_and(|t| t.is_float());
+        if is_float != ident.is_float()
+            || (is_float && get_expr_size(expr) != Some(ident.get_size()))
+        {
+            let (mut result, val) = generate_expr_tac(expr, code_env, ValTarget::Generate);
+            result.push(TacInstr::Copy(ident, val));
+            return (result, TacVal::Var(ident));
+        }
+    }
+
     match &expr.content {
+        ExprEnum::Var(var_name) if is_global_variable(var_name, code_env) => {
+            gen_global_var_tac(expr, var_name, target)
+        }
         ExprEnum::Var(var_name) => {
             if let ValTarget::Ident(target_temp_name) = target {
                 return (
@@ -52,12 +73,24 @@ pub fn generate_expr_tac(
                 TacVal::Var(ident),
             ),
         },
+        ExprEnum::Float(v, fund_t) => {
+            let val = TacVal::FloatLit(*v, fund_t.to_size());
+            match target {
+                ValTarget::Generate | ValTarget::None => (vec![], val),
+                ValTarget::Ident(ident) => (vec![TacInstr::Copy(ident, val)], TacVal::Var(ident)),
+            }
+        }
+        // unary plus only promotes its operand, which check_types has already done
+        ExprEnum::UnOp(UnOp::Plus, inner_expr) => generate_expr_tac(inner_expr, code_env, target),
         ExprEnum::UnOp(op, inner_expr) => match target {
             ValTarget::Generate | ValTarget::Ident(_) => {
                 let final_temp_name = if let ValTarget::Ident(ident) = target {
                     ident
                 } else {
-                    get_new_temp_name(get_expr_size(inner_expr).unwrap_or_default())
+                    match &expr.type_ {
+                        Some(t) => get_new_temp_name_for_type(t),
+                        None => get_new_temp_name(VarSize::default()),
+                    }
                 };
                 let (mut result, inner_val) =
                     generate_expr_tac(inner_expr, code_env, ValTarget::Generate);
@@ -67,10 +100,10 @@ pub fn generate_expr_tac(
             ValTarget::None => generate_expr_tac(inner_expr, code_env, ValTarget::None),
         },
         ExprEnum::BinOp(op, expr1, expr2) => {
-            generate_binop_tac(*op, expr1, expr2, code_env, target)
+            generate_binop_tac(*op, expr1, expr2, &expr.type_, code_env, target)
         }
         ExprEnum::Ternary(decision_expr, expr1, expr2) => {
-            generate_ternary_tac(decision_expr, expr1, expr2, code_env, target)
+            generate_ternary_tac(decision_expr, expr1, expr2, &expr.type_, code_env, target)
         }
 
         ExprEnum::PostfixInc(var) => {
@@ -87,7 +120,30 @@ pub fn generate_expr_tac(
         }
 
         ExprEnum::FunctionCall(func_ident, args) => {
-            gen_function_call_tac(func_ident, args, code_env, target)
+            gen_function_call_tac(func_ident, args, &expr.type_, code_env, target)
+        }
+        ExprEnum::IndirectCall(callee, args) => {
+            gen_indirect_call_tac(callee, args, &expr.type_, code_env, target)
+        }
+        ExprEnum::FunctionPtr(func_name) => match target {
+            ValTarget::None => (vec![], TacVal::Lit(0, VarSize::Quad)),
+            ValTarget::Generate | ValTarget::Ident(_) => {
+                let final_temp_name = if let ValTarget::Ident(ident) = target {
+                    ident
+                } else {
+                    get_new_temp_name(VarSize::Quad)
+                };
+                (
+                    vec![TacInstr::FunctionPtr(final_temp_name, func_name.clone())],
+                    TacVal::Var(final_temp_name),
+                )
+            }
+        },
+        ExprEnum::Deref(inner_expr)
+            if inner_expr.type_.as_ref().is_some_and(|t| t.is_func_ptr()) =>
+        {
+            // dereferencing a function pointer gives back the same function pointer
+            generate_expr_tac(inner_expr, code_env, target)
         }
         ExprEnum::Deref(inner_expr) => {
             let (mut res, res_ident) = generate_expr_tac(inner_expr, code_env, ValTarget::Generate);
@@ -98,12 +154,14 @@ pub fn generate_expr_tac(
                     let final_temp_name = if let ValTarget::Ident(ident) = target {
                         ident
                     } else {
-                        let pointee_size = get_pointee_size(inner_expr_type);
-                        get_new_temp_name(pointee_size.unwrap_or_default())
+                        get_new_temp_name_for_type(&get_pointee_type(inner_expr_type))
                     };
                     if let TacVal::Var(ident) = res_ident {
-                        if let VarType::Arr(_, _) = get_pointee_type(inner_expr_type) {
+                        if let VarType::Arr(_, _) | VarType::Struct(_) =
+                            get_pointee_type(inner_expr_type)
+                        {
                             // if we have a pointer to an array, then dereferencing should  give a pointer _into_ the array (pointing at first element).
+                            // similarly, structs are represented by a pointer to the beginning of the struct.
                             res.push(TacInstr::Copy(final_temp_name, TacVal::Var(ident)));
                         } else {
                             res.push(TacInstr::Deref(final_temp_name, ident));
@@ -117,19 +175,33 @@ pub fn generate_expr_tac(
         }
         ExprEnum::Ref(inner_exp) => match &inner_exp.content {
             ExprEnum::Deref(inner) => generate_expr_tac(inner, code_env, target),
-            ExprEnum::Var(var_name) => {
+            ExprEnum::FunctionPtr(_) => generate_expr_tac(inner_exp, code_env, target),
+            ExprEnum::Var(var_name) if !is_global_variable(var_name, code_env) => {
                 let final_temp_name = if let ValTarget::Ident(ident) = target {
                     ident
                 } else {
                     get_new_temp_name(VarSize::Quad)
                 };
                 assert!(final_temp_name.1 == VarSize::Quad);
-                let result = vec![TacInstr::Ref(
-                    final_temp_name,
-                    resolve_variable_to_temp_name(var_name, code_env),
-                )];
+                let var_ident = resolve_variable_to_temp_name(var_name, code_env);
+                let result = match inner_exp.type_ {
+                    // the temporary of an array or struct already holds a pointer to its memory
+                    Some(VarType::Arr(_, _)) | Some(VarType::Struct(_)) => {
+                        vec![TacInstr::Copy(final_temp_name, TacVal::Var(var_ident))]
+                    }
+                    _ => vec![TacInstr::Ref(final_temp_name, var_ident)],
+                };
                 (result, TacVal::Var(final_temp_name))
             }
+            ExprEnum::Var(_) | ExprEnum::Member(_, _) => {
+                let (mut result, ptr) = gen_lvalue_addr_tac(inner_exp, code_env);
+                if let ValTarget::Ident(ident) = target {
+                    result.push(TacInstr::Copy(ident, TacVal::Var(ptr)));
+                    (result, TacVal::Var(ident))
+                } else {
+                    (result, TacVal::Var(ptr))
+                }
+            }
             _ => unreachable!(),
         },
         ExprEnum::StaticStrPtr(val) => match target {
@@ -147,7 +219,11 @@ pub fn generate_expr_tac(
                 )
             }
         },
-        ExprEnum::Sizeof(_) => unreachable!(), // sizeof should have been replaced by int literal by check_types
+        ExprEnum::Member(_, _) => gen_member_tac(expr, code_env, target),
+        ExprEnum::Cast(target_type, inner_expr) => {
+            gen_cast_tac(target_type, inner_expr, code_env, target)
+        }
+        ExprEnum::Sizeof(_) | ExprEnum::SizeofType(_) => unreachable!(), // sizeof should have been replaced by int literal by check_types
         ExprEnum::ArrInitExpr(_) => unreachable!(), // ArrInitExpr should only appear in array initializations
     }
 }
@@ -156,6 +232,7 @@ fn generate_binop_tac(
     op: BinOp,
     expr1: &Expr,
     expr2: &Expr,
+    result_type: &Option<VarType>,
     code_env: &CodeEnv,
     target: ValTarget,
 ) -> (Vec<TacInstr>, TacVal) {
@@ -166,20 +243,52 @@ fn generate_binop_tac(
     if op == BinOp::Assign {
         return generate_assignment_tac(expr1, expr2, code_env, target);
     }
+
+    if op == BinOp::Comma {
+        // the left operand is evaluated only for its side effects, the value is the right operand
+        let (mut result, _) = generate_expr_tac(expr1, code_env, ValTarget::None);
+        let (result2, val) = generate_expr_tac(expr2, code_env, target);
+        result.extend(result2);
+        return (result, val);
+    }
     match target {
         ValTarget::Generate | ValTarget::Ident(_) => {
             let final_temp_name = if let ValTarget::Ident(ident) = target {
                 ident
             } else {
-                // integer promotion rules
-                get_new_temp_name(VarSize::Quad)
+                match result_type {
+                    Some(t) => get_new_temp_name_for_type(t),
+                    None => get_new_temp_name(VarSize::Quad),
+                }
             };
 
-            let (mut result, expr_1_val) = generate_expr_tac(expr1, code_env, ValTarget::Generate);
-            let (result2, expr_2_val) = generate_expr_tac(expr2, code_env, ValTarget::Generate);
+            let (mut result, mut expr_1_val) =
+                generate_expr_tac(expr1, code_env, ValTarget::Generate);
+            let (result2, mut expr_2_val) = generate_expr_tac(expr2, code_env, ValTarget::Generate);
 
             result.extend(result2);
 
+            let is_arithmetic = |t: &Option<VarType>| matches!(t, None | Some(VarType::Fund(_)));
+            if op == BinOp::ShiftLeft || op == BinOp::ShiftRight {
+                // the operands of a shift are not converted to a common type,
+                // only the left operand is promoted
+                if let Some(promoted_type) = result_type {
+                    let (instrs, val) = gen_conversion_tac(expr_1_val, promoted_type);
+                    result.extend(instrs);
+                    expr_1_val = val;
+                }
+            } else if is_arithmetic(&expr1.type_) && is_arithmetic(&expr2.type_) {
+                // the usual arithmetic conversions: both operands are converted to a common type
+                if let Some(common_type) = get_common_type(&expr1.type_, &expr2.type_) {
+                    let (instrs, val) = gen_conversion_tac(expr_1_val, &common_type);
+                    result.extend(instrs);
+                    expr_1_val = val;
+                    let (instrs, val) = gen_conversion_tac(expr_2_val, &common_type);
+                    result.extend(instrs);
+                    expr_2_val = val;
+                }
+            }
+
             if op == BinOp::Plus {
                 let t1 = expr1.type_.clone().unwrap_or_default();
                 let t2 = expr2.type_.clone().unwrap_or_default();
@@ -290,8 +399,12 @@ fn generate_assignment_tac(
     code_env: &CodeEnv,
     target: ValTarget,
 ) -> (Vec<TacInstr>, TacVal) {
+    if let Some(VarType::Struct(_)) = &lhs.type_ {
+        return generate_struct_assignment_tac(lhs, rhs, code_env, target);
+    }
+
     match &lhs.content {
-        ExprEnum::Var(var_name) => {
+        ExprEnum::Var(var_name) if !is_global_variable(var_name, code_env) => {
             let temp_name_of_assignee = resolve_variable_to_temp_name(var_name, code_env);
 
             let (mut result, tac_val) =
@@ -303,35 +416,85 @@ fn generate_assignment_tac(
                 (result, TacVal::Var(temp_name_of_assignee))
             }
         }
-        ExprEnum::Deref(inner) => {
-            let (mut result, tac_val_1) = generate_expr_tac(inner, code_env, ValTarget::Generate);
-            let (result2, tac_val_2) = generate_expr_tac(rhs, code_env, ValTarget::Generate);
+        ExprEnum::Var(_) | ExprEnum::Deref(_) | ExprEnum::Member(_, _) => {
+            let (mut result, ptr) = gen_lvalue_addr_tac(lhs, code_env);
+            // the value must have the size of the lvalue, so that the store doesn't overwrite neighboring memory
+            let val_tmp = get_new_temp_name_for_type(lhs.type_.as_ref().unwrap());
+            let (result2, tac_val) = generate_expr_tac(rhs, code_env, ValTarget::Ident(val_tmp));
             result.extend(result2);
-            if let TacVal::Var(ident) = tac_val_1 {
-                result.push(TacInstr::DerefStore(ident, tac_val_2.clone()));
+            result.push(TacInstr::DerefStore(ptr, tac_val.clone()));
+
+            if let ValTarget::Ident(ident) = target {
+                result.push(TacInstr::Copy(ident, tac_val));
+                (result, TacVal::Var(ident))
             } else {
-                unreachable!();
+                (result, tac_val)
             }
-
-            (result, tac_val_2)
         }
         _ => unreachable!(), // already checked that lhs must be a l_value
     }
 }
 
+/// generates a pointer to the memory of an lvalue which is not stored in a temporary,
+/// such as `*p`, `s.x` or a global variable.
+pub fn gen_lvalue_addr_tac(expr: &Expr, code_env: &CodeEnv) -> (Vec<TacInstr>, Identifier) {
+    match &expr.content {
+        ExprEnum::Var(var_name) => {
+            assert!(is_global_variable(var_name, code_env));
+            gen_global_var_ptr_tac(var_name)
+        }
+        ExprEnum::Deref(inner) => match generate_expr_tac(inner, code_env, ValTarget::Generate) {
+            (result, TacVal::Var(ptr)) => (result, ptr),
+            (_, TacVal::Lit(_, _) | TacVal::FloatLit(_, _)) => unreachable!(), // check_types only allows dereferencing pointers
+        },
+        ExprEnum::Member(struct_expr, member_name) => {
+            gen_member_addr_tac(struct_expr, member_name, code_env)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn generate_struct_assignment_tac(
+    lhs: &Expr,
+    rhs: &Expr,
+    code_env: &CodeEnv,
+    target: ValTarget,
+) -> (Vec<TacInstr>, TacVal) {
+    // both sides of the assignment evaluate to pointers to the structs
+    let (mut result, dst_ptr) = generate_expr_tac(lhs, code_env, ValTarget::Generate);
+    let (result2, src_ptr) = generate_expr_tac(rhs, code_env, ValTarget::Generate);
+    result.extend(result2);
+
+    match (&dst_ptr, src_ptr) {
+        (TacVal::Var(dst_ptr), TacVal::Var(src_ptr)) => {
+            let num_bytes = lhs.type_.as_ref().unwrap().num_bytes();
+            result.extend(gen_struct_copy_tac(*dst_ptr, src_ptr, num_bytes));
+        }
+        _ => unreachable!(),
+    }
+
+    if let ValTarget::Ident(ident) = target {
+        result.push(TacInstr::Copy(ident, dst_ptr));
+        (result, TacVal::Var(ident))
+    } else {
+        (result, dst_ptr)
+    }
+}
+
 fn generate_ternary_tac(
     decision_expr: &Expr,
     expr1: &Expr,
     expr2: &Expr,
+    result_type: &Option<VarType>,
     code_env: &CodeEnv,
     target: ValTarget,
 ) -> (Vec<TacInstr>, TacVal) {
-    let final_temp_name = if let ValTarget::Ident(ident) = target {
-        ident
-    } else {
-        get_new_temp_name(
+    let final_temp_name = match (target, result_type) {
+        (ValTarget::Ident(ident), _) => ident,
+        (_, Some(t)) if t.is_float() => get_new_temp_name_for_type(t),
+        _ => get_new_temp_name(
             get_bigger_size(get_expr_size(expr1), get_expr_size(expr2)).unwrap_or_default(),
-        )
+        ),
     };
 
     let label_num = get_new_label_number();
@@ -356,32 +519,180 @@ fn generate_ternary_tac(
 
 pub fn gen_function_call_tac(
     func_ident: &str,
-    args: &Vec<Expr>,
+    args: &[Expr],
+    return_type: &Option<VarType>,
     code_env: &CodeEnv,
     target: ValTarget,
 ) -> (Vec<TacInstr>, TacVal) {
-    let final_temp_name = if let ValTarget::Ident(ident) = target {
-        ident
-    } else {
-        get_new_temp_name(VarSize::default())
+    let param_types = code_env.func_param_types_map.get(func_ident);
+    let (mut result, arg_vals) = gen_call_args_tac(args, param_types, code_env);
+
+    let optional_ident = get_call_result_ident(return_type, target);
+    result.push(TacInstr::Call(
+        func_ident.to_string(),
+        arg_vals,
+        optional_ident,
+    ));
+
+    (result, get_call_result_val(optional_ident))
+}
+
+/// generates a call through a function pointer, such as `(*fp)(1, 2)` or `ops[i](1, 2)`
+fn gen_indirect_call_tac(
+    callee: &Expr,
+    args: &[Expr],
+    return_type: &Option<VarType>,
+    code_env: &CodeEnv,
+    target: ValTarget,
+) -> (Vec<TacInstr>, TacVal) {
+    let param_types = match get_pointee_type(callee.type_.as_ref().unwrap()) {
+        VarType::Func(_, param_types) => param_types,
+        _ => unreachable!(), // check_types verified that the callee is a function pointer
     };
 
+    // the callee is evaluated before the arguments
+    let (mut result, callee_val) = generate_expr_tac(callee, code_env, ValTarget::Generate);
+    let (instrs, arg_vals) = gen_call_args_tac(args, Some(&param_types), code_env);
+    result.extend(instrs);
+
+    let optional_ident = get_call_result_ident(return_type, target);
+    result.push(TacInstr::CallIndirect(callee_val, arg_vals, optional_ident));
+
+    (result, get_call_result_val(optional_ident))
+}
+
+/// evaluates the arguments of a call, converting arithmetic arguments to the types of the parameters.
+/// Builtin functions do not have parameter types, so their arguments are not converted.
+fn gen_call_args_tac(
+    args: &[Expr],
+    param_types: Option<&Vec<VarType>>,
+    code_env: &CodeEnv,
+) -> (Vec<TacInstr>, Vec<TacVal>) {
     let mut result = Vec::new();
     let mut arg_vals = Vec::new();
 
-    for arg_expr in args {
-        let (instrs, arg_val) = generate_expr_tac(arg_expr, code_env, ValTarget::Generate);
+    for (index, arg_expr) in args.iter().enumerate() {
+        let (instrs, mut arg_val) = generate_expr_tac(arg_expr, code_env, ValTarget::Generate);
         result.extend(instrs);
+
+        // arithmetic arguments are implicitly converted to the type of the parameter
+        if let Some(param_type @ VarType::Fund(_)) = param_types.map(|types| &types[index]) {
+            if let None | Some(VarType::Fund(_)) = arg_expr.type_ {
+                let (instrs, converted_val) = gen_conversion_tac(arg_val, param_type);
+                result.extend(instrs);
+                arg_val = converted_val;
+            }
+        }
         arg_vals.push(arg_val);
     }
 
-    result.push(TacInstr::Call(
-        func_ident.to_string(),
-        arg_vals,
-        Some(final_temp_name),
-    ));
+    (result, arg_vals)
+}
 
-    (result, TacVal::Var(final_temp_name))
+/// returns the identifier which holds the return value of a call, or None if the value is discarded,
+/// which is always the case for void functions
+fn get_call_result_ident(return_type: &Option<VarType>, target: ValTarget) -> Option<Identifier> {
+    match target {
+        ValTarget::Ident(ident) => Some(ident),
+        ValTarget::Generate => match return_type {
+            // floating point values are returned in xmm0 instead of rax
+            Some(t) if t.is_float() => Some(get_new_temp_name_for_type(t)),
+            _ => Some(get_new_temp_name(VarSize::default())),
+        },
+        ValTarget::None => None,
+    }
+}
+
+fn get_call_result_val(optional_ident: Option<Identifier>) -> TacVal {
+    match optional_ident {
+        Some(ident) => TacVal::Var(ident),
+        None => TacVal::Lit(0, VarSize::default()),
+    }
+}
+
+/// generates an explicit conversion. Converting to a smaller type truncates the value,
+/// while converting to a bigger type sign extends or zero extends it, depending on the signedness of the value.
+fn gen_cast_tac(
+    target_type: &VarType,
+    inner_expr: &Expr,
+    code_env: &CodeEnv,
+    target: ValTarget,
+) -> (Vec<TacInstr>, TacVal) {
+    if target == ValTarget::None || *target_type == VarType::Void {
+        let (result, _) = generate_expr_tac(inner_expr, code_env, ValTarget::None);
+        return (result, TacVal::Lit(0, VarSize::Quad));
+    }
+
+    let (mut result, inner_val) = generate_expr_tac(inner_expr, code_env, ValTarget::Generate);
+    let (instrs, converted_val) = gen_conversion_tac(inner_val, target_type);
+    result.extend(instrs);
+
+    match target {
+        ValTarget::Ident(ident) => {
+            result.push(TacInstr::Copy(ident, converted_val));
+            (result, TacVal::Var(ident))
+        }
+        _ => (result, converted_val),
+    }
+}
+
+/// converts a value to the given arithmetic type. Integers are truncated, sign extended or zero extended as needed,
+/// and conversions to and from floating point types round or truncate the value.
+pub fn gen_conversion_tac(val: TacVal, t: &VarType) -> (Vec<TacInstr>, TacVal) {
+    let size = get_type_size(t).unwrap();
+    match val {
+        // literals can be converted at compile time
+        TacVal::Lit(v, _) if t.is_float() => {
+            let v = if size == VarSize::Dword {
+                v as f32 as f64
+            } else {
+                v as f64
+            };
+            (vec![], TacVal::FloatLit(v, size))
+        }
+        TacVal::FloatLit(v, _) if t.is_float() => {
+            let v = if size == VarSize::Dword {
+                v as f32 as f64
+            } else {
+                v
+            };
+            (vec![], TacVal::FloatLit(v, size))
+        }
+        TacVal::FloatLit(v, _) => {
+            // floating point values are truncated towards zero
+            let v = if size == VarSize::Quad && t.is_unsigned() {
+                v as u64 as i64
+            } else {
+                v as i64
+            };
+            gen_conversion_tac(TacVal::Lit(v, VarSize::Quad), t)
+        }
+        TacVal::Lit(v, _) => {
+            let converted = match (size, t.is_unsigned()) {
+                (VarSize::Byte, false) => v as i8 as i64,
+                (VarSize::Byte, true) => v as u8 as i64,
+                (VarSize::Word, false) => v as i16 as i64,
+                (VarSize::Word, true) => v as u16 as i64,
+                (VarSize::Dword, false) => v as i32 as i64,
+                (VarSize::Dword, true) => v as u32 as i64,
+                (VarSize::Quad, _) => v,
+            };
+            (vec![], TacVal::Lit(converted, size))
+        }
+        TacVal::Var(ident) => {
+            if ident.get_size() == size
+                && ident.is_unsigned() == t.is_unsigned()
+                && ident.is_float() == t.is_float()
+            {
+                return (vec![], TacVal::Var(ident));
+            }
+            let converted = get_new_temp_name_for_type(t);
+            (
+                vec![TacInstr::Copy(converted, TacVal::Var(ident))],
+                TacVal::Var(converted),
+            )
+        }
+    }
 }
 
 pub fn get_bigger_size(s1: Option<VarSize>, s2: Option<VarSize>) -> Option<VarSize> {
@@ -408,8 +719,8 @@ pub fn get_expr_size(expr: &Expr) -> Option<VarSize> {
 }
 
 pub fn get_type_size(t: &VarType) -> Option<VarSize> {
-    if let VarType::Arr(_, _) = t {
-        // arrays are pointers, and therefore occupy a quad
+    if let VarType::Arr(_, _) | VarType::Struct(_) = t {
+        // arrays and structs are pointers, and therefore occupy a quad
         return Some(VarSize::Quad);
     }
     match t.num_bytes() {
@@ -471,7 +782,13 @@ fn gen_addition_tac(
         (VarType::Ptr(_), VarType::Ptr(_))
         | (VarType::Ptr(_), VarType::Arr(_, _))
         | (VarType::Arr(_, _), VarType::Ptr(_))
-        | (VarType::Arr(_, _), VarType::Arr(_, _)) => unreachable!(),
+        | (VarType::Arr(_, _), VarType::Arr(_, _))
+        | (VarType::Struct(_), _)
+        | (_, VarType::Struct(_))
+        | (VarType::Void, _)
+        | (_, VarType::Void)
+        | (VarType::Func(_, _), _)
+        | (_, VarType::Func(_, _)) => unreachable!(),
     }
     result
 }
@@ -521,16 +838,12 @@ pub fn gen_subtraction_tac(
     result
 }
 
-pub fn get_pointee_size(t: &VarType) -> Option<VarSize> {
-    match t {
-        VarType::Ptr(inner) | VarType::Arr(inner, _) => get_type_size(inner),
-        VarType::Fund(_) => unreachable!(), // this function should only be called with a type of pointer or array
-    }
-}
-
 pub fn get_pointee_type(t: &VarType) -> VarType {
     match t {
         VarType::Ptr(inner) | VarType::Arr(inner, _) => *inner.clone(),
-        VarType::Fund(_) => unreachable!(), // this function should only be called with a type of pointer or array
+        // this function should only be called with a type of pointer or array
+        VarType::Fund(_) | VarType::Struct(_) | VarType::Void | VarType::Func(_, _) => {
+            unreachable!()
+        }
     }
 }
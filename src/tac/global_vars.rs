@@ -0,0 +1,277 @@
+use std::ptr::addr_of_mut;
+
+use crate::{
+    errors::display::err_display,
+    parser::{
+        arr_initializer_expr::get_initializer_element_type,
+        expr_parser::{BinOp, Expr, ExprEnum},
+        global_strings::get_string_label,
+        Statement,
+    },
+    types::{struct_defs::get_struct_member, VarSize, VarType},
+};
+
+use super::{
+    array_init_expr::{get_constant_bytes, get_initializer_element_offset},
+    expr::ValTarget,
+    get_new_temp_name, get_new_temp_name_for_type, Identifier, TacInstr, TacVal,
+};
+
+struct GlobalVar {
+    name: String,
+    num_bytes: usize,
+    alignment: usize,
+    init: Option<GlobalVarInit>, // None if the variable is zero initialized
+}
+
+struct GlobalVarInit {
+    bytes: Vec<u8>,
+    // offsets into `bytes` which hold a pointer to a static string, a function or a global variable instead,
+    // along with the label which is pointed to, possibly plus an offset
+    label_ptrs: Vec<(usize, String)>,
+}
+
+static mut GLOBAL_VARS: Vec<GlobalVar> = Vec::new();
+
+/// Registers a global variable declaration, evaluating its initializer at compile time.
+pub fn add_global_var(declaration: &Statement) {
+    let (name, opt_expr, type_) = match declaration {
+        Statement::Declare(name, opt_expr, type_, _) => (name, opt_expr, type_),
+        Statement::EnumConsts(_) => return, // enumerators do not take up any memory
+        _ => unreachable!(),
+    };
+
+    let init = opt_expr.as_ref().map(|expr| {
+        let mut init = GlobalVarInit {
+            bytes: vec![0; type_.num_bytes()],
+            label_ptrs: Vec::new(),
+        };
+        write_global_init_data(name, type_, expr, 0, &mut init);
+        init
+    });
+
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        (*addr_of_mut!(GLOBAL_VARS)).push(GlobalVar {
+            name: name.clone(),
+            num_bytes: type_.num_bytes(),
+            alignment: type_.alignment(),
+            init,
+        });
+    }
+}
+
+fn write_global_init_data(
+    var_name: &String,
+    type_: &VarType,
+    expr: &Expr,
+    offset: usize,
+    init: &mut GlobalVarInit,
+) {
+    match (&expr.content, type_) {
+        (ExprEnum::Int(_) | ExprEnum::Float(_, _), VarType::Fund(_) | VarType::Ptr(_)) => {
+            let bytes = match get_constant_bytes(expr, type_) {
+                Some(bytes) => bytes,
+                None => err_display(
+                    format!(
+                        "initializer of global variable {} is not a compile-time constant",
+                        var_name
+                    ),
+                    expr.pos,
+                ),
+            };
+            init.bytes[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        }
+        (ExprEnum::FunctionPtr(func_name), VarType::Ptr(_)) => {
+            init.label_ptrs.push((offset, format!(".{}", func_name)));
+        }
+        (ExprEnum::Ref(inner) | ExprEnum::Deref(inner), VarType::Ptr(_))
+            if matches!(inner.content, ExprEnum::FunctionPtr(_)) =>
+        {
+            // `&f` and `*f` are the same pointer as `f`
+            write_global_init_data(var_name, type_, inner, offset, init);
+        }
+        (ExprEnum::ArrInitExpr(exprs), VarType::Arr(_, _) | VarType::Struct(_)) => {
+            for (index, element_expr) in exprs.iter().enumerate() {
+                let element_type = get_initializer_element_type(type_, index).unwrap();
+                let element_offset = offset + get_initializer_element_offset(type_, index);
+                write_global_init_data(var_name, &element_type, element_expr, element_offset, init);
+            }
+        }
+        (_, VarType::Ptr(_)) => match get_address_constant(expr) {
+            Some((label, 0)) => init.label_ptrs.push((offset, label)),
+            Some((label, addr_offset)) => init
+                .label_ptrs
+                .push((offset, format!("{} + {}", label, addr_offset))),
+            None => err_display(
+                format!(
+                    "initializer of global variable {} is not a compile-time constant",
+                    var_name
+                ),
+                expr.pos,
+            ),
+        },
+        _ => err_display(
+            format!(
+                "initializer of global variable {} is not a compile-time constant",
+                var_name
+            ),
+            expr.pos,
+        ),
+    }
+}
+
+/// evaluates an address constant, such as `&g`, `arr`, `&arr[2]`, `&s.member` or `"abc" + 1`,
+/// to a label and an offset in bytes from it. Returns None if the expression is not an address constant.
+fn get_address_constant(expr: &Expr) -> Option<(String, i64)> {
+    match &expr.content {
+        ExprEnum::StaticStrPtr(s) => Some((get_string_label(s), 0)),
+        // arrays evaluate to a pointer to their first element
+        ExprEnum::Var(name) if matches!(expr.type_, Some(VarType::Arr(_, _))) => {
+            Some((get_global_var_label(name), 0))
+        }
+        ExprEnum::Ref(inner) => get_lvalue_address(inner),
+        ExprEnum::Cast(VarType::Ptr(_), inner) => get_address_constant(inner),
+        ExprEnum::BinOp(op @ (BinOp::Plus | BinOp::Minus), expr1, expr2) => {
+            // the integer operand may come first in an addition
+            let (ptr_expr, int_expr) = match (&expr1.content, &expr2.content) {
+                (ExprEnum::Int(_), _) if *op == BinOp::Plus => (expr2, expr1),
+                _ => (expr1, expr2),
+            };
+            let (label, offset) = get_address_constant(ptr_expr)?;
+            let num_elements = match int_expr.content {
+                ExprEnum::Int(n) if *op == BinOp::Minus => -n,
+                ExprEnum::Int(n) => n,
+                _ => return None,
+            };
+            let element_size = match ptr_expr.type_.as_ref()? {
+                VarType::Ptr(inner) | VarType::Arr(inner, _) if inner.is_complete() => {
+                    inner.num_bytes() as i64
+                }
+                _ => return None,
+            };
+            Some((label, offset + num_elements * element_size))
+        }
+        _ => None,
+    }
+}
+
+/// returns the label and offset of an lvalue whose address is a constant, such as a global variable or its member
+fn get_lvalue_address(expr: &Expr) -> Option<(String, i64)> {
+    match &expr.content {
+        ExprEnum::Var(name) => Some((get_global_var_label(name), 0)),
+        ExprEnum::Deref(inner) => get_address_constant(inner),
+        ExprEnum::Member(inner, member_name) => {
+            let (label, offset) = get_lvalue_address(inner)?;
+            let member = match inner.type_.as_ref()? {
+                VarType::Struct(tag) => get_struct_member(tag, member_name)?,
+                _ => return None,
+            };
+            Some((label, offset + member.offset as i64))
+        }
+        _ => None,
+    }
+}
+
+pub fn get_global_var_label(name: &String) -> String {
+    format!(".globl_var_{}", name)
+}
+
+/// generates the value of a global variable. Like local variables, global arrays and structs
+/// evaluate to a pointer to their memory.
+pub fn gen_global_var_tac(
+    expr: &Expr,
+    var_name: &str,
+    target: ValTarget,
+) -> (Vec<TacInstr>, TacVal) {
+    if target == ValTarget::None {
+        return (vec![], TacVal::Lit(0, VarSize::Quad));
+    }
+
+    let type_ = expr.type_.clone().unwrap();
+    let (mut result, var_ptr) = gen_global_var_ptr_tac(var_name);
+
+    let final_temp_name = if let ValTarget::Ident(ident) = target {
+        ident
+    } else {
+        get_new_temp_name_for_type(&type_)
+    };
+
+    match type_ {
+        VarType::Arr(_, _) | VarType::Struct(_) => {
+            result.push(TacInstr::Copy(final_temp_name, TacVal::Var(var_ptr)))
+        }
+        VarType::Fund(_) | VarType::Ptr(_) => {
+            result.push(TacInstr::Deref(final_temp_name, var_ptr))
+        }
+        // check_types rejects values of type void, and functions are only used through pointers
+        VarType::Void | VarType::Func(_, _) => unreachable!(),
+    }
+
+    (result, TacVal::Var(final_temp_name))
+}
+
+/// generates a pointer to the memory of a global variable
+pub fn gen_global_var_ptr_tac(var_name: &str) -> (Vec<TacInstr>, Identifier) {
+    let var_ptr = get_new_temp_name(VarSize::Quad);
+    (
+        vec![TacInstr::GlobalVarPtr(var_ptr, var_name.to_owned())],
+        var_ptr,
+    )
+}
+
+/// Initialized global variables are placed into the .data section,
+/// and zero initialized global variables are placed in the .bss section.
+pub fn generate_global_vars_asm() -> String {
+    let mut data_section = String::from("section .data\n");
+    let mut bss_section = String::from("section .bss\n");
+
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        for global_var in (*addr_of_mut!(GLOBAL_VARS)).iter() {
+            let label = get_global_var_label(&global_var.name);
+            match &global_var.init {
+                Some(init) => {
+                    data_section.push_str(&format!("align {}\n{}:\n", global_var.alignment, label));
+                    data_section.push_str(&generate_init_data_asm(init));
+                }
+                None => bss_section.push_str(&format!(
+                    "alignb {}\n{}: resb {}\n",
+                    global_var.alignment, label, global_var.num_bytes
+                )),
+            }
+        }
+    }
+
+    data_section + &bss_section + "section .text\n"
+}
+
+fn generate_init_data_asm(init: &GlobalVarInit) -> String {
+    let mut result = String::new();
+    let mut offset = 0;
+
+    while offset < init.bytes.len() {
+        if let Some((_, label)) = init.label_ptrs.iter().find(|(o, _)| *o == offset) {
+            result.push_str(&format!("dq {}\n", label));
+            offset += 8;
+            continue;
+        }
+
+        // emit bytes up until the next label pointer
+        let next_ptr_offset = init
+            .label_ptrs
+            .iter()
+            .map(|(o, _)| *o)
+            .filter(|o| *o > offset)
+            .min()
+            .unwrap_or(init.bytes.len());
+        let byte_strings: Vec<String> = init.bytes[offset..next_ptr_offset]
+            .iter()
+            .map(|b| b.to_string())
+            .collect();
+        result.push_str(&format!("db {}\n", byte_strings.join(", ")));
+        offset = next_ptr_offset;
+    }
+
+    result
+}
@@ -1,23 +1,27 @@
 use std::collections::HashMap;
 
-use crate::parser::{expr_parser::Expr, Statement};
+use crate::{
+    errors::display::err_display,
+    parser::{expr_parser::Expr, Statement},
+    tokenizer::source_cursor::SourcePtr,
+};
 
 use super::{
     expr::{generate_expr_tac, ValTarget},
     generate_declaration_tac, generate_statement_tac, get_new_label_number, CodeEnv, TacInstr,
 };
 
-pub fn generate_continue_tac(code_env: &CodeEnv) -> Vec<TacInstr> {
+pub fn generate_continue_tac(pos: SourcePtr, code_env: &CodeEnv) -> Vec<TacInstr> {
     match &code_env.loop_label_begin {
         Some(label) => vec![TacInstr::Jmp(label.clone())],
-        None => panic!("continue statement outside of loop"),
+        None => err_display("continue statement not within a loop", pos),
     }
 }
 
-pub fn generate_break_tac(code_env: &CodeEnv) -> Vec<TacInstr> {
+pub fn generate_break_tac(pos: SourcePtr, code_env: &CodeEnv) -> Vec<TacInstr> {
     match &code_env.loop_label_end {
         Some(label) => vec![TacInstr::Jmp(label.clone())],
-        None => panic!("break statement outside of loop"),
+        None => err_display("break statement not within a loop or switch", pos),
     }
 }
 
@@ -51,8 +55,40 @@ pub fn gen_while_loop_tac(
     result
 }
 
+pub fn gen_do_while_loop_tac(
+    body: &Statement,
+    condition: &Expr,
+    code_env: &mut CodeEnv,
+) -> Vec<TacInstr> {
+    let label_num = get_new_label_number();
+    let label_loop_begin = format!("begin_do_while_{}", label_num);
+    let label_loop_condition = format!("condition_do_while_{}", label_num);
+    let label_loop_end = format!("end_do_while_{}", label_num);
+
+    let outer_loop_label_end = code_env.loop_label_end.clone();
+    let outer_loop_label_begin = code_env.loop_label_begin.clone();
+
+    // continue skips the rest of the body, but the condition is still checked
+    code_env.loop_label_begin = Some(label_loop_condition.clone());
+    code_env.loop_label_end = Some(label_loop_end.clone());
+
+    let mut result = Vec::new();
+    result.push(TacInstr::Label(label_loop_begin.clone()));
+    result.extend(generate_statement_tac(body, code_env));
+    result.push(TacInstr::Label(label_loop_condition));
+    let (expr_result, expr_val) = generate_expr_tac(condition, code_env, ValTarget::Generate);
+    result.extend(expr_result);
+    result.push(TacInstr::JmpNotZero(label_loop_begin, expr_val));
+    result.push(TacInstr::Label(label_loop_end));
+
+    code_env.loop_label_end = outer_loop_label_end;
+    code_env.loop_label_begin = outer_loop_label_begin;
+
+    result
+}
+
 pub fn gen_for_loop_tac(
-    initial_expr: &Statement,
+    initial_stmts: &Vec<Statement>,
     control_expr: Option<&Expr>,
     post_expr: Option<&Expr>,
     body: &Statement,
@@ -71,17 +107,18 @@ pub fn gen_for_loop_tac(
 
     let mut result = Vec::new();
     code_env.var_map_list.push(HashMap::new()); // push header var map
-    match initial_expr {
-        Statement::Declare(var_name, optional_expr, t) => {
-            let instrs = generate_declaration_tac(var_name, optional_expr, t, code_env);
-            result.extend(instrs);
-        }
-        Statement::Expr(expr) => {
-            let (instrs, _) = generate_expr_tac(expr, code_env, ValTarget::None);
-            result.extend(instrs);
+    for initial_stmt in initial_stmts {
+        match initial_stmt {
+            Statement::Declare(var_name, optional_expr, t, _) => {
+                let instrs = generate_declaration_tac(var_name, optional_expr, t, code_env);
+                result.extend(instrs);
+            }
+            Statement::Expr(expr) => {
+                let (instrs, _) = generate_expr_tac(expr, code_env, ValTarget::None);
+                result.extend(instrs);
+            }
+            _ => unreachable!(),
         }
-        Statement::Empty => {}
-        _ => unreachable!(),
     }
 
     result.push(TacInstr::Label(start_loop_label.clone()));
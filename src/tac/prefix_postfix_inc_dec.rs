@@ -1,14 +1,13 @@
 use crate::{
     parser::expr_parser::{BinOp, Expr, ExprEnum},
-    tac::expr::{generate_expr_tac, get_pointee_size},
     types::{VarSize, VarType},
 };
 
 use super::{
-    expr::{get_pointee_type, ValTarget},
-    Identifier,
+    expr::{gen_lvalue_addr_tac, ValTarget},
+    is_global_variable, Identifier,
 };
-use super::{get_new_temp_name, resolve_variable_to_temp_name, CodeEnv, TacInstr, TacVal};
+use super::{get_new_temp_name_for_type, resolve_variable_to_temp_name, CodeEnv, TacInstr, TacVal};
 
 pub enum Operation {
     PrefixInc,
@@ -38,7 +37,7 @@ pub fn gen_prefix_postfix_inc_dec(
     }
 
     match &expr.content {
-        ExprEnum::Var(var_name) => {
+        ExprEnum::Var(var_name) if !is_global_variable(var_name, code_env) => {
             let mut result = Vec::new();
             let ident_to_update = resolve_variable_to_temp_name(var_name, code_env);
             let mut ident_to_return;
@@ -46,8 +45,7 @@ pub fn gen_prefix_postfix_inc_dec(
                 if let ValTarget::Ident(ident) = target {
                     ident_to_return = ident;
                 } else {
-                    ident_to_return =
-                        get_new_temp_name(resolve_variable_to_temp_name(var_name, code_env).1);
+                    ident_to_return = get_new_temp_name_for_type(expr.type_.as_ref().unwrap());
                 };
                 result.push(TacInstr::Copy(
                     ident_to_return,
@@ -71,39 +69,33 @@ pub fn gen_prefix_postfix_inc_dec(
 
             (result, TacVal::Var(ident_to_return))
         }
-        ExprEnum::Deref(ptr_expr) => {
-            let (mut result, tac_val_1) =
-                generate_expr_tac(ptr_expr, code_env, ValTarget::Generate);
-            let pointee_size = get_pointee_size(&ptr_expr.type_.clone().unwrap()).unwrap();
-            let pointee_type = get_pointee_type(&ptr_expr.type_.clone().unwrap());
-            let temp_ident = get_new_temp_name(pointee_size);
+        ExprEnum::Var(_) | ExprEnum::Deref(_) | ExprEnum::Member(_, _) => {
+            let (mut result, ptr) = gen_lvalue_addr_tac(expr, code_env);
+            let pointee_type = expr.type_.clone().unwrap();
+            let temp_ident = get_new_temp_name_for_type(&pointee_type);
             let mut ident_to_return;
 
-            if let TacVal::Var(ident) = tac_val_1 {
-                result.push(TacInstr::Deref(temp_ident, ident));
+            result.push(TacInstr::Deref(temp_ident, ptr));
 
-                if should_return_old_val {
-                    if let ValTarget::Ident(ident) = target {
-                        ident_to_return = ident;
-                    } else {
-                        ident_to_return = get_new_temp_name(pointee_size);
-                    };
-                    result.push(TacInstr::Copy(ident_to_return, TacVal::Var(temp_ident)));
+            if should_return_old_val {
+                if let ValTarget::Ident(ident) = target {
+                    ident_to_return = ident;
                 } else {
-                    ident_to_return = temp_ident;
-                }
+                    ident_to_return = get_new_temp_name_for_type(&pointee_type);
+                };
+                result.push(TacInstr::Copy(ident_to_return, TacVal::Var(temp_ident)));
+            } else {
+                ident_to_return = temp_ident;
+            }
 
-                result.push(generate_update_code(temp_ident, pointee_type, binary_op));
-                result.push(TacInstr::DerefStore(ident, TacVal::Var(temp_ident)));
+            result.push(generate_update_code(temp_ident, pointee_type, binary_op));
+            result.push(TacInstr::DerefStore(ptr, TacVal::Var(temp_ident)));
 
-                if !should_return_old_val {
-                    if let ValTarget::Ident(ident) = target {
-                        result.push(TacInstr::Copy(ident, TacVal::Var(temp_ident)));
-                        ident_to_return = ident;
-                    }
+            if !should_return_old_val {
+                if let ValTarget::Ident(ident) = target {
+                    result.push(TacInstr::Copy(ident, TacVal::Var(temp_ident)));
+                    ident_to_return = ident;
                 }
-            } else {
-                unreachable!();
             }
 
             (result, TacVal::Var(ident_to_return))
@@ -116,6 +108,8 @@ fn generate_update_code(ident_to_update: Identifier, type_: VarType, binary_op:
     let change_amt = match type_ {
         VarType::Fund(_) => 1,
         VarType::Ptr(inner) | VarType::Arr(inner, _) => inner.num_bytes(),
+        // check_types only allows ++ and -- on numbers and pointers to complete types
+        VarType::Struct(_) | VarType::Void | VarType::Func(_, _) => unreachable!(),
     };
     TacInstr::BinOp(
         ident_to_update,
@@ -0,0 +1,526 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    parser::expr_parser::{BinOp, UnOp},
+    types::VarSize,
+};
+
+use super::{
+    cfg::{BlockId, Cfg, ENTRY_BLOCK},
+    expr::get_bigger_size,
+    tac_func::TacFunc,
+    tac_instr::TacInstr,
+    Identifier, TacVal,
+};
+
+/// what is known about the value of a temporary
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum LatticeVal {
+    Unknown,    // no assignment of the temporary has been found to be executed yet
+    Const(i64), // the temporary always has this value, as it is read into a 64 bit register
+    Varying,    // the temporary may have different values
+}
+
+impl LatticeVal {
+    fn meet(self, other: LatticeVal) -> LatticeVal {
+        match (self, other) {
+            (LatticeVal::Unknown, x) | (x, LatticeVal::Unknown) => x,
+            (LatticeVal::Const(a), LatticeVal::Const(b)) if a == b => LatticeVal::Const(a),
+            _ => LatticeVal::Varying,
+        }
+    }
+}
+
+/// Sparse conditional constant propagation, from "Constant Propagation with Conditional Branches" by Wegman
+/// and Zadeck, which must be run on a function in SSA form.
+/// Finds the temporaries which always have the same value, assuming that branches on constants
+/// are only ever taken one way. The instructions computing those temporaries are replaced with copies
+/// of the constant, and the branches on constants are replaced with unconditional jumps.
+/// The blocks which can no longer be reached are left for `eliminate_dead_code` to remove.
+pub fn propagate_constants(func: &mut TacFunc) {
+    let cfg = func.get_cfg_mut();
+    let mut sccp = Sccp {
+        values: HashMap::new(),
+        executable_edges: HashSet::new(),
+        executable_blocks: vec![false; cfg.blocks.len()],
+        edge_worklist: Vec::new(),
+        ssa_worklist: Vec::new(),
+    };
+
+    // the temporaries which are assigned exactly once start out unknown,
+    // the others (arguments, uninitialized and address-taken variables) are varying
+    let mut num_assignments: HashMap<Identifier, usize> = HashMap::new();
+    for instr in cfg.instrs() {
+        if let Some(ident) = instr.get_written_identifier() {
+            *num_assignments.entry(ident).or_default() += 1;
+        }
+    }
+    for instr in cfg.instrs() {
+        if let TacInstr::Ref(_, ident) = instr {
+            num_assignments.insert(*ident, 0);
+        }
+    }
+    for (ident, count) in num_assignments {
+        if count == 1 && !ident.is_float() {
+            sccp.values.insert(ident, LatticeVal::Unknown);
+        }
+    }
+
+    // the instructions which read each temporary, as a block and the index of the instruction in it
+    let mut uses: HashMap<Identifier, Vec<(BlockId, usize)>> = HashMap::new();
+    for (block_id, block) in cfg.blocks.iter().enumerate() {
+        for (index, instr) in block.instrs.iter().enumerate() {
+            for ident in instr.get_read_identifiers() {
+                uses.entry(ident).or_default().push((block_id, index));
+            }
+        }
+    }
+
+    // instructions are only evaluated again when one of their operands changes,
+    // or when a new edge into their block is found to be executable
+    sccp.executable_blocks[ENTRY_BLOCK] = true;
+    sccp.visit_block(cfg, ENTRY_BLOCK);
+    loop {
+        if let Some((pred, block)) = sccp.edge_worklist.pop() {
+            if !sccp.executable_edges.insert((pred, block)) {
+                continue;
+            }
+            if sccp.executable_blocks[block] {
+                // only the phis depend on which edges into the block are executable
+                let num_phis = cfg.blocks[block]
+                    .instrs
+                    .iter()
+                    .take_while(|instr| matches!(instr, TacInstr::Label(_) | TacInstr::Phi(_, _)))
+                    .count();
+                for index in 0..num_phis {
+                    sccp.visit_instr(cfg, block, index);
+                }
+            } else {
+                sccp.executable_blocks[block] = true;
+                sccp.visit_block(cfg, block);
+            }
+        } else if let Some(ident) = sccp.ssa_worklist.pop() {
+            for (block, index) in uses.get(&ident).into_iter().flatten() {
+                if sccp.executable_blocks[*block] {
+                    sccp.visit_instr(cfg, *block, *index);
+                }
+            }
+        } else {
+            break;
+        }
+    }
+
+    sccp.rewrite(cfg);
+}
+
+struct Sccp {
+    values: HashMap<Identifier, LatticeVal>,
+    executable_edges: HashSet<(BlockId, BlockId)>,
+    executable_blocks: Vec<bool>,
+    edge_worklist: Vec<(BlockId, BlockId)>, // the edges which were found to be executable, but not yet visited
+    ssa_worklist: Vec<Identifier>, // the temporaries whose values changed, but whose uses were not visited
+}
+
+impl Sccp {
+    /// evaluates all of the instructions of a block which was just found to be executable
+    fn visit_block(&mut self, cfg: &Cfg, block: BlockId) {
+        if cfg.blocks[block].instrs.is_empty() {
+            self.visit_block_end(cfg, block);
+        }
+        for index in 0..cfg.blocks[block].instrs.len() {
+            self.visit_instr(cfg, block, index);
+        }
+    }
+
+    /// evaluates an instruction with what is known so far, adding the temporary it writes to the SSA worklist
+    /// if its value changed, and the edges out of the block to the edge worklist if it ends the block
+    fn visit_instr(&mut self, cfg: &Cfg, block: BlockId, index: usize) {
+        let instrs = &cfg.blocks[block].instrs;
+        if index == instrs.len() - 1 {
+            self.visit_block_end(cfg, block);
+        }
+        let instr = &instrs[index];
+        let Some(dst) = instr.get_written_identifier() else {
+            return;
+        };
+        let Some(old_val) = self.values.get(&dst).copied() else {
+            return;
+        };
+        let new_val = match instr {
+            // only the values coming from blocks which can be executed are considered
+            TacInstr::Phi(_, args) => args
+                .iter()
+                .filter(|(pred, _)| self.executable_edges.contains(&(*pred, block)))
+                .map(|(_, val)| self.eval_copy(dst, val))
+                .fold(LatticeVal::Unknown, LatticeVal::meet),
+            _ => self.eval_instr(instr),
+        };
+        // the values only ever move from unknown to constant to varying, so that this terminates
+        let new_val = old_val.meet(new_val);
+        if new_val != old_val {
+            self.values.insert(dst, new_val);
+            self.ssa_worklist.push(dst);
+        }
+    }
+
+    fn visit_block_end(&mut self, cfg: &Cfg, block: BlockId) {
+        for succ in self.get_executable_succs(cfg, block) {
+            if !self.executable_edges.contains(&(block, succ)) {
+                self.edge_worklist.push((block, succ));
+            }
+        }
+    }
+
+    /// returns the successors which the end of the block can jump to, with what is known so far
+    fn get_executable_succs(&self, cfg: &Cfg, block: BlockId) -> Vec<BlockId> {
+        let (label, val, jumps_if_zero) = match cfg.blocks[block].instrs.last() {
+            Some(TacInstr::JmpZero(label, val)) => (label, val, true),
+            Some(TacInstr::JmpNotZero(label, val)) => (label, val, false),
+            _ => return cfg.succs[block].clone(),
+        };
+        match self.eval_condition(val) {
+            LatticeVal::Unknown => Vec::new(),
+            LatticeVal::Const(is_zero) => {
+                if (is_zero != 0) == jumps_if_zero {
+                    vec![get_label_block(cfg, label)]
+                } else {
+                    vec![block + 1]
+                }
+            }
+            LatticeVal::Varying => cfg.succs[block].clone(),
+        }
+    }
+
+    /// returns the value of an operand, along with the number of low bytes of the register it is
+    /// loaded into which hold the value. Literals smaller than a dword leave the rest of the register unchanged.
+    fn get_operand(&self, val: &TacVal) -> (LatticeVal, usize) {
+        match val {
+            TacVal::Lit(imm, VarSize::Dword) => (LatticeVal::Const(*imm as u32 as i64), 8),
+            TacVal::Lit(imm, size) => (LatticeVal::Const(*imm), size.num_bytes()),
+            TacVal::FloatLit(_, _) => (LatticeVal::Varying, 8),
+            TacVal::Var(ident) => (
+                self.values
+                    .get(ident)
+                    .copied()
+                    .unwrap_or(LatticeVal::Varying),
+                8,
+            ),
+        }
+    }
+
+    /// returns the value of an operand if at least `num_bytes` of it are known
+    fn get_operand_bytes(&self, val: &TacVal, num_bytes: usize) -> LatticeVal {
+        match self.get_operand(val) {
+            (value, valid_bytes) if valid_bytes >= num_bytes => value,
+            _ => LatticeVal::Varying,
+        }
+    }
+
+    /// returns Const(1) if the value tested by a conditional jump is zero, and Const(0) if it is not
+    fn eval_condition(&self, val: &TacVal) -> LatticeVal {
+        if val.is_float() {
+            return LatticeVal::Varying;
+        }
+        let size = val.get_size();
+        match self.get_operand_bytes(val, size.num_bytes()) {
+            LatticeVal::Const(value) => LatticeVal::Const((truncate(value, size) == 0) as i64),
+            other => other,
+        }
+    }
+
+    fn eval_copy(&self, dst: Identifier, val: &TacVal) -> LatticeVal {
+        if val.is_float() || dst.is_float() {
+            return LatticeVal::Varying;
+        }
+        match self.get_operand_bytes(val, dst.get_num_bytes()) {
+            LatticeVal::Const(value) => LatticeVal::Const(store(dst, value)),
+            other => other,
+        }
+    }
+
+    /// evaluates an instruction in the same way that the generated code does
+    fn eval_instr(&self, instr: &TacInstr) -> LatticeVal {
+        match instr {
+            TacInstr::Copy(dst, val) => self.eval_copy(*dst, val),
+            TacInstr::BinOp(dst, val1, val2, op)
+                if !val1.is_float() && !val2.is_float() && !dst.is_float() =>
+            {
+                self.eval_binop(*dst, val1, val2, *op)
+            }
+            TacInstr::UnOp(dst, val, op) if !val.is_float() && !dst.is_float() => {
+                self.eval_unop(*dst, val, *op)
+            }
+            _ => LatticeVal::Varying,
+        }
+    }
+
+    fn eval_binop(&self, dst: Identifier, val1: &TacVal, val2: &TacVal, op: BinOp) -> LatticeVal {
+        let bigger_size = get_bigger_size(Some(val1.get_size()), Some(val2.get_size())).unwrap();
+        let operation_size = match bigger_size {
+            VarSize::Quad => VarSize::Quad,
+            _ => VarSize::Dword,
+        };
+        let is_unsigned = val1.is_unsigned() || val2.is_unsigned();
+        let (bytes_1, bytes_2) = match op {
+            // the lowest bytes of the result only depend on the lowest bytes of the operands
+            BinOp::Plus
+            | BinOp::Minus
+            | BinOp::Multiply
+            | BinOp::BitwiseAnd
+            | BinOp::BitwiseOr
+            | BinOp::BitwiseXor => {
+                let num_bytes = operation_size.num_bytes().min(dst.get_num_bytes());
+                (num_bytes, num_bytes)
+            }
+            BinOp::ShiftLeft | BinOp::ShiftRight => match val1.get_size() {
+                VarSize::Quad => (8, 1),
+                _ => (4, 1),
+            },
+            BinOp::GreaterThan
+            | BinOp::GreaterThanEq
+            | BinOp::LessThan
+            | BinOp::LessThanEq
+            | BinOp::Equals
+            | BinOp::NotEquals => (bigger_size.num_bytes(), bigger_size.num_bytes()),
+            _ => (operation_size.num_bytes(), operation_size.num_bytes()),
+        };
+        let (a, b) = match (
+            self.get_operand_bytes(val1, bytes_1),
+            self.get_operand_bytes(val2, bytes_2),
+        ) {
+            (LatticeVal::Const(a), LatticeVal::Const(b)) => (a, b),
+            (LatticeVal::Varying, _) | (_, LatticeVal::Varying) => return LatticeVal::Varying,
+            _ => return LatticeVal::Unknown,
+        };
+
+        let is_quad = operation_size == VarSize::Quad;
+        // the operation is done at the type of its operands, and the result is then converted to the type of dst
+        let result = match op {
+            BinOp::Multiply => extend(a.wrapping_mul(b), operation_size, is_unsigned),
+            BinOp::BitwiseAnd => extend(a & b, operation_size, is_unsigned),
+            BinOp::BitwiseOr => extend(a | b, operation_size, is_unsigned),
+            BinOp::BitwiseXor => extend(a ^ b, operation_size, is_unsigned),
+            BinOp::Plus => extend(a.wrapping_add(b), operation_size, is_unsigned),
+            BinOp::Minus => extend(a.wrapping_sub(b), operation_size, is_unsigned),
+            BinOp::Divide | BinOp::Modulus => {
+                let is_divide = op == BinOp::Divide;
+                let result = match (is_quad, is_unsigned) {
+                    (true, true) => {
+                        let (a, b) = (a as u64, b as u64);
+                        (if is_divide {
+                            a.checked_div(b)
+                        } else {
+                            a.checked_rem(b)
+                        })
+                        .map(|r| r as i64)
+                    }
+                    (true, false) => {
+                        if is_divide {
+                            a.checked_div(b)
+                        } else {
+                            a.checked_rem(b)
+                        }
+                    }
+                    (false, true) => {
+                        let (a, b) = (a as u32, b as u32);
+                        (if is_divide {
+                            a.checked_div(b)
+                        } else {
+                            a.checked_rem(b)
+                        })
+                        .map(|r| r as i64)
+                    }
+                    (false, false) => {
+                        let (a, b) = (a as i32, b as i32);
+                        (if is_divide {
+                            a.checked_div(b)
+                        } else {
+                            a.checked_rem(b)
+                        })
+                        .map(|r| r as i64)
+                    }
+                };
+                // dividing by zero, or overflowing, raises an exception at runtime
+                match result {
+                    Some(result) => result,
+                    None => return LatticeVal::Varying,
+                }
+            }
+            BinOp::ShiftLeft | BinOp::ShiftRight => {
+                // only the left operand determines the size of a shift, and the count is masked to that size
+                let is_quad = val1.get_size() == VarSize::Quad;
+                let count = if is_quad { b & 63 } else { b & 31 } as u32;
+                let result = match (op, is_quad, val1.is_unsigned()) {
+                    (BinOp::ShiftLeft, _, _) => a.wrapping_shl(count),
+                    (_, true, true) => ((a as u64) >> count) as i64,
+                    (_, true, false) => a >> count,
+                    (_, false, true) => ((a as u32) >> count) as i64,
+                    (_, false, false) => ((a as i32) >> count) as i64,
+                };
+                let size = if is_quad {
+                    VarSize::Quad
+                } else {
+                    VarSize::Dword
+                };
+                extend(result, size, val1.is_unsigned())
+            }
+            BinOp::GreaterThan
+            | BinOp::GreaterThanEq
+            | BinOp::LessThan
+            | BinOp::LessThanEq
+            | BinOp::Equals
+            | BinOp::NotEquals => {
+                // the operands are compared at the size of the bigger one
+                let (a, b) = if is_unsigned {
+                    (
+                        truncate(a, bigger_size) as u64 as i128,
+                        truncate(b, bigger_size) as u64 as i128,
+                    )
+                } else {
+                    (
+                        sign_extend(a, bigger_size) as i128,
+                        sign_extend(b, bigger_size) as i128,
+                    )
+                };
+                let is_true = match op {
+                    BinOp::GreaterThan => a > b,
+                    BinOp::GreaterThanEq => a >= b,
+                    BinOp::LessThan => a < b,
+                    BinOp::LessThanEq => a <= b,
+                    BinOp::Equals => a == b,
+                    _ => a != b,
+                };
+                is_true as i64
+            }
+            BinOp::LogicalAnd | BinOp::LogicalOr | BinOp::Assign | BinOp::Comma => {
+                unreachable!()
+            }
+        };
+        LatticeVal::Const(store(dst, result))
+    }
+
+    fn eval_unop(&self, dst: Identifier, val: &TacVal, op: UnOp) -> LatticeVal {
+        let size = val.get_size();
+        let a = match self.get_operand_bytes(val, size.num_bytes()) {
+            LatticeVal::Const(a) => a,
+            other => return other,
+        };
+        let result = match op {
+            UnOp::Plus => unreachable!(), // unary plus generates no TAC instruction
+            UnOp::Negation => extend(a.wrapping_neg(), size, val.is_unsigned()),
+            UnOp::BitwiseComplement => extend(!a, size, val.is_unsigned()),
+            UnOp::Not => (truncate(a, size) == 0) as i64,
+        };
+        LatticeVal::Const(store(dst, result))
+    }
+
+    /// replaces the instructions computing constants with copies of the constants,
+    /// and the branches on constants with unconditional jumps or nothing
+    fn rewrite(&self, cfg: &mut Cfg) {
+        for (block_id, block) in cfg.blocks.iter_mut().enumerate() {
+            if !self.executable_blocks[block_id] {
+                continue;
+            }
+            let mut const_phis = Vec::new();
+            let mut new_instrs = Vec::new();
+            for instr in std::mem::take(&mut block.instrs) {
+                let const_val =
+                    instr
+                        .get_written_identifier()
+                        .and_then(|dst| match self.values.get(&dst) {
+                            Some(LatticeVal::Const(value)) => Some((dst, *value)),
+                            _ => None,
+                        });
+                match (instr, const_val) {
+                    // phis must stay at the start of the block, so the copies go after them
+                    (TacInstr::Phi(_, _), Some((dst, value))) => {
+                        const_phis.push(TacInstr::Copy(dst, TacVal::Lit(value, dst.get_size())))
+                    }
+                    (
+                        TacInstr::BinOp(..) | TacInstr::UnOp(..) | TacInstr::Copy(..),
+                        Some((dst, value)),
+                    ) => new_instrs.push(TacInstr::Copy(dst, TacVal::Lit(value, dst.get_size()))),
+                    (TacInstr::Phi(dst, args), None) => {
+                        // the values from blocks which are never executed are dropped
+                        let args = args
+                            .into_iter()
+                            .filter(|(pred, _)| self.executable_edges.contains(&(*pred, block_id)))
+                            .collect();
+                        new_instrs.push(TacInstr::Phi(dst, args));
+                    }
+                    (TacInstr::JmpZero(label, val), _) => {
+                        new_instrs.extend(self.resolve_branch(label, val, true))
+                    }
+                    (TacInstr::JmpNotZero(label, val), _) => {
+                        new_instrs.extend(self.resolve_branch(label, val, false))
+                    }
+                    (instr, _) => new_instrs.push(instr),
+                }
+            }
+            let phis_end = new_instrs
+                .iter()
+                .position(|instr| !matches!(instr, TacInstr::Label(_) | TacInstr::Phi(_, _)))
+                .unwrap_or(new_instrs.len());
+            new_instrs.splice(phis_end..phis_end, const_phis);
+            block.instrs = new_instrs;
+        }
+        cfg.compute_edges();
+    }
+
+    /// returns an unconditional jump if a conditional jump on a constant is always taken,
+    /// nothing if it is never taken, or the conditional jump otherwise
+    fn resolve_branch(&self, label: String, val: TacVal, jumps_if_zero: bool) -> Option<TacInstr> {
+        match self.eval_condition(&val) {
+            LatticeVal::Const(is_zero) if (is_zero != 0) == jumps_if_zero => {
+                Some(TacInstr::Jmp(label))
+            }
+            LatticeVal::Const(_) => None,
+            _ if jumps_if_zero => Some(TacInstr::JmpZero(label, val)),
+            _ => Some(TacInstr::JmpNotZero(label, val)),
+        }
+    }
+}
+
+/// returns the block starting with the label
+fn get_label_block(cfg: &Cfg, label: &str) -> BlockId {
+    cfg.blocks
+        .iter()
+        .position(|block| block.get_label().is_some_and(|l| l == label))
+        .unwrap()
+}
+
+/// returns the lowest bytes of the value, zero extended
+fn truncate(value: i64, size: VarSize) -> i64 {
+    match size {
+        VarSize::Byte => value as u8 as i64,
+        VarSize::Word => value as u16 as i64,
+        VarSize::Dword => value as u32 as i64,
+        VarSize::Quad => value,
+    }
+}
+
+/// returns the lowest bytes of the value, sign extended
+fn sign_extend(value: i64, size: VarSize) -> i64 {
+    match size {
+        VarSize::Byte => value as i8 as i64,
+        VarSize::Word => value as i16 as i64,
+        VarSize::Dword => value as i32 as i64,
+        VarSize::Quad => value,
+    }
+}
+
+/// returns the lowest bytes of the value, zero extended if they are unsigned and sign extended if they are signed.
+/// This converts the result of an operation of the given size to a bigger type, as C does.
+fn extend(value: i64, size: VarSize, is_unsigned: bool) -> i64 {
+    if is_unsigned {
+        truncate(value, size)
+    } else {
+        sign_extend(value, size)
+    }
+}
+
+/// returns the value that the temporary holds, as it would be read back into a register,
+/// after the value in a register is stored into it
+fn store(dst: Identifier, value: i64) -> i64 {
+    extend(value, dst.get_size(), dst.is_unsigned())
+}
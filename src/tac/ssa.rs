@@ -0,0 +1,390 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::{
+    cfg::{is_block_end, BlockId, Cfg, DomTree, ENTRY_BLOCK},
+    get_new_label_number, get_new_temp_like,
+    tac_func::{TacBody, TacFunc},
+    tac_instr::TacInstr,
+    Identifier, TacVal,
+};
+
+/// converts a function to SSA (static single assignment) form, where each temporary is assigned exactly once.
+/// Where different assignments of a temporary meet, such as after an if statement, a phi instruction
+/// chooses between them depending on which block was executed before.
+/// Temporaries whose address is taken are left alone, since they can also be assigned through pointers.
+pub fn construct_ssa(func: &mut TacFunc) {
+    let args: Vec<Identifier> = func.args.iter().map(|(ident, _)| *ident).collect();
+    let cfg = func.get_cfg_mut();
+    let dom_tree = cfg.dominators();
+    let address_taken = get_address_taken_identifiers(cfg);
+
+    let phi_vars = insert_phis(cfg, &dom_tree, &args, &address_taken);
+
+    let mut renamer = Renamer {
+        stacks: HashMap::new(),
+        address_taken,
+        phi_vars,
+    };
+    // the arguments keep their names, since the function's prologue stores the arguments in them
+    for arg in args {
+        renamer.stacks.entry(arg).or_default().push(arg);
+    }
+    renamer.rename_block(cfg, &dom_tree, ENTRY_BLOCK);
+}
+
+/// converts a function out of SSA form, by replacing the phi instructions at the start of each block
+/// with copies at the end of the blocks before it. When a block can jump to more than one block,
+/// the copies are placed in a new block on the edge between them, so that they only happen when that edge is taken.
+pub fn destruct_ssa(func: &mut TacFunc) {
+    let cfg = func.get_cfg_mut();
+
+    // the copies which replace the phis of the block at the end of each edge
+    let mut edge_copies: BTreeMap<(BlockId, BlockId), Vec<(Identifier, TacVal)>> = BTreeMap::new();
+    for (block_id, block) in cfg.blocks.iter_mut().enumerate() {
+        block.instrs.retain(|instr| match instr {
+            TacInstr::Phi(dst, args) => {
+                for (pred, val) in args {
+                    edge_copies
+                        .entry((*pred, block_id))
+                        .or_default()
+                        .push((*dst, val.clone()));
+                }
+                false
+            }
+            _ => true,
+        });
+    }
+
+    let num_blocks = cfg.blocks.len();
+    let mut copies_at_end: Vec<Vec<TacInstr>> = (0..num_blocks).map(|_| Vec::new()).collect();
+    let mut copies_after: Vec<Vec<TacInstr>> = (0..num_blocks).map(|_| Vec::new()).collect();
+    let mut edge_blocks = Vec::new();
+
+    for ((pred, succ), copies) in edge_copies {
+        let copies = sequentialize_copies(copies);
+        let succ_label = cfg.blocks[succ].get_label().cloned();
+        let pred_instrs = &mut cfg.blocks[pred].instrs;
+        if cfg.succs[pred].len() == 1 {
+            // a conditional jump to the next block always continues there, so the copies cannot change the condition
+            if let Some(TacInstr::JmpZero(label, _) | TacInstr::JmpNotZero(label, _)) =
+                pred_instrs.last()
+            {
+                let label = label.clone();
+                *pred_instrs.last_mut().unwrap() = TacInstr::Jmp(label);
+            }
+            copies_at_end[pred].extend(copies);
+        } else if succ == pred + 1
+            && matches!(
+                pred_instrs.last(),
+                Some(TacInstr::JmpZero(_, _) | TacInstr::JmpNotZero(_, _))
+            )
+        {
+            // the edge is taken by not jumping, so the copies are placed between the two blocks
+            copies_after[pred].extend(copies);
+        } else {
+            // the edge is taken by jumping, so the block has a label
+            let succ_label = succ_label.unwrap();
+            let edge_label = format!("ssa_edge_{}", get_new_label_number());
+            retarget_jump(pred_instrs.last_mut().unwrap(), &succ_label, &edge_label);
+            edge_blocks.push(TacInstr::Label(edge_label));
+            edge_blocks.extend(copies);
+            edge_blocks.push(TacInstr::Jmp(succ_label));
+        }
+    }
+
+    let mut instrs = Vec::new();
+    for ((block, end_copies), after_copies) in std::mem::take(&mut cfg.blocks)
+        .into_iter()
+        .zip(copies_at_end)
+        .zip(copies_after)
+    {
+        let mut block_instrs = block.instrs;
+        let terminator = match block_instrs.last() {
+            Some(instr) if is_block_end(instr) => block_instrs.pop(),
+            _ => None,
+        };
+        instrs.extend(block_instrs);
+        instrs.extend(end_copies);
+        instrs.extend(terminator);
+        instrs.extend(after_copies);
+    }
+    // the last block of a function always ends with a return or a call to exit, so it does not fall through to these
+    instrs.extend(edge_blocks);
+
+    *cfg = Cfg::new(instrs);
+}
+
+/// checks that the function is in SSA form, so that optimizations can rely on it:
+/// each temporary other than those whose address is taken is assigned at most once,
+/// each use of a temporary is dominated by its assignment,
+/// and the phi instructions are at the start of their blocks, with one value for each predecessor.
+/// Panics if the function is not in SSA form, since that is a bug in the compiler.
+pub fn verify_ssa(func: &TacFunc) {
+    let TacBody::Cfg(cfg) = &func.body else {
+        panic!("function {} is not split into basic blocks", func.name);
+    };
+    let fail = |msg: String| -> ! { panic!("invalid SSA form in function {}: {}", func.name, msg) };
+    let dom_tree = cfg.dominators();
+    let address_taken = get_address_taken_identifiers(cfg);
+    let rpo = cfg.reverse_postorder();
+
+    // where each temporary is assigned, as the block and the index of the instruction in it.
+    // The arguments are assigned before the first instruction.
+    let mut defs: HashMap<Identifier, (BlockId, Option<usize>)> = HashMap::new();
+    for (arg, _) in &func.args {
+        defs.insert(*arg, (ENTRY_BLOCK, None));
+    }
+    for block in &rpo {
+        for (index, instr) in cfg.blocks[*block].instrs.iter().enumerate() {
+            if let Some(ident) = instr.get_written_identifier() {
+                if address_taken.contains(&ident) {
+                    continue;
+                }
+                if defs.insert(ident, (*block, Some(index))).is_some() {
+                    fail(format!("{:?} is assigned more than once", ident));
+                }
+            }
+        }
+    }
+
+    // returns true if the value of the temporary is available before the instruction at the index in the block.
+    // Temporaries which are never assigned are uninitialized variables, which may be used anywhere
+    let is_available = |ident: &Identifier, block: BlockId, index: usize| match defs.get(ident) {
+        _ if address_taken.contains(ident) => true,
+        None => true,
+        Some((def_block, def_index)) if *def_block == block => {
+            def_index.map_or(true, |def_index| def_index < index)
+        }
+        Some((def_block, _)) => dom_tree.dominates(*def_block, block),
+    };
+
+    for block in &rpo {
+        let instrs = &cfg.blocks[*block].instrs;
+        let mut is_phi_allowed = true;
+        for (index, instr) in instrs.iter().enumerate() {
+            match instr {
+                TacInstr::Label(_) if index == 0 => {}
+                TacInstr::Phi(dst, args) => {
+                    if !is_phi_allowed {
+                        fail(format!(
+                            "the phi for {:?} is not at the start of block {}",
+                            dst, block
+                        ));
+                    }
+                    let mut arg_blocks: Vec<BlockId> = args.iter().map(|(pred, _)| *pred).collect();
+                    let mut reachable_preds: Vec<BlockId> = cfg.preds[*block]
+                        .iter()
+                        .filter(|pred| dom_tree.is_reachable(**pred))
+                        .copied()
+                        .collect();
+                    arg_blocks.sort();
+                    reachable_preds.sort();
+                    if arg_blocks != reachable_preds {
+                        fail(format!(
+                            "the phi for {:?} has values for blocks {:?}, but block {} has predecessors {:?}",
+                            dst, arg_blocks, block, reachable_preds
+                        ));
+                    }
+                    // the values are read at the end of the predecessors
+                    for (pred, val) in args {
+                        if let TacVal::Var(ident) = val {
+                            if !is_available(ident, *pred, cfg.blocks[*pred].instrs.len()) {
+                                fail(format!(
+                                    "{:?} is used at the end of block {} without being assigned first",
+                                    ident, pred
+                                ));
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    is_phi_allowed = false;
+                    for ident in instr.get_read_identifiers() {
+                        if !is_available(&ident, *block, index) {
+                            fail(format!(
+                                "{:?} is used in block {} without being assigned first",
+                                ident, block
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// returns the temporaries whose address is taken, which may be assigned through pointers
+fn get_address_taken_identifiers(cfg: &Cfg) -> HashSet<Identifier> {
+    cfg.instrs()
+        .filter_map(|instr| match instr {
+            TacInstr::Ref(_, ident) => Some(*ident),
+            _ => None,
+        })
+        .collect()
+}
+
+/// inserts phi instructions with no values yet at the start of the blocks where different assignments
+/// of a temporary meet, and returns the temporaries which each block has phis for, in order.
+/// Phis are only inserted for temporaries which are used in a different block than they are assigned in,
+/// since the others are never needed at the start of a block.
+fn insert_phis(
+    cfg: &mut Cfg,
+    dom_tree: &DomTree,
+    args: &[Identifier],
+    address_taken: &HashSet<Identifier>,
+) -> Vec<Vec<Identifier>> {
+    let mut non_local_vars = Vec::new();
+    let mut def_blocks: HashMap<Identifier, Vec<BlockId>> = HashMap::new();
+    for arg in args {
+        def_blocks.entry(*arg).or_default().push(ENTRY_BLOCK);
+    }
+    for block in cfg.reverse_postorder() {
+        let mut assigned_in_block = HashSet::new();
+        for instr in &cfg.blocks[block].instrs {
+            for ident in instr.get_read_identifiers() {
+                if !assigned_in_block.contains(&ident)
+                    && !address_taken.contains(&ident)
+                    && !non_local_vars.contains(&ident)
+                {
+                    non_local_vars.push(ident);
+                }
+            }
+            if let Some(ident) = instr.get_written_identifier() {
+                assigned_in_block.insert(ident);
+                def_blocks.entry(ident).or_default().push(block);
+            }
+        }
+    }
+
+    let frontiers = cfg.dominance_frontiers(dom_tree);
+    let mut phi_vars: Vec<Vec<Identifier>> = vec![Vec::new(); cfg.blocks.len()];
+    for var in non_local_vars {
+        // a phi is itself an assignment, which may need more phis where it meets other assignments
+        let mut worklist = def_blocks.remove(&var).unwrap_or_default();
+        while let Some(block) = worklist.pop() {
+            for frontier_block in &frontiers[block] {
+                if !phi_vars[*frontier_block].contains(&var) {
+                    phi_vars[*frontier_block].push(var);
+                    worklist.push(*frontier_block);
+                }
+            }
+        }
+    }
+
+    for (block, vars) in cfg.blocks.iter_mut().zip(&phi_vars) {
+        let start = get_phis_start(&block.instrs);
+        block.instrs.splice(
+            start..start,
+            vars.iter().map(|var| TacInstr::Phi(*var, Vec::new())),
+        );
+    }
+    phi_vars
+}
+
+/// returns the index where the phis of a block start, which is after its label
+fn get_phis_start(instrs: &[TacInstr]) -> usize {
+    match instrs.first() {
+        Some(TacInstr::Label(_)) => 1,
+        _ => 0,
+    }
+}
+
+struct Renamer {
+    // the names of the assignments of each temporary which are in scope, the innermost being the last
+    stacks: HashMap<Identifier, Vec<Identifier>>,
+    address_taken: HashSet<Identifier>,
+    // the temporaries which each block has phis for, in order
+    phi_vars: Vec<Vec<Identifier>>,
+}
+
+impl Renamer {
+    /// gives each assignment in the block a new name, and replaces each use with the name of the assignment which reaches it.
+    /// The blocks dominated by this one are renamed next, since the assignments in this block are in scope there.
+    fn rename_block(&mut self, cfg: &mut Cfg, dom_tree: &DomTree, block: BlockId) {
+        let mut assigned_vars = Vec::new();
+        for instr in &mut cfg.blocks[block].instrs {
+            if !matches!(instr, TacInstr::Phi(_, _)) {
+                for ident in instr.get_read_identifiers_mut() {
+                    *ident = self.get_current_name(*ident);
+                }
+            }
+            if let Some(ident) = instr.get_written_identifier_mut() {
+                if !self.address_taken.contains(ident) {
+                    let new_ident = get_new_temp_like(*ident);
+                    self.stacks.entry(*ident).or_default().push(new_ident);
+                    assigned_vars.push(*ident);
+                    *ident = new_ident;
+                }
+            }
+        }
+
+        for succ in cfg.succs[block].clone() {
+            let start = get_phis_start(&cfg.blocks[succ].instrs);
+            for (index, var) in self.phi_vars[succ].iter().enumerate() {
+                let val = TacVal::Var(self.get_current_name(*var));
+                match &mut cfg.blocks[succ].instrs[start + index] {
+                    TacInstr::Phi(_, args) => args.push((block, val)),
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        for child in dom_tree.children[block].clone() {
+            self.rename_block(cfg, dom_tree, child);
+        }
+
+        for var in assigned_vars {
+            self.stacks.get_mut(&var).unwrap().pop();
+        }
+    }
+
+    /// returns the name of the assignment of the temporary which is in scope.
+    /// If there is none, the temporary is an uninitialized variable, which keeps its name.
+    fn get_current_name(&self, ident: Identifier) -> Identifier {
+        self.stacks
+            .get(&ident)
+            .and_then(|names| names.last())
+            .copied()
+            .unwrap_or(ident)
+    }
+}
+
+/// the copies replacing the phis of a block happen at the same time, so when one copy would overwrite
+/// a value read by another, the value is copied into a new temporary before any of the copies are done
+fn sequentialize_copies(copies: Vec<(Identifier, TacVal)>) -> Vec<TacInstr> {
+    let is_overwritten = |val: &TacVal| matches!(val, TacVal::Var(ident) if copies.iter().any(|(dst, _)| dst == ident));
+
+    let mut result = Vec::new();
+    let mut final_copies = Vec::new();
+    for (dst, val) in &copies {
+        if matches!(val, TacVal::Var(ident) if ident == dst) {
+            continue;
+        }
+        if is_overwritten(val) {
+            let temp = get_new_temp_like(*dst);
+            result.push(TacInstr::Copy(temp, val.clone()));
+            final_copies.push(TacInstr::Copy(*dst, TacVal::Var(temp)));
+        } else {
+            final_copies.push(TacInstr::Copy(*dst, val.clone()));
+        }
+    }
+    result.extend(final_copies);
+    result
+}
+
+/// changes a jump to the label `from` so that it jumps to the label `to` instead
+fn retarget_jump(jump: &mut TacInstr, from: &str, to: &str) {
+    match jump {
+        TacInstr::Jmp(label) | TacInstr::JmpZero(label, _) | TacInstr::JmpNotZero(label, _) => {
+            if label == from {
+                *label = to.to_owned();
+            }
+        }
+        TacInstr::JmpTable(_, _, labels) => {
+            for label in labels.iter_mut().filter(|label| *label == from) {
+                *label = to.to_owned();
+            }
+        }
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    parser::expr_parser::{BinOp, Expr, ExprEnum},
+    types::{struct_defs::get_struct_member, VarSize, VarType},
+};
+
+use super::{
+    expr::{generate_expr_tac, ValTarget},
+    get_new_temp_name, get_new_temp_name_for_type, CodeEnv, Identifier, TacInstr, TacVal,
+};
+
+/// generates the value of a struct member access expression (`s.x` or `p->x`)
+pub fn gen_member_tac(
+    expr: &Expr,
+    code_env: &CodeEnv,
+    target: ValTarget,
+) -> (Vec<TacInstr>, TacVal) {
+    let (struct_expr, member_name) = match &expr.content {
+        ExprEnum::Member(struct_expr, member_name) => (struct_expr, member_name),
+        _ => unreachable!(),
+    };
+    if target == ValTarget::None {
+        return generate_expr_tac(struct_expr, code_env, ValTarget::None);
+    }
+
+    let member_type = expr.type_.clone().unwrap();
+    let (mut result, member_ptr) = gen_member_addr_tac(struct_expr, member_name, code_env);
+
+    let final_temp_name = if let ValTarget::Ident(ident) = target {
+        ident
+    } else {
+        get_new_temp_name_for_type(&member_type)
+    };
+
+    match member_type {
+        VarType::Arr(_, _) | VarType::Struct(_) => {
+            // just like variables, arrays and structs are represented by a pointer to their memory
+            result.push(TacInstr::Copy(final_temp_name, TacVal::Var(member_ptr)))
+        }
+        VarType::Fund(_) | VarType::Ptr(_) => {
+            result.push(TacInstr::Deref(final_temp_name, member_ptr))
+        }
+        // check_types rejects values of type void, and functions are only used through pointers
+        VarType::Void | VarType::Func(_, _) => unreachable!(),
+    }
+
+    (result, TacVal::Var(final_temp_name))
+}
+
+/// generates a pointer to a struct member, by adding the offset of the member
+/// to the pointer of the struct.
+pub fn gen_member_addr_tac(
+    struct_expr: &Expr,
+    member_name: &str,
+    code_env: &CodeEnv,
+) -> (Vec<TacInstr>, Identifier) {
+    let tag = match &struct_expr.type_ {
+        Some(VarType::Struct(tag)) => tag,
+        _ => unreachable!(), // check_types already verified that member access is done on a struct
+    };
+    let member = get_struct_member(tag, member_name).unwrap();
+
+    let (mut result, struct_ptr) = generate_expr_tac(struct_expr, code_env, ValTarget::Generate);
+    let member_ptr = get_new_temp_name(VarSize::Quad);
+    result.push(TacInstr::BinOp(
+        member_ptr,
+        struct_ptr,
+        TacVal::Lit(member.offset as i64, VarSize::Quad),
+        BinOp::Plus,
+    ));
+
+    (result, member_ptr)
+}
+
+/// copies `num_bytes` bytes from the memory pointed to by `src_ptr` into the memory pointed to by `dst_ptr`,
+/// using the largest loads and stores possible.
+pub fn gen_struct_copy_tac(
+    dst_ptr: Identifier,
+    src_ptr: Identifier,
+    num_bytes: usize,
+) -> Vec<TacInstr> {
+    let mut result = Vec::new();
+    let mut bytes_copied = 0;
+
+    while bytes_copied < num_bytes {
+        let size = match num_bytes - bytes_copied {
+            8.. => VarSize::Quad,
+            4..=7 => VarSize::Dword,
+            2..=3 => VarSize::Word,
+            _ => VarSize::Byte,
+        };
+
+        let src_addr = get_new_temp_name(VarSize::Quad);
+        let dst_addr = get_new_temp_name(VarSize::Quad);
+        let val = get_new_temp_name(size);
+        result.push(TacInstr::BinOp(
+            src_addr,
+            TacVal::Var(src_ptr),
+            TacVal::Lit(bytes_copied as i64, VarSize::Quad),
+            BinOp::Plus,
+        ));
+        result.push(TacInstr::Deref(val, src_addr));
+        result.push(TacInstr::BinOp(
+            dst_addr,
+            TacVal::Var(dst_ptr),
+            TacVal::Lit(bytes_copied as i64, VarSize::Quad),
+            BinOp::Plus,
+        ));
+        result.push(TacInstr::DerefStore(dst_addr, TacVal::Var(val)));
+
+        bytes_copied += size.num_bytes();
+    }
+
+    result
+}
@@ -0,0 +1,191 @@
+use crate::{
+    errors::{check_bin_op_exprs::get_common_type, display::err_display},
+    parser::{
+        const_eval::get_switch_labels,
+        expr_parser::{BinOp, Expr, ExprEnum},
+        Statement,
+    },
+    tokenizer::source_cursor::SourcePtr,
+    types::{FundT, VarSize, VarType},
+};
+
+use super::{
+    expr::{gen_conversion_tac, generate_expr_tac, ValTarget},
+    generate_statement_tac, get_new_label_number, get_new_temp_name_for_type, CodeEnv, TacInstr,
+    TacVal,
+};
+
+/// the labels of the switch statement currently being generated,
+/// which the `case` and `default` labels in its body are lowered to.
+pub struct SwitchLabels {
+    cases: Vec<(i64, String)>, // the value of each case label, and the label it jumps to
+    default: Option<String>,
+}
+
+pub fn gen_switch_tac(ctrl_expr: &Expr, body: &Statement, code_env: &mut CodeEnv) -> Vec<TacInstr> {
+    let label_num = get_new_label_number();
+    let label_switch_end = format!("end_switch_{}", label_num);
+    let table_label = format!("switch_table_{}", label_num);
+
+    let (case_exprs, default_positions) = get_switch_labels(body);
+    let cases: Vec<(i64, String)> = case_exprs
+        .iter()
+        .enumerate()
+        .map(|(index, expr)| match expr.content {
+            ExprEnum::Int(value) => (value, format!("switch_{}_case_{}", label_num, index)),
+            _ => unreachable!(), // const_eval verified that case labels are integer constants
+        })
+        .collect();
+    let default = (!default_positions.is_empty()).then(|| format!("switch_{}_default", label_num));
+    // if no case matches, control continues at the default label, or after the switch if there is none
+    let fallback_label = default.clone().unwrap_or(label_switch_end.clone());
+
+    // the controlling expression is promoted, and each case value is converted to the promoted type
+    let ctrl_type =
+        get_common_type(&ctrl_expr.type_, &ctrl_expr.type_).unwrap_or(VarType::Fund(FundT::Int));
+    let (mut result, ctrl_val) = generate_expr_tac(ctrl_expr, code_env, ValTarget::Generate);
+    let (instrs, ctrl_val) = gen_conversion_tac(ctrl_val, &ctrl_type);
+    result.extend(instrs);
+
+    let case_vals: Vec<(i128, String)> = cases
+        .iter()
+        .map(|(value, label)| {
+            let converted = match gen_conversion_tac(TacVal::Lit(*value, VarSize::Quad), &ctrl_type)
+            {
+                (_, TacVal::Lit(converted, _)) => converted,
+                _ => unreachable!(),
+            };
+            // compare values in 128 bits, so that the order of unsigned longs is preserved
+            if ctrl_type.is_unsigned() {
+                (converted as u64 as i128, label.clone())
+            } else {
+                (converted as i128, label.clone())
+            }
+        })
+        .collect();
+
+    if is_dense(&case_vals) {
+        result.extend(gen_jump_table_dispatch_tac(
+            ctrl_val,
+            &ctrl_type,
+            &case_vals,
+            table_label,
+            &fallback_label,
+        ));
+    } else {
+        for (value, label) in &case_vals {
+            let is_equal = get_new_temp_name_for_type(&VarType::Fund(FundT::Int));
+            result.push(TacInstr::BinOp(
+                is_equal,
+                ctrl_val.clone(),
+                TacVal::Lit(*value as i64, ctrl_val.get_size()),
+                BinOp::Equals,
+            ));
+            result.push(TacInstr::JmpNotZero(label.clone(), TacVal::Var(is_equal)));
+        }
+        result.push(TacInstr::Jmp(fallback_label));
+    }
+
+    // a break inside of the switch exits the switch, but continue still refers to the enclosing loop
+    let outer_loop_label_end = code_env.loop_label_end.clone();
+    let outer_switch_labels = code_env.switch_labels.take();
+    code_env.loop_label_end = Some(label_switch_end.clone());
+    code_env.switch_labels = Some(SwitchLabels { cases, default });
+
+    result.extend(generate_statement_tac(body, code_env));
+    result.push(TacInstr::Label(label_switch_end));
+
+    code_env.loop_label_end = outer_loop_label_end;
+    code_env.switch_labels = outer_switch_labels;
+
+    result
+}
+
+/// a jump table is used when there are enough cases, and the case values are close enough together
+/// that most entries of the table are used.
+fn is_dense(case_vals: &[(i128, String)]) -> bool {
+    if case_vals.len() < 4 {
+        return false;
+    }
+    let min = case_vals.iter().map(|(v, _)| *v).min().unwrap();
+    let max = case_vals.iter().map(|(v, _)| *v).max().unwrap();
+    let table_len = max - min + 1;
+    table_len <= 3 * case_vals.len() as i128
+}
+
+fn gen_jump_table_dispatch_tac(
+    ctrl_val: TacVal,
+    ctrl_type: &VarType,
+    case_vals: &[(i128, String)],
+    table_label: String,
+    fallback_label: &str,
+) -> Vec<TacInstr> {
+    let min = case_vals.iter().map(|(v, _)| *v).min().unwrap();
+    let max = case_vals.iter().map(|(v, _)| *v).max().unwrap();
+    let table_len = (max - min + 1) as usize;
+
+    // the table is indexed by the offset of the value from the smallest case, computed in 64 bits.
+    // Treating the offset as unsigned means values below the smallest case are also out of range.
+    let wide_type = if ctrl_type.is_unsigned() {
+        VarType::Fund(FundT::ULong)
+    } else {
+        VarType::Fund(FundT::Long)
+    };
+    let (mut result, wide_val) = gen_conversion_tac(ctrl_val, &wide_type);
+    let offset = get_new_temp_name_for_type(&VarType::Fund(FundT::ULong));
+    result.push(TacInstr::BinOp(
+        offset,
+        wide_val,
+        TacVal::Lit(min as i64, VarSize::Quad),
+        BinOp::Minus,
+    ));
+    let is_out_of_range = get_new_temp_name_for_type(&VarType::Fund(FundT::Int));
+    result.push(TacInstr::BinOp(
+        is_out_of_range,
+        TacVal::Var(offset),
+        TacVal::Lit(table_len as i64 - 1, VarSize::Quad),
+        BinOp::GreaterThan,
+    ));
+    result.push(TacInstr::JmpNotZero(
+        fallback_label.to_owned(),
+        TacVal::Var(is_out_of_range),
+    ));
+
+    // values in between the cases jump to the fallback label
+    let mut table = vec![fallback_label.to_owned(); table_len];
+    for (value, label) in case_vals.iter().rev() {
+        // iterating in reverse so that if two case values collide, the first case wins
+        table[(value - min) as usize] = label.clone();
+    }
+    result.push(TacInstr::JmpTable(table_label, TacVal::Var(offset), table));
+
+    result
+}
+
+/// a `case` label in the body of a switch statement becomes the label which the switch dispatch jumps to
+pub fn gen_case_label_tac(expr: &Expr, code_env: &CodeEnv) -> Vec<TacInstr> {
+    let switch_labels = match &code_env.switch_labels {
+        Some(switch_labels) => switch_labels,
+        None => err_display("case label not within a switch statement", expr.pos),
+    };
+    let value = match expr.content {
+        ExprEnum::Int(value) => value,
+        _ => unreachable!(), // const_eval verified that case labels are integer constants
+    };
+    let (_, label) = switch_labels
+        .cases
+        .iter()
+        .find(|(case_value, _)| *case_value == value)
+        .unwrap();
+    vec![TacInstr::Label(label.clone())]
+}
+
+pub fn gen_default_label_tac(pos: SourcePtr, code_env: &CodeEnv) -> Vec<TacInstr> {
+    match &code_env.switch_labels {
+        Some(SwitchLabels {
+            default: Some(label),
+            ..
+        }) => vec![TacInstr::Label(label.clone())],
+        _ => err_display("default label not within a switch statement", pos),
+    }
+}
@@ -1,10 +1,45 @@
 use crate::types::VarType;
 
-use super::{tac_instr::TacInstr, Identifier};
+use super::{cfg::Cfg, tac_instr::TacInstr, Identifier};
 
 #[derive(Debug)]
 pub struct TacFunc {
     pub name: String,
     pub args: Vec<(Identifier, VarType)>,
-    pub body: Vec<TacInstr>,
+    pub body: TacBody,
+}
+
+/// the instructions of a function, either in the order they were generated,
+/// or split into the basic blocks of a control flow graph
+#[derive(Debug)]
+pub enum TacBody {
+    Instrs(Vec<TacInstr>),
+    Cfg(Cfg),
+}
+
+impl TacFunc {
+    /// splits the body of the function into basic blocks, if it has not been already
+    pub fn build_cfg(&mut self) {
+        if let TacBody::Instrs(instrs) = &mut self.body {
+            self.body = TacBody::Cfg(Cfg::new(std::mem::take(instrs)));
+        }
+    }
+
+    /// returns the control flow graph of the function, splitting the body into basic blocks if needed
+    pub fn get_cfg_mut(&mut self) -> &mut Cfg {
+        self.build_cfg();
+        match &mut self.body {
+            TacBody::Cfg(cfg) => cfg,
+            TacBody::Instrs(_) => unreachable!(),
+        }
+    }
+
+    /// iterates over the instructions of the function in the order they are laid out,
+    /// so that code can be generated from either form of the body
+    pub fn instrs(&self) -> Box<dyn Iterator<Item = &TacInstr> + '_> {
+        match &self.body {
+            TacBody::Instrs(instrs) => Box::new(instrs.iter()),
+            TacBody::Cfg(cfg) => Box::new(cfg.instrs()),
+        }
+    }
 }
@@ -2,8 +2,9 @@ use std::fmt::{self};
 
 use crate::parser::expr_parser::{BinOp, UnOp};
 
-use super::{Identifier, TacVal};
+use super::{cfg::BlockId, Identifier, TacVal};
 
+#[derive(Clone)]
 pub enum TacInstr {
     Return(TacVal),
     MemChunk(Identifier, usize, Option<Vec<u8>>), // sets the identifier to a pointer pointing to a usize number of bytes
@@ -17,22 +18,15 @@ pub enum TacInstr {
     Jmp(String),
     JmpZero(String, TacVal),
     JmpNotZero(String, TacVal),
+    JmpTable(String, TacVal, Vec<String>), // jumps to the label at the index given by the value, in a table with the given name
     Call(String, Vec<TacVal>, Option<Identifier>),
-    StaticStrPtr(Identifier, String), // set identifier to a static string pointing to data specified by the string.
+    CallIndirect(TacVal, Vec<TacVal>, Option<Identifier>), // calls the function which the value points to
+    StaticStrPtr(Identifier, Vec<u8>), // set identifier to a static string pointing to data specified by the string.
+    GlobalVarPtr(Identifier, String), // set identifier to a pointer to the global variable with the given name
+    FunctionPtr(Identifier, String), // set identifier to a pointer to the function with the given name
+    Phi(Identifier, Vec<(BlockId, TacVal)>), // only in SSA form, sets the identifier to the value given for the block which jumped here
 }
 
-// TODO: implement basic blocks
-// pub enumTacBBInstr {
-
-// }
-
-// /// A `TacBasicBlock` represents a chain of instructions which will be executed in order,
-// /// uninterrupted by branches. The basic block allows optimizations to be performed.
-// /// A basic block always ends with a branch or a return.
-// pub struct TacBasicBlock {
-//     instrs: [TacBBInstr],
-// }
-
 impl TacInstr {
     pub fn get_written_identifier(&self) -> Option<Identifier> {
         let mut result = None;
@@ -43,16 +37,22 @@ impl TacInstr {
             | TacInstr::Deref(ident, _)
             | TacInstr::Ref(ident, _)
             | TacInstr::MemChunk(ident, _, _)
-            | TacInstr::StaticStrPtr(ident, _) => {
+            | TacInstr::StaticStrPtr(ident, _)
+            | TacInstr::GlobalVarPtr(ident, _)
+            | TacInstr::FunctionPtr(ident, _)
+            | TacInstr::Phi(ident, _) => {
                 result = Some(*ident);
             }
             TacInstr::Label(..)
             | TacInstr::Jmp(..)
             | TacInstr::JmpNotZero(..)
             | TacInstr::JmpZero(..)
+            | TacInstr::JmpTable(..)
             | TacInstr::Return(_)
             | TacInstr::DerefStore(_, _) => {}
-            TacInstr::Call(_, _, optional_ident) => result = *optional_ident,
+            TacInstr::Call(_, _, optional_ident) | TacInstr::CallIndirect(_, _, optional_ident) => {
+                result = *optional_ident
+            }
         }
         result
     }
@@ -72,8 +72,14 @@ impl TacInstr {
             | TacInstr::Copy(_, v)
             | TacInstr::JmpNotZero(_, v)
             | TacInstr::JmpZero(_, v)
-            | TacInstr::Return(v)
-            | TacInstr::DerefStore(_, v) => {
+            | TacInstr::JmpTable(_, v, _)
+            | TacInstr::Return(v) => {
+                if let TacVal::Var(ident) = v {
+                    result.push(*ident);
+                }
+            }
+            TacInstr::DerefStore(ptr, v) => {
+                result.push(*ptr);
                 if let TacVal::Var(ident) = v {
                     result.push(*ident);
                 }
@@ -83,7 +89,9 @@ impl TacInstr {
             | TacInstr::Jmp(..)
             | TacInstr::MemChunk(_, _, _)
             | TacInstr::Ref(_, _)
-            | TacInstr::StaticStrPtr(_, _) => {}
+            | TacInstr::StaticStrPtr(_, _)
+            | TacInstr::GlobalVarPtr(_, _)
+            | TacInstr::FunctionPtr(_, _) => {}
 
             TacInstr::Call(_, args, _) => {
                 for arg in args {
@@ -92,9 +100,89 @@ impl TacInstr {
                     }
                 }
             }
+            TacInstr::CallIndirect(callee, args, _) => {
+                for val in args.iter().chain([callee]) {
+                    if let TacVal::Var(ident) = val {
+                        result.push(*ident);
+                    }
+                }
+            }
             TacInstr::Deref(_, ident) => {
                 result.push(*ident);
             }
+            TacInstr::Phi(_, args) => {
+                for (_, val) in args {
+                    if let TacVal::Var(ident) = val {
+                        result.push(*ident);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// like `get_written_identifier`, but allows the identifier to be replaced
+    pub fn get_written_identifier_mut(&mut self) -> Option<&mut Identifier> {
+        match self {
+            TacInstr::BinOp(ident, _, _, _)
+            | TacInstr::UnOp(ident, _, _)
+            | TacInstr::Copy(ident, _)
+            | TacInstr::Deref(ident, _)
+            | TacInstr::Ref(ident, _)
+            | TacInstr::MemChunk(ident, _, _)
+            | TacInstr::StaticStrPtr(ident, _)
+            | TacInstr::GlobalVarPtr(ident, _)
+            | TacInstr::FunctionPtr(ident, _)
+            | TacInstr::Phi(ident, _) => Some(ident),
+            TacInstr::Call(_, _, optional_ident) | TacInstr::CallIndirect(_, _, optional_ident) => {
+                optional_ident.as_mut()
+            }
+            TacInstr::Label(..)
+            | TacInstr::Jmp(..)
+            | TacInstr::JmpNotZero(..)
+            | TacInstr::JmpZero(..)
+            | TacInstr::JmpTable(..)
+            | TacInstr::Return(_)
+            | TacInstr::DerefStore(_, _) => None,
+        }
+    }
+
+    /// like `get_read_identifiers`, but allows the identifiers to be replaced.
+    /// The values of phi instructions are not included, since they are read at the end of the previous block.
+    pub fn get_read_identifiers_mut(&mut self) -> Vec<&mut Identifier> {
+        let mut vals: Vec<&mut TacVal> = Vec::new();
+        let mut result = Vec::new();
+        match self {
+            TacInstr::BinOp(_, v1, v2, _) => vals = vec![v1, v2],
+            TacInstr::UnOp(_, v, _)
+            | TacInstr::Copy(_, v)
+            | TacInstr::JmpNotZero(_, v)
+            | TacInstr::JmpZero(_, v)
+            | TacInstr::JmpTable(_, v, _)
+            | TacInstr::Return(v) => vals = vec![v],
+            TacInstr::DerefStore(ptr, v) => {
+                result.push(ptr);
+                vals = vec![v];
+            }
+            TacInstr::Call(_, args, _) => vals = args.iter_mut().collect(),
+            TacInstr::CallIndirect(callee, args, _) => {
+                vals = args.iter_mut().collect();
+                vals.push(callee);
+            }
+            TacInstr::Deref(_, ident) => result.push(ident),
+            TacInstr::Label(..)
+            | TacInstr::Jmp(..)
+            | TacInstr::MemChunk(_, _, _)
+            | TacInstr::Ref(_, _)
+            | TacInstr::StaticStrPtr(_, _)
+            | TacInstr::GlobalVarPtr(_, _)
+            | TacInstr::FunctionPtr(_, _)
+            | TacInstr::Phi(_, _) => {}
+        }
+        for val in vals {
+            if let TacVal::Var(ident) = val {
+                result.push(ident);
+            }
         }
         result
     }
@@ -124,10 +212,17 @@ impl fmt::Debug for TacInstr {
             TacInstr::JmpNotZero(label, v) => {
                 write!(f, "jnz {} {:?}", label, v)
             }
+            TacInstr::JmpTable(table_name, v, labels) => {
+                write!(f, "jmp {}[{:?}] {:?}", table_name, v, labels)
+            }
             TacInstr::Call(name, args, optional_ident) => match optional_ident {
                 None => write!(f, "call {}({:?})", name, args),
                 Some(ident) => write!(f, "{:?} = call {}({:?})", ident, name, args),
             },
+            TacInstr::CallIndirect(callee, args, optional_ident) => match optional_ident {
+                None => write!(f, "call *{:?}({:?})", callee, args),
+                Some(ident) => write!(f, "{:?} = call *{:?}({:?})", ident, callee, args),
+            },
             TacInstr::Return(v) => {
                 write!(f, "return {:?}", v)
             }
@@ -144,7 +239,25 @@ impl fmt::Debug for TacInstr {
                 write!(f, "*{:?} = {:?}", ident, v)
             }
             TacInstr::StaticStrPtr(ident, data) => {
-                write!(f, "{:?} points to static string `{}`", ident, data)
+                write!(
+                    f,
+                    "{:?} points to static string `{}`",
+                    ident,
+                    data.escape_ascii()
+                )
+            }
+            TacInstr::GlobalVarPtr(ident, name) => {
+                write!(f, "{:?} = &{}", ident, name)
+            }
+            TacInstr::FunctionPtr(ident, name) => {
+                write!(f, "{:?} = &{}", ident, name)
+            }
+            TacInstr::Phi(ident, args) => {
+                let args: Vec<String> = args
+                    .iter()
+                    .map(|(block, val)| format!("block {}: {:?}", block, val))
+                    .collect();
+                write!(f, "{:?} = phi({})", ident, args.join(", "))
             }
         }
     }
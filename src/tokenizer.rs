@@ -1,11 +1,13 @@
+pub mod literals;
 pub mod operator;
 pub mod source_cursor;
 
 use self::source_cursor::{SourceCodeCursor, SourcePtr};
-use crate::errors::display::{err_display, err_display_no_source};
+use crate::errors::display::report_error;
 use crate::parser::expr_parser::{BinOp, BinOpPrecedenceLevel, UnOp};
 use crate::types::FundT;
-use operator::{char_to_operator, chars_to_operator, Op};
+use literals::{parse_int_literal, read_char_literal, read_string_literal};
+use operator::{char_to_operator, chars_to_operator, three_chars_to_operator, Op};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Token {
@@ -17,13 +19,22 @@ pub enum Token {
     CloseBracket,
     Star,
     Ampersand,
-    IntLit { val: String },
+    IntLit { val: i64, type_: FundT },
+    FloatLit { val: String },
     Identifier { val: String },
-    StringLiteral(String),
+    StringLiteral(Vec<u8>),
     Return,
     Type(FundT),
+    Signed,
+    Unsigned,
+    Void,
     Semicolon,
     Comma,
+    Dot,
+    Arrow,
+    Struct,
+    Enum,
+    Typedef,
 
     Op(Op),
     QuestionMark,
@@ -31,9 +42,14 @@ pub enum Token {
     If,
     Else,
     While,
+    Do,
     For,
+    Switch,
+    Case,
+    Default,
     Break,
     Continue,
+    Goto,
     Sizeof,
 }
 
@@ -51,6 +67,11 @@ impl Token {
                 Token::Op(Op::Plus) => Some(BinOp::Plus),
                 _ => None,
             },
+            BinOpPrecedenceLevel::Shift => match self {
+                Token::Op(Op::ShiftLeft) => Some(BinOp::ShiftLeft),
+                Token::Op(Op::ShiftRight) => Some(BinOp::ShiftRight),
+                _ => None,
+            },
             BinOpPrecedenceLevel::OrderingCmp => match self {
                 Token::Op(Op::LessThan) => Some(BinOp::LessThan),
                 Token::Op(Op::LessThanEq) => Some(BinOp::LessThanEq),
@@ -63,6 +84,18 @@ impl Token {
                 Token::Op(Op::DoubleEq) => Some(BinOp::Equals),
                 _ => None,
             },
+            BinOpPrecedenceLevel::BitwiseAnd => match self {
+                Token::Ampersand => Some(BinOp::BitwiseAnd),
+                _ => None,
+            },
+            BinOpPrecedenceLevel::BitwiseXor => match self {
+                Token::Op(Op::Caret) => Some(BinOp::BitwiseXor),
+                _ => None,
+            },
+            BinOpPrecedenceLevel::BitwiseOr => match self {
+                Token::Op(Op::Pipe) => Some(BinOp::BitwiseOr),
+                _ => None,
+            },
             BinOpPrecedenceLevel::LogicalAnd => match self {
                 Token::Op(Op::DoubleAnd) => Some(BinOp::LogicalAnd),
                 _ => None,
@@ -71,12 +104,22 @@ impl Token {
                 Token::Op(Op::DoublePipe) => Some(BinOp::LogicalOr),
                 _ => None,
             },
+            BinOpPrecedenceLevel::Comma => match self {
+                Token::Comma => Some(BinOp::Comma),
+                _ => None,
+            },
             BinOpPrecedenceLevel::Assignment => match self {
                 Token::Op(Op::AssignmentEquals) => Some(BinOp::Assign),
                 Token::Op(Op::PlusEquals) => Some(BinOp::Assign),
                 Token::Op(Op::MinusEquals) => Some(BinOp::Assign),
                 Token::Op(Op::ModEquals) => Some(BinOp::Assign),
+                Token::Op(Op::MulEquals) => Some(BinOp::Assign),
                 Token::Op(Op::DivEquals) => Some(BinOp::Assign),
+                Token::Op(Op::AndEquals) => Some(BinOp::Assign),
+                Token::Op(Op::OrEquals) => Some(BinOp::Assign),
+                Token::Op(Op::XorEquals) => Some(BinOp::Assign),
+                Token::Op(Op::ShiftLeftEquals) => Some(BinOp::Assign),
+                Token::Op(Op::ShiftRightEquals) => Some(BinOp::Assign),
                 _ => None,
             },
         }
@@ -84,6 +127,7 @@ impl Token {
 
     pub fn to_un_op(&self) -> Option<UnOp> {
         match self {
+            Token::Op(Op::Plus) => Some(UnOp::Plus),
             Token::Op(Op::Minus) => Some(UnOp::Negation),
             Token::Op(Op::BitwiseComplement) => Some(UnOp::BitwiseComplement),
             Token::Op(Op::Not) => Some(UnOp::Not),
@@ -100,6 +144,7 @@ pub fn get_tokens(source_code_contents: String) -> Vec<(Token, SourcePtr)> {
     while cursor.peek().is_some() {
         let next_char: char = *cursor.peek().unwrap();
         let next_next_char: char = *cursor.peek_nth(2).unwrap_or(&' ');
+        let next_next_next_char: char = *cursor.peek_nth(3).unwrap_or(&' ');
 
         if next_char == '/' && cursor.peek_nth(2) == Some(&'/') {
             // ignore single line comments
@@ -134,6 +179,22 @@ pub fn get_tokens(source_code_contents: String) -> Vec<(Token, SourcePtr)> {
         } else if next_char == ',' {
             cursor.next();
             tokens.push((Token::Comma, cursor.get_last_pos()));
+        } else if next_char == '.' && !next_next_char.is_ascii_digit() {
+            cursor.next();
+            tokens.push((Token::Dot, cursor.get_last_pos()));
+        } else if next_char == '-' && next_next_char == '>' {
+            cursor.next();
+            let pos = cursor.get_last_pos();
+            cursor.next();
+            tokens.push((Token::Arrow, pos));
+        } else if let Some(op) =
+            three_chars_to_operator((next_char, next_next_char, next_next_next_char))
+        {
+            cursor.next();
+            let pos = cursor.get_last_pos();
+            cursor.next();
+            cursor.next();
+            tokens.push((Token::Op(op), pos));
         } else if let Some(op) = chars_to_operator((next_char, next_next_char)) {
             // must consume 2 characters for an operator that is 2 characters long
             cursor.next();
@@ -156,17 +217,44 @@ pub fn get_tokens(source_code_contents: String) -> Vec<(Token, SourcePtr)> {
             // ignore all whitespace
             cursor.next();
         } else if next_char == '"' {
+            cursor.next(); // consume the opening double quote
             let pos = cursor.get_last_pos();
-            tokens.push((build_string_literal(&mut cursor), pos));
-        } else if next_char.is_ascii_digit() {
-            // handle an integer literal
+            let bytes = read_string_literal(&mut cursor, pos);
+
+            // adjacent string literals, such as `"ab" "cd"`, are concatenated into one
+            match tokens.last_mut() {
+                Some((Token::StringLiteral(prev_bytes), _)) => prev_bytes.extend(bytes),
+                _ => tokens.push((Token::StringLiteral(bytes), pos)),
+            }
+        } else if next_char.is_ascii_digit() || next_char == '.' {
+            // handle an integer or floating point literal, such as `12`, `1.5`, `.5f` or `1e-3`
             let mut val = String::new();
             let mut pos: SourcePtr = cursor.get_last_pos();
             pos.col += 1;
-            while cursor.peek().is_some() && (*cursor.peek().unwrap()).is_ascii_alphanumeric() {
+            let mut is_float = false;
+            while let Some(c) = cursor.peek().copied() {
+                if c == '.' {
+                    is_float = true;
+                } else if (c == 'e' || c == 'E') && !val.starts_with("0x") && !val.starts_with("0X")
+                {
+                    // an exponent, which may have a sign
+                    is_float = true;
+                    val.push(*cursor.next().unwrap());
+                    if matches!(cursor.peek(), Some('+') | Some('-')) {
+                        val.push(*cursor.next().unwrap());
+                    }
+                    continue;
+                } else if !c.is_ascii_alphanumeric() {
+                    break;
+                }
                 val.push(*cursor.next().unwrap());
             }
-            tokens.push((Token::IntLit { val }, pos));
+            if is_float {
+                tokens.push((Token::FloatLit { val }, pos));
+            } else {
+                let (val, type_) = parse_int_literal(&val, pos);
+                tokens.push((Token::IntLit { val, type_ }, pos));
+            }
         } else if next_char.is_ascii_alphabetic() {
             // handle an identifier or C keyword
             let mut val = String::new();
@@ -185,111 +273,48 @@ pub fn get_tokens(source_code_contents: String) -> Vec<(Token, SourcePtr)> {
                 "long" => tokens.push((Token::Type(FundT::Long), pos)),
                 "short" => tokens.push((Token::Type(FundT::Short), pos)),
                 "char" => tokens.push((Token::Type(FundT::Char), pos)),
+                "float" => tokens.push((Token::Type(FundT::Float), pos)),
+                "double" => tokens.push((Token::Type(FundT::Double), pos)),
+                "signed" => tokens.push((Token::Signed, pos)),
+                "unsigned" => tokens.push((Token::Unsigned, pos)),
+                "void" => tokens.push((Token::Void, pos)),
                 "if" => tokens.push((Token::If, pos)),
                 "else" => tokens.push((Token::Else, pos)),
                 "while" => tokens.push((Token::While, pos)),
+                "do" => tokens.push((Token::Do, pos)),
+                "switch" => tokens.push((Token::Switch, pos)),
+                "case" => tokens.push((Token::Case, pos)),
+                "default" => tokens.push((Token::Default, pos)),
                 "break" => tokens.push((Token::Break, pos)),
                 "continue" => tokens.push((Token::Continue, pos)),
+                "goto" => tokens.push((Token::Goto, pos)),
                 "for" => tokens.push((Token::For, pos)),
                 "sizeof" => tokens.push((Token::Sizeof, pos)),
+                "struct" => tokens.push((Token::Struct, pos)),
+                "enum" => tokens.push((Token::Enum, pos)),
+                "typedef" => tokens.push((Token::Typedef, pos)),
                 _ => tokens.push((Token::Identifier { val }, pos)),
             }
         } else if next_char == '\'' {
             cursor.next(); // consume the single quote char
             let pos = cursor.get_last_pos();
-
-            let mut val = String::new();
-            while cursor.peek().is_some()
-                && (*cursor.peek().unwrap()) != '\''
-                && (*cursor.peek().unwrap()) != '\n'
-            {
-                val.push(*cursor.next().unwrap());
-                if val.ends_with('\\') {
-                    val.push(*cursor.next().unwrap());
-                }
-            }
-            if cursor.next() != Some(&'\'') {
-                err_display("expected a closing `'` for character expression!", pos)
-            }
-
+            let val = read_char_literal(&mut cursor, pos);
             tokens.push((
                 Token::IntLit {
-                    val: convert_str_to_char_int(val, pos),
+                    val,
+                    type_: FundT::Int,
                 },
                 pos,
             ))
         } else {
-            println!("you messed up, unrecognized character {}", next_char);
-            std::process::exit(1);
+            // the character is skipped, so that the rest of the file is still checked for errors
+            cursor.next();
+            report_error(
+                format!("unrecognized character `{}`", next_char),
+                cursor.get_last_pos(),
+            );
         }
     }
 
     tokens
 }
-
-fn convert_str_to_char_int(val: String, pos: SourcePtr) -> String {
-    match val.len() {
-        1 => {
-            let res = val.chars().next().unwrap();
-            format!("{}", res as i32)
-        }
-        2 => {
-            if !val.starts_with('\\') {
-                err_display(
-                    "character literal must start with backslash or be 1 character",
-                    pos,
-                );
-            }
-
-            match val.chars().nth(1).unwrap() {
-                't' => "9".to_owned(),
-                'n' => "10".to_owned(),
-                '\\' => "92".to_owned(),
-                '0' => "0".to_owned(),
-                '\'' => "39".to_owned(),
-                _ => err_display(
-                    format!("unrecognized character escape sequence: '{}'", val),
-                    pos,
-                ),
-            }
-        }
-
-        _ => err_display(format!("invalid char literal: '{}'", val), pos),
-    }
-}
-
-fn build_string_literal(cursor: &mut SourceCodeCursor) -> Token {
-    cursor.next(); // consume the opening '"'
-    let mut res = String::new();
-
-    loop {
-        let char = cursor.next();
-        match char {
-            Some(c) => {
-                if *c == '"' {
-                    break;
-                }
-                if *c == '\\' {
-                    match cursor.next() {
-                        Some('t') => res.push('\t'),
-                        Some('n') => res.push('\n'),
-                        Some('\\') => res.push('\\'),
-                        Some('0') => res.push('\0'),
-                        Some('\'') => res.push('\''),
-                        val => err_display_no_source(format!(
-                            "unrecognized character escape sequence: '{:?}'",
-                            val
-                        )),
-                    }
-                } else {
-                    res.push(*c);
-                }
-            }
-            None => {
-                panic!()
-            }
-        }
-    }
-
-    Token::StringLiteral(res)
-}
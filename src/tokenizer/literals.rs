@@ -0,0 +1,170 @@
+use crate::{errors::display::err_display, types::FundT};
+
+use super::source_cursor::{SourceCodeCursor, SourcePtr};
+
+/// parses an integer literal such as `42`, `0x2A`, `052`, `0b101010` or `42ul`, returning its value
+/// and its type. Like C, the type is the first of a list of types which can represent the value,
+/// where the list depends on the suffix and on whether the literal is decimal.
+pub fn parse_int_literal(val: &str, pos: SourcePtr) -> (i64, FundT) {
+    let suffix_start = val.find(['u', 'U', 'l', 'L']).unwrap_or(val.len());
+    let (digits, suffix) = val.split_at(suffix_start);
+
+    let (digits, radix) = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        (hex, 16)
+    } else if let Some(binary) = digits
+        .strip_prefix("0b")
+        .or_else(|| digits.strip_prefix("0B"))
+    {
+        (binary, 2)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (&digits[1..], 8)
+    } else {
+        (digits, 10)
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        err_display(format!("invalid integer literal {}", val), pos);
+    }
+    let value = match u64::from_str_radix(digits, radix) {
+        Ok(value) => value,
+        Err(_) => err_display(
+            format!("integer literal {} is too large for any integer type", val),
+            pos,
+        ),
+    };
+
+    let (is_unsigned, is_long) = match suffix {
+        "" => (false, false),
+        "u" | "U" => (true, false),
+        "l" | "L" | "ll" | "LL" => (false, true),
+        "ul" | "uL" | "Ul" | "UL" | "lu" | "lU" | "Lu" | "LU" | "ull" | "uLL" | "Ull" | "ULL"
+        | "llu" | "llU" | "LLu" | "LLU" => (true, true),
+        _ => err_display(
+            format!("invalid suffix {} on integer literal {}", suffix, val),
+            pos,
+        ),
+    };
+
+    // decimal literals without a `u` suffix are never given an unsigned type
+    let candidates: &[FundT] = match (is_unsigned, is_long, radix == 10) {
+        (false, false, true) => &[FundT::Int, FundT::Long],
+        (false, false, false) => &[FundT::Int, FundT::UInt, FundT::Long, FundT::ULong],
+        (false, true, true) => &[FundT::Long],
+        (false, true, false) => &[FundT::Long, FundT::ULong],
+        (true, false, _) => &[FundT::UInt, FundT::ULong],
+        (true, true, _) => &[FundT::ULong],
+    };
+    let fund_t = candidates
+        .iter()
+        .copied()
+        .find(|fund_t| value <= get_max_value(*fund_t))
+        .unwrap_or_else(|| {
+            err_display(
+                format!("integer literal {} is too large for type long", val),
+                pos,
+            )
+        });
+
+    (value as i64, fund_t)
+}
+
+fn get_max_value(fund_t: FundT) -> u64 {
+    match fund_t {
+        FundT::Int => i32::MAX as u64,
+        FundT::UInt => u32::MAX as u64,
+        FundT::Long => i64::MAX as u64,
+        FundT::ULong => u64::MAX,
+        _ => unreachable!(), // integer literals are at least as big as an int
+    }
+}
+
+/// reads the rest of an escape sequence after the backslash, returning the byte it stands for.
+/// Octal escapes have up to 3 digits, while hexadecimal escapes continue until the first non hex digit.
+pub fn read_escape_sequence(cursor: &mut SourceCodeCursor, pos: SourcePtr) -> u8 {
+    let escaped = match cursor.next() {
+        Some(c) => *c,
+        None => err_display("unterminated escape sequence", pos),
+    };
+    match escaped {
+        'a' => 0x07,
+        'b' => 0x08,
+        'f' => 0x0c,
+        'n' => b'\n',
+        'r' => b'\r',
+        't' => b'\t',
+        'v' => 0x0b,
+        '\\' | '\'' | '"' | '?' => escaped as u8,
+        '0'..='7' => {
+            let mut value = escaped.to_digit(8).unwrap();
+            for _ in 0..2 {
+                match cursor.peek().and_then(|c| c.to_digit(8)) {
+                    Some(digit) => {
+                        value = value * 8 + digit;
+                        cursor.next();
+                    }
+                    None => break,
+                }
+            }
+            if value > 0xff {
+                err_display(
+                    format!("octal escape sequence \\{:o} is out of range", value),
+                    pos,
+                );
+            }
+            value as u8
+        }
+        'x' => {
+            let mut value: u32 = 0;
+            let mut num_digits = 0;
+            while let Some(digit) = cursor.peek().and_then(|c| c.to_digit(16)) {
+                value = value.saturating_mul(16).saturating_add(digit);
+                num_digits += 1;
+                cursor.next();
+            }
+            if num_digits == 0 {
+                err_display("\\x used with no following hex digits", pos);
+            }
+            if value > 0xff {
+                err_display("hex escape sequence is out of range", pos);
+            }
+            value as u8
+        }
+        _ => err_display(
+            format!("unrecognized character escape sequence: '\\{}'", escaped),
+            pos,
+        ),
+    }
+}
+
+/// reads a character literal such as `'a'` or `'\n'`, after the opening quote.
+/// Like gcc, the value is the character converted to a (signed) char, and then to an int.
+pub fn read_char_literal(cursor: &mut SourceCodeCursor, pos: SourcePtr) -> i64 {
+    let value = match cursor.next() {
+        Some('\\') => read_escape_sequence(cursor, pos) as i8 as i64,
+        Some('\'') => err_display("empty character literal", pos),
+        Some('\n') | None => err_display("expected a closing `'` for character expression!", pos),
+        Some(c) => *c as i64,
+    };
+    if cursor.next() != Some(&'\'') {
+        err_display("expected a closing `'` for character expression!", pos)
+    }
+    value
+}
+
+/// reads the bytes of a string literal, after the opening quote.
+/// Characters which are not escaped are stored as UTF-8.
+pub fn read_string_literal(cursor: &mut SourceCodeCursor, pos: SourcePtr) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        match cursor.next() {
+            Some('"') => break,
+            Some('\\') => bytes.push(read_escape_sequence(cursor, pos)),
+            Some('\n') | None => err_display("missing terminating `\"` character", pos),
+            Some(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+    bytes
+}
@@ -22,6 +22,15 @@ pub enum Op {
     ModEquals,
     PlusPlus,
     MinusMinus,
+    Pipe,
+    Caret,
+    ShiftLeft,
+    ShiftRight,
+    AndEquals,
+    OrEquals,
+    XorEquals,
+    ShiftLeftEquals,
+    ShiftRightEquals,
 }
 
 pub fn char_to_operator(c: char) -> Option<Op> {
@@ -34,6 +43,8 @@ pub fn char_to_operator(c: char) -> Option<Op> {
         '%' => Some(Op::Percent),
         '<' => Some(Op::LessThan),
         '>' => Some(Op::GreaterThan),
+        '|' => Some(Op::Pipe),
+        '^' => Some(Op::Caret),
         _ => None,
     }
 }
@@ -53,6 +64,19 @@ pub fn chars_to_operator(chars: (char, char)) -> Option<Op> {
         ('%', '=') => Some(Op::ModEquals),
         ('+', '+') => Some(Op::PlusPlus),
         ('-', '-') => Some(Op::MinusMinus),
+        ('<', '<') => Some(Op::ShiftLeft),
+        ('>', '>') => Some(Op::ShiftRight),
+        ('&', '=') => Some(Op::AndEquals),
+        ('|', '=') => Some(Op::OrEquals),
+        ('^', '=') => Some(Op::XorEquals),
+        _ => None,
+    }
+}
+
+pub fn three_chars_to_operator(chars: (char, char, char)) -> Option<Op> {
+    match chars {
+        ('<', '<', '=') => Some(Op::ShiftLeftEquals),
+        ('>', '>', '=') => Some(Op::ShiftRightEquals),
         _ => None,
     }
 }
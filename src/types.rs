@@ -1,10 +1,17 @@
+pub mod struct_defs;
+
 use std::fmt;
 
+use self::struct_defs::get_struct_layout;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum VarType {
     Fund(FundT),
     Ptr(Box<VarType>),
-    Arr(Box<VarType>, usize),
+    Arr(Box<VarType>, usize), // a length of 0 means the length is unknown, as in `int a[]`
+    Struct(String),           // the tag of the struct, the members are stored in struct_defs
+    Void,                     // only used as a function return type, or behind a pointer
+    Func(Box<VarType>, Vec<VarType>), // the return type and parameter types, only used behind a pointer
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -14,6 +21,12 @@ pub enum FundT {
     Short,
     Int,
     Long,
+    UChar,
+    UShort,
+    UInt,
+    ULong,
+    Float,
+    Double,
 }
 
 impl fmt::Display for VarType {
@@ -21,7 +34,22 @@ impl fmt::Display for VarType {
         match self {
             VarType::Fund(inner) => write!(f, "{}", inner),
             VarType::Ptr(inner) => write!(f, "ptr to {}", inner),
+            VarType::Arr(inner, 0) => write!(f, "array of unknown length of {}s", inner),
             VarType::Arr(inner, len) => write!(f, "array of {} {}s", len, inner),
+            VarType::Struct(tag) => {
+                // structs hiding another struct with the same tag have a unique suffix after a `#`
+                write!(f, "struct {}", tag.split('#').next().unwrap())
+            }
+            VarType::Void => write!(f, "void"),
+            VarType::Func(return_type, param_types) => {
+                let params: Vec<String> = param_types.iter().map(|t| t.to_string()).collect();
+                write!(
+                    f,
+                    "function ({}) returning {}",
+                    params.join(", "),
+                    return_type
+                )
+            }
         }
     }
 }
@@ -39,6 +67,12 @@ impl fmt::Display for FundT {
             FundT::Short => write!(f, "short"),
             FundT::Int => write!(f, "int"),
             FundT::Long => write!(f, "long"),
+            FundT::UChar => write!(f, "unsigned char"),
+            FundT::UShort => write!(f, "unsigned short"),
+            FundT::UInt => write!(f, "unsigned int"),
+            FundT::ULong => write!(f, "unsigned long"),
+            FundT::Float => write!(f, "float"),
+            FundT::Double => write!(f, "double"),
         }
     }
 }
@@ -49,26 +83,89 @@ impl VarType {
             VarType::Fund(inner) => inner.to_size().num_bytes(),
             VarType::Ptr(_) => 8,
             VarType::Arr(inner, len) => len * inner.num_bytes(),
+            VarType::Struct(tag) => get_struct_layout(tag).unwrap().size,
+            // void and functions are incomplete types, so they have no size
+            VarType::Void | VarType::Func(_, _) => unreachable!(),
         }
     }
 
-    pub fn to_size(&self) -> Option<VarSize> {
-        // returns None if this variable cannot be stored in a single register (arrays cannot be stored in a single register)
+    pub fn alignment(&self) -> usize {
         match self {
-            VarType::Fund(inner) => Some(inner.to_size()),
-            VarType::Ptr(_) => Some(VarSize::Quad),
-            VarType::Arr(_, _) => None,
+            VarType::Fund(_) | VarType::Ptr(_) => self.num_bytes(),
+            VarType::Arr(inner, _) => inner.alignment(),
+            VarType::Struct(tag) => get_struct_layout(tag).unwrap().alignment,
+            VarType::Void | VarType::Func(_, _) => unreachable!(),
         }
     }
+
+    /// returns false if the size of this type is not known, which happens for `void`, functions,
+    /// arrays of unknown length, and structs that are declared but not (yet) defined.
+    pub fn is_complete(&self) -> bool {
+        match self {
+            VarType::Fund(_) | VarType::Ptr(_) => true,
+            VarType::Arr(inner, len) => *len > 0 && inner.is_complete(),
+            VarType::Struct(tag) => get_struct_layout(tag).is_some(),
+            VarType::Void | VarType::Func(_, _) => false,
+        }
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        match self {
+            VarType::Fund(inner) => inner.is_unsigned(),
+            VarType::Ptr(_)
+            | VarType::Arr(_, _)
+            | VarType::Struct(_)
+            | VarType::Void
+            | VarType::Func(_, _) => false,
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, VarType::Fund(inner) if inner.is_float())
+    }
+
+    /// returns true for `void *`, the generic pointer type
+    pub fn is_void_ptr(&self) -> bool {
+        matches!(self, VarType::Ptr(inner) if **inner == VarType::Void)
+    }
+
+    /// returns true for pointers to functions, such as `int (*)(int, int)`
+    pub fn is_func_ptr(&self) -> bool {
+        matches!(self, VarType::Ptr(inner) if matches!(**inner, VarType::Func(_, _)))
+    }
 }
 
 impl FundT {
     pub fn to_size(self) -> VarSize {
         match self {
-            FundT::Char => VarSize::Byte,
-            FundT::Short => VarSize::Word,
-            FundT::Int => VarSize::Dword,
-            FundT::Long => VarSize::Quad,
+            FundT::Char | FundT::UChar => VarSize::Byte,
+            FundT::Short | FundT::UShort => VarSize::Word,
+            FundT::Int | FundT::UInt | FundT::Float => VarSize::Dword,
+            FundT::Long | FundT::ULong | FundT::Double => VarSize::Quad,
+        }
+    }
+
+    pub fn is_unsigned(self) -> bool {
+        match self {
+            FundT::Char | FundT::Short | FundT::Int | FundT::Long => false,
+            FundT::UChar | FundT::UShort | FundT::UInt | FundT::ULong => true,
+            FundT::Float | FundT::Double => false,
+        }
+    }
+
+    /// returns true for the floating point types `float` and `double`
+    pub fn is_float(self) -> bool {
+        matches!(self, FundT::Float | FundT::Double)
+    }
+
+    /// returns the unsigned type with the same size, for example `unsigned int` for `int`
+    pub fn to_unsigned(self) -> FundT {
+        match self {
+            FundT::Char | FundT::UChar => FundT::UChar,
+            FundT::Short | FundT::UShort => FundT::UShort,
+            FundT::Int | FundT::UInt => FundT::UInt,
+            FundT::Long | FundT::ULong => FundT::ULong,
+            FundT::Float | FundT::Double => unreachable!(), // the parser rejects `unsigned float`
         }
     }
 }
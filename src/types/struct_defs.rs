@@ -0,0 +1,126 @@
+use std::ptr::addr_of_mut;
+
+use super::VarType;
+
+#[derive(Debug, Clone)]
+pub struct StructMember {
+    pub name: String,
+    pub type_: VarType,
+    pub offset: usize, // offset in bytes from the start of the struct
+}
+
+#[derive(Debug, Clone)]
+pub struct StructLayout {
+    pub members: Vec<StructMember>,
+    pub size: usize,
+    pub alignment: usize,
+}
+
+// maps each struct tag to its layout, or None if the struct has only been declared so far.
+static mut STRUCT_DEFS: Vec<(String, Option<StructLayout>)> = Vec::new();
+static mut ANONYMOUS_STRUCT_NUMBER: usize = 0;
+
+/// Registers a struct tag without defining its members (eg. `struct node *next;`
+/// inside of the definition of `struct node`). Does nothing if the tag is already known.
+pub fn declare_struct(tag: &str) {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let struct_defs = &mut *addr_of_mut!(STRUCT_DEFS);
+        if !struct_defs.iter().any(|(name, _)| name == tag) {
+            struct_defs.push((tag.to_owned(), None));
+        }
+    }
+}
+
+/// Defines the members of a struct, computing the offset of each member the same way
+/// gcc does: each member is placed at the next offset that is a multiple of its alignment,
+/// and the size of the struct is padded to a multiple of the largest member alignment.
+pub fn define_struct(tag: &str, members: Vec<(String, VarType)>) {
+    let mut struct_members = Vec::new();
+    let mut offset = 0;
+    let mut alignment = 1;
+
+    for (name, type_) in members {
+        let member_alignment = type_.alignment();
+        offset = round_up(offset, member_alignment);
+        alignment = alignment.max(member_alignment);
+        let num_bytes = type_.num_bytes();
+        struct_members.push(StructMember {
+            name,
+            type_,
+            offset,
+        });
+        offset += num_bytes;
+    }
+
+    let layout = StructLayout {
+        members: struct_members,
+        size: round_up(offset, alignment),
+        alignment,
+    };
+
+    declare_struct(tag);
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let struct_defs = &mut *addr_of_mut!(STRUCT_DEFS);
+        for (name, optional_layout) in struct_defs.iter_mut() {
+            if name == tag {
+                *optional_layout = Some(layout);
+                return;
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// returns None if the struct has not been defined (it may still have been declared)
+pub fn get_struct_layout(tag: &str) -> Option<StructLayout> {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let struct_defs = &*addr_of_mut!(STRUCT_DEFS);
+        for (name, optional_layout) in struct_defs {
+            if name == tag {
+                return optional_layout.clone();
+            }
+        }
+    }
+    None
+}
+
+pub fn get_struct_member(tag: &str, member_name: &str) -> Option<StructMember> {
+    get_struct_layout(tag)?
+        .members
+        .into_iter()
+        .find(|member| member.name == member_name)
+}
+
+/// returns the tag itself if no struct has been registered with it, otherwise a new tag made from it,
+/// for a struct in an inner scope which hides another struct with the same tag.
+/// The part of the new tag after the `#` is not shown in messages.
+pub fn get_unique_struct_tag(tag: &str) -> String {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        let struct_defs = &*addr_of_mut!(STRUCT_DEFS);
+        let num_with_tag = struct_defs
+            .iter()
+            .filter(|(name, _)| name.split('#').next() == Some(tag))
+            .count();
+        match num_with_tag {
+            0 => tag.to_owned(),
+            n => format!("{}#{}", tag, n),
+        }
+    }
+}
+
+/// generates a tag for a struct declared without one, such as `struct { int x; } point;`
+pub fn get_new_anonymous_struct_tag() -> String {
+    unsafe {
+        // SAFETY: no race conditions in this single threaded program.
+        ANONYMOUS_STRUCT_NUMBER += 1;
+        format!("<anonymous {}>", ANONYMOUS_STRUCT_NUMBER - 1)
+    }
+}
+
+fn round_up(offset: usize, alignment: usize) -> usize {
+    (offset + alignment - 1) / alignment * alignment
+}
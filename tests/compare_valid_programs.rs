@@ -42,6 +42,7 @@ fn test_programs(dir: PathBuf) {
         // compile source code with tcc
         let tcc_exit_status = Command::new(TCC_DIR)
             .arg(input_file_dir)
+            .args(get_tcc_flags(input_file_dir))
             .status()
             .unwrap_or_else(|_| panic!("tcc could not compile {}", input_file_dir));
         if !tcc_exit_status.success() {
@@ -74,3 +75,18 @@ fn test_programs(dir: PathBuf) {
             .expect("could not remove generated artifacts");
     }
 }
+
+/// returns the extra flags to compile a test program with, which are given by its first line, as in
+/// `// tcc flags: -Werror`
+fn get_tcc_flags(input_file_dir: &str) -> Vec<String> {
+    let contents = fs::read_to_string(input_file_dir).unwrap();
+    match contents
+        .lines()
+        .next()
+        .unwrap_or("")
+        .strip_prefix("// tcc flags:")
+    {
+        Some(flags) => flags.split_whitespace().map(String::from).collect(),
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,12 @@
+#ifndef PP_HEADER_H
+#define PP_HEADER_H
+
+#define HEADER_VALUE 12
+
+int header_func(int x) {
+    return x + HEADER_VALUE;
+}
+
+#include "pp_nested.h"
+
+#endif
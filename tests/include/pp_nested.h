@@ -0,0 +1 @@
+int nested_line = __LINE__;
@@ -0,0 +1,14 @@
+struct person {
+    char name[8];
+    int age;
+};
+
+int main() {
+    char names[][6] = {"ann", "bob", "carol"};
+    struct person people[] = {{"dave", 30}, {"eve", 25}};
+    for (int i = 0; i < 3; i++) {
+        puts(names[i]);
+    }
+    puts(people[1].name);
+    return sizeof(names) + sizeof(people) + people[0].age;
+}
@@ -0,0 +1,26 @@
+char global_greeting[] = "global";
+char global_padded[10] = "pad";
+
+int main() {
+    char s[] = "hello";
+    char exact[5] = "world";
+    char padded[8] = "hi";
+    unsigned char bytes[] = "\t\n";
+
+    puts(s);
+    puts(global_greeting);
+    for (int i = 0; i < 5; i++) {
+        putchar(exact[i]);
+    }
+    putchar('\n');
+
+    s[0] = 'j';
+    puts(s);
+    int zeros = 0;
+    for (int i = 0; i < 8; i++) {
+        if (padded[i] == 0) {
+            zeros++;
+        }
+    }
+    return sizeof(s) * 10 + sizeof(exact) + zeros + strlen(global_padded) + sizeof(bytes) + bytes[1];
+}
@@ -0,0 +1,24 @@
+int primes[] = {2, 3, 5, 7, 11};
+
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    int a[] = {1, 2, 3};
+    long grid[][2] = {{1, 2}, {3, 4}, {5, 6}};
+    struct point points[] = {{1, 2}, {3, 4}};
+    char *words[] = {"one", "two", "three"};
+    double halves[] = {0.5, 1.5};
+
+    int total = 0;
+    for (int i = 0; i < sizeof(a) / sizeof(a[0]); i++) {
+        total += a[i];
+    }
+    for (int i = 0; i < sizeof(primes) / sizeof(int); i++) {
+        total += primes[i];
+    }
+    puts(words[2]);
+    return total + sizeof(grid) + grid[2][1] + points[1].y + sizeof(points) + sizeof(words) + (int)(halves[0] + halves[1]);
+}
@@ -0,0 +1,8 @@
+int main() {
+    int a = 12;
+    int b = 10;
+    int and = a & b;
+    int or = a | b;
+    int xor = a ^ b;
+    return and * 100 + or * 10 + xor - 200;
+}
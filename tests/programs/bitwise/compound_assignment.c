@@ -0,0 +1,18 @@
+int main() {
+    int a = 255;
+    a &= 60;
+    a |= 3;
+    a ^= 1;
+    a <<= 2;
+    a >>= 1;
+
+    int m = 17;
+    m %= 5;
+    m *= 7;
+
+    char flags[2] = {1, 0};
+    flags[1] |= 64;
+    flags[0] <<= 3;
+
+    return a + m + flags[0] + flags[1];
+}
@@ -0,0 +1,10 @@
+int table[10];
+int flags = (1 << 3) | 2;
+
+int main() {
+    int i;
+    for (i = 0; i < sizeof(table) / sizeof(int); i++) {
+        table[i] = i ^ 5;
+    }
+    return table[9] + (~0 & 7) + (-16 >> 2) + flags;
+}
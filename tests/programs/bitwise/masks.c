@@ -0,0 +1,17 @@
+int popcount(unsigned int x) {
+    int count = 0;
+    while (x) {
+        count = count + (x & 1);
+        x = x >> 1;
+    }
+    return count;
+}
+
+int main() {
+    unsigned char byte = 170;
+    unsigned char inverted = ~byte;
+    unsigned long flags = 0;
+    flags = flags | (1 << 3) | (1 << 7);
+    int is_set = (flags & 8) != 0;
+    return popcount(4294967295) + popcount(byte) + inverted + is_set;
+}
@@ -0,0 +1,26 @@
+int main() {
+    int result = 0;
+
+    // & binds tighter than ^, which binds tighter than |
+    if ((1 | 6 & 3 ^ 4) == 7) {
+        result = result + 1;
+    }
+
+    // shifts bind looser than addition
+    if (1 << 2 + 1 == 8) {
+        result = result + 2;
+    }
+
+    // comparisons bind tighter than bitwise and
+    int x = 5;
+    if ((x & 4 == 4) == 0) {
+        result = result + 4;
+    }
+
+    // bitwise or binds tighter than logical and
+    if (x | 0 && 0 | 2) {
+        result = result + 8;
+    }
+
+    return result;
+}
@@ -0,0 +1,16 @@
+int main() {
+    int x = 3;
+    int left = x << 4;
+
+    int negative = -64;
+    int arithmetic = negative >> 3;
+
+    unsigned int u = 4294967232;
+    unsigned int logical = u >> 26;
+
+    long big = 1;
+    big = big << 40;
+
+    int n = 2;
+    return left + arithmetic + logical + (big >> 38) + (5 << n);
+}
@@ -0,0 +1,41 @@
+int identity(int x) { return x; }
+
+long shift_right(int a) {
+  long r = a >> 1;
+  return r;
+}
+
+long xor(int a, int b) {
+  long r = a ^ b;
+  return r;
+}
+
+int main() {
+  int a = identity(-7);
+  int b = identity(2);
+  unsigned int u = identity(-16);
+  long r = a >> 1;
+  long l = a << 1;
+  long x = a ^ b;
+  long n = a & -4;
+  long o = a | 1;
+  long ur = u >> 2;
+  long ux = u ^ 1;
+  if (r != -4 || l != -14 || x != -5 || n != -8 || o != -7) {
+    return 1;
+  }
+  if (ur != 1073741820 || ux != 4294967281) {
+    return 2;
+  }
+  if (shift_right(-100) != -50 || xor(-7, 2) != -5) {
+    return 3;
+  }
+  long total = 0;
+  for (int i = -4; i < 4; i++) {
+    long shifted = i << 3;
+    long masked = i & 6;
+    long flipped = i ^ -1;
+    total = total * 5 + shifted + masked + flipped;
+  }
+  return total % 256;
+}
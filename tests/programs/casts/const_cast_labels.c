@@ -0,0 +1,18 @@
+long g = (long)(char)200;
+unsigned char limit = (unsigned char)511;
+
+int f(int x) {
+    switch (x) {
+    case (char)257:
+        return 10;
+    case (unsigned char)258:
+        return 20;
+    case (int)3:
+        return 30;
+    }
+    return 0;
+}
+
+int main() {
+    return f(1) + f(2) + f(3) + (int)g + limit;
+}
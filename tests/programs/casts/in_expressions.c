@@ -0,0 +1,19 @@
+int get(int x) {
+    return x;
+}
+
+int main() {
+    int x = 1000;
+    unsigned int u = 3000000000;
+    int half = (int)(u / 2);
+    long product = (long)x * x * x * 10;
+    char letter = (char)(get(65) + 1);
+    int truncated = (int)(char)x + (unsigned char)x;
+    int r = -(int)3 + (int)sizeof(long) + ~(unsigned char)1;
+    putchar(letter);
+    putchar((char)(product / 1000000000 + '0'));
+    putchar('\n');
+    (void)get(5);
+    (void)x;
+    return (half / 100000000 + truncated + r) % 256;
+}
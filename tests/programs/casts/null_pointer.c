@@ -0,0 +1,20 @@
+struct node {
+    int value;
+    struct node *next;
+};
+
+int sum(struct node *n) {
+    int total = 0;
+    while (n != (struct node *)0) {
+        total = total + n->value;
+        n = n->next;
+    }
+    return total;
+}
+
+int main() {
+    struct node c = {3, (struct node *)0};
+    struct node b = {20, &c};
+    struct node a = {100, &b};
+    return sum(&a);
+}
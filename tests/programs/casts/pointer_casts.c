@@ -0,0 +1,11 @@
+int main() {
+    int values[4] = {1, 2, 3, 4};
+    char *bytes = (char *)values;
+    int *back = (int *)bytes;
+    long address = (long)values;
+    int *from_address = (int *)address;
+    unsigned long difference = (unsigned long)(back + 2) - (unsigned long)back;
+    void *generic = (void *)values;
+    int *p = (int *)generic;
+    return bytes[4] + back[2] * 10 + from_address[3] * 100 + (int)difference + p[1];
+}
@@ -0,0 +1,19 @@
+int main() {
+    char c = -5;
+    unsigned char uc = 251;
+    long a = (long)c;
+    long b = (long)uc;
+    long d = (long)(char)uc;
+    unsigned int u = (unsigned int)c;
+    long e = (long)u;
+    if (a == -5)
+        putchar('a');
+    if (b == 251)
+        putchar('b');
+    if (d == -5)
+        putchar('d');
+    if (e == 4294967291)
+        putchar('e');
+    putchar('\n');
+    return (int)(a + b + d) % 256;
+}
@@ -0,0 +1,11 @@
+int main() {
+    int x = 300;
+    long big = 4294967297;
+    char c = (char)x;
+    short s = (short)70000;
+    int i = (int)big;
+    unsigned char uc = (unsigned char)-1;
+    putchar('0' + (char)(x - 256) / 10);
+    putchar('\n');
+    return c + s + i + uc;
+}
@@ -0,0 +1,31 @@
+int main() {
+  char c = 100;
+  c = c + 100;
+  unsigned char uc = 250;
+  uc = uc + 10;
+  short s = -30000;
+  s = s - 10000;
+  unsigned int u = 0;
+  u = u - 1;
+  long l = 2000000000;
+  l = l * 4;
+  int i = -7;
+
+  long total = 0;
+  total = total + c;
+  total = total + uc;
+  total = total + s;
+  total = total + (u > 5);
+  total = total + (i < u);
+  total = total + (l >> 3);
+  total = total + (i >> 1);
+  total = total + (u >> 28);
+  total = total + (1 << 20);
+  total = total + i / 2;
+  total = total + i % 3;
+  total = total + (u / 16 == 268435455);
+  total = total + (~i ^ 12 | 3 & 5);
+  total = total + -c + !c + !0;
+  total = total * (c != 0);
+  return total % 251;
+}
@@ -0,0 +1,50 @@
+int global = 3;
+
+int always_taken(int n) {
+  int x = 4;
+  int y = x * 2;
+  if (y > 5) {
+    n = n + y;
+  } else {
+    n = n * global;
+  }
+  while (x < 4) {
+    n = n - 1;
+  }
+  return n;
+}
+
+int unused_results(int n) {
+  int a = n * 3;
+  int b = a + global;
+  int c = 0;
+  for (int i = 0; i < 10; i++) {
+    c = i * 2;
+    b = b + 1;
+  }
+  return n;
+}
+
+int main() {
+  int mode = 2;
+  int result = 0;
+  switch (mode) {
+  case 1:
+    result = 10;
+    break;
+  case 2:
+    result = 20;
+  case 3:
+    result = result + 5;
+    break;
+  }
+  if (0) {
+    result = 1000;
+  }
+  int zero = 0;
+  if (zero && global / zero) {
+    result = 2000;
+  }
+  result = result + always_taken(1) + unused_results(7);
+  return result;
+}
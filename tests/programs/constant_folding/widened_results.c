@@ -0,0 +1,23 @@
+int main() {
+  // the operations are folded at the type of their operands, and then converted to long
+  int a = -7;
+  int b = 2;
+  unsigned int zero = 0;
+  unsigned char uc = 5;
+  unsigned short us = 7;
+  long quotient = a / b;
+  long remainder = a % b;
+  long product = a * b;
+  long shifted = a >> 1;
+  long xored = a ^ b;
+  long anded = a & -2;
+  long negated = -uc;
+  long complemented = ~us;
+  long below = zero - 1;
+  long above = zero + 4294967295u + 2;
+  unsigned long unsigned_product = zero + 65536u * 65536u;
+  int ok = (quotient == -3) + 2 * (remainder == -1) + 4 * (product == -14) + 8 * (shifted == -4) +
+           16 * (xored == -5) + 32 * (anded == -8) + 64 * (negated == -5) + 128 * (complemented == -8);
+  ok += 256 * (below == 4294967295) + 512 * (above == 1) + 1024 * (unsigned_product == 0);
+  return ok == 2047;
+}
@@ -0,0 +1,13 @@
+int main() {
+    int total = 0;
+    for (int i = 0, j = 10; i < j; i++, j--) {
+        total += j - i;
+    }
+    for (int k = 0, *p = &total; k < 3; k++) {
+        *p += k;
+    }
+    for (int i = 0, squares[4] = {0, 1, 4, 9}; i < 4; i++) {
+        total += squares[i];
+    }
+    return total;
+}
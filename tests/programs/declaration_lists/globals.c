@@ -0,0 +1,17 @@
+int count = 3, values[3] = {4, 5, 6}, *unused;
+char *greeting = "hello", *farewell = "bye";
+struct point {
+    int x;
+    int y;
+} origin, corner = {7, 8};
+
+typedef int number, *number_ptr;
+
+int main() {
+    number n = 2;
+    number_ptr p = &n;
+    puts(greeting);
+    puts(farewell);
+    unused = &count;
+    return *unused + values[2] + origin.x + corner.y + *p;
+}
@@ -0,0 +1,15 @@
+int main() {
+    int a = 5, *b = &a, c[3] = {1, 2, 3};
+    *b += c[2];
+    char x = 'x', y, *s = "list";
+    y = x + 1;
+    puts(s);
+    putchar(y);
+    putchar('\n');
+
+    int first = 10, second = first * 2, third = first + second;
+    long big, small;
+    big = 1000000000000;
+    small = 3;
+    return a + c[0] + c[1] + third + (int)(big / 1000000000000) + small;
+}
@@ -0,0 +1,13 @@
+int main() {
+    int x = 1;
+    {
+        int y = x + 1, x = y * 10, z = x + 1;
+        putchar('0' + y);
+        putchar('\n');
+        if (z != 21) {
+            return 1;
+        }
+    }
+    enum { A = 4, B } e = B, f = A;
+    return x + e + f;
+}
@@ -0,0 +1,18 @@
+int main() {
+    char *names[4];
+    names[0] = "alpha";
+    names[1] = "beta";
+    names[2] = "gamma";
+    names[3] = "delta";
+    for (int i = 0; i < 4; i++) {
+        puts(names[i]);
+    }
+
+    int a = 1;
+    int b = 2;
+    int *ptrs[2];
+    ptrs[0] = &a;
+    ptrs[1] = &b;
+    *ptrs[1] = 40;
+    return sizeof(names) + *ptrs[0] + b;
+}
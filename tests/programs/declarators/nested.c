@@ -0,0 +1,17 @@
+struct pair {
+    int (*values)[2];
+    char *labels[2];
+};
+
+int main() {
+    int data[2] = {3, 4};
+    struct pair p;
+    p.values = &data;
+    p.labels[0] = "first";
+    p.labels[1] = "second";
+    puts(p.labels[1]);
+
+    int *(*table)[2];
+    int n = sizeof(*table) + sizeof(int (*)[5]) + sizeof(int *[5]);
+    return (*p.values)[0] * 10 + (*p.values)[1] + n;
+}
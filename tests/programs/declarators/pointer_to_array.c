@@ -0,0 +1,17 @@
+int sum_row(int (*row)[3]) {
+    return (*row)[0] + (*row)[1] + (*row)[2];
+}
+
+int main() {
+    int grid[2][3];
+    for (int i = 0; i < 2; i++) {
+        for (int j = 0; j < 3; j++) {
+            grid[i][j] = i * 3 + j;
+        }
+    }
+
+    int (*p)[3] = grid;
+    p++;
+    int total = sum_row(p) + sum_row(grid);
+    return total + sizeof(p) + sizeof(*p);
+}
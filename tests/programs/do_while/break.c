@@ -0,0 +1,12 @@
+int main() {
+    int n = 0;
+    do {
+        n = n + 7;
+        if (n > 50) {
+            break;
+        }
+        putchar('a' + n % 26);
+    } while (1);
+    putchar('\n');
+    return n;
+}
@@ -0,0 +1,15 @@
+int main() {
+    int i = 0;
+    int sum = 0;
+    do {
+        i++;
+        if (i % 3 == 0) {
+            // continue jumps to the condition, so the loop still ends
+            continue;
+        }
+        sum = sum + i;
+        putchar('0' + i % 10);
+    } while (i < 20);
+    putchar('\n');
+    return sum;
+}
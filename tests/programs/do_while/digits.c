@@ -0,0 +1,22 @@
+void print_number(unsigned int n) {
+    char buf[12];
+    int len = 0;
+    do {
+        buf[len] = '0' + n % 10;
+        n = n / 10;
+        len++;
+    } while (n != 0);
+    do {
+        len--;
+        putchar(buf[len]);
+    } while (len > 0);
+    putchar('\n');
+}
+
+int main() {
+    print_number(0);
+    print_number(7);
+    print_number(1234567);
+    print_number(4294967295);
+    return 0;
+}
@@ -0,0 +1,13 @@
+int main() {
+    int i = 0;
+    int total = 0;
+    do {
+        int j = i;
+        do {
+            total = total + j;
+            j--;
+        } while (j > 0);
+        i++;
+    } while (i < 6);
+    return total;
+}
@@ -0,0 +1,10 @@
+int main() {
+    int count = 0;
+    do {
+        count++;
+    } while (0);
+    do
+        count = count + 10;
+    while (count < 5);
+    return count;
+}
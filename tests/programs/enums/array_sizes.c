@@ -0,0 +1,22 @@
+enum sizes { ROWS = 3, COLS = ROWS + 1, CELLS = ROWS * COLS };
+
+int grid[ROWS][COLS];
+struct buffer {
+    char data[CELLS];
+    int len;
+};
+
+int main() {
+    int total = 0;
+    for (int i = 0; i < ROWS; i++) {
+        for (int j = 0; j < COLS; j++) {
+            grid[i][j] = i * COLS + j;
+        }
+    }
+    for (int i = 0; i < ROWS; i++) {
+        total += grid[i][COLS - 1];
+    }
+    struct buffer b;
+    int local[2 * CELLS];
+    return total + sizeof(b) + sizeof(local) + sizeof(grid);
+}
@@ -0,0 +1,13 @@
+enum color { RED, GREEN };
+
+int main(void) {
+  int total = 0;
+  {
+    // the inner definition hides the outer enum color, and its enumerators hide RED
+    enum color { BLUE = 10, RED = 20 };
+    enum color c = BLUE;
+    total += c + RED;
+  }
+  enum color outer = GREEN;
+  return total + outer + RED;
+}
@@ -0,0 +1,24 @@
+enum { A = 1, B = 2 };
+
+int shadow_with_variable() {
+    int A = 10;
+    return A + B;
+}
+
+int shadow_with_enum() {
+    int result = A;
+    {
+        enum { A = 100 };
+        result += A;
+    }
+    return result + A;
+}
+
+int main() {
+    int total = shadow_with_variable() + shadow_with_enum();
+    if (total > 0) {
+        enum local { B = 20, C };
+        total += B + C;
+    }
+    return total + B;
+}
@@ -0,0 +1,28 @@
+struct shape {
+    enum { CIRCLE, SQUARE, TRIANGLE } kind;
+    int size;
+};
+
+int area(struct shape *s) {
+    if (s->kind == SQUARE) {
+        return s->size * s->size;
+    } else if (s->kind == TRIANGLE) {
+        return s->size * s->size / 2;
+    }
+    return 3 * s->size * s->size;
+}
+
+int main() {
+    struct shape shapes[3];
+    shapes[0].kind = CIRCLE;
+    shapes[0].size = 2;
+    shapes[1].kind = SQUARE;
+    shapes[1].size = 3;
+    shapes[2].kind = TRIANGLE;
+    shapes[2].size = 4;
+    int total = 0;
+    for (int i = 0; i < 3; i++) {
+        total += area(&shapes[i]);
+    }
+    return total + sizeof(struct shape);
+}
@@ -0,0 +1,23 @@
+enum token_kind { NUMBER, PLUS, MINUS, STAR, END = 10 };
+
+int apply(enum token_kind op, int a, int b) {
+    switch (op) {
+    case PLUS:
+        return a + b;
+    case MINUS:
+        return a - b;
+    case STAR:
+        return a * b;
+    case END:
+    case NUMBER:
+        return -1;
+    }
+    return 0;
+}
+
+int main() {
+    int result = apply(PLUS, 3, 4);
+    result = apply(STAR, result, 3);
+    result = apply(MINUS, result, 1);
+    return result + apply(END, 0, 0);
+}
@@ -0,0 +1,11 @@
+enum color { RED, GREEN = 5, BLUE, YELLOW = GREEN * 3 - 1, };
+
+int main() {
+    enum color c = BLUE;
+    putchar('0' + RED);
+    putchar('0' + c);
+    putchar('0' + YELLOW % 10);
+    putchar('\n');
+    enum { NEGATIVE = -3, NEXT } n = NEXT;
+    return GREEN + BLUE + YELLOW + n + sizeof(c) + sizeof(enum color);
+}
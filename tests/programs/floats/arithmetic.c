@@ -0,0 +1,32 @@
+double average(int *values, int len) {
+    double sum = 0;
+    for (int i = 0; i < len; i++) {
+        sum += values[i];
+    }
+    return sum / len;
+}
+
+int main() {
+    double x = 1.5;
+    float y = 2.25f;
+    double z = x * y + 0.5;
+    z -= .125;
+    z /= 2;
+
+    int values[4] = {1, 2, 3, 5};
+    double avg = average(values, 4);
+
+    float f = 0.1f;
+    f += 0.2f;
+    double d = 0.1;
+    d += 0.2;
+
+    double counter = 0.5;
+    counter++;
+    ++counter;
+    counter--;
+
+    // 1.875 * 1000 + 2.75 * 100 + 1.5 * 10
+    return (int)(z * 1000) - 1800 + (int)(avg * 100) - 200 + (int)(counter * 10) - 10 + (f == 0.3f) +
+           2 * (d == 0.3) + 4 * (-x < 0) + 8 * (1e2 == 100) + 16 * (2.5e-1 == 0.25);
+}
@@ -0,0 +1,31 @@
+int check(double a, double b) {
+    return (a < b) + 2 * (a <= b) + 4 * (a > b) + 8 * (a >= b) + 16 * (a == b) + 32 * (a != b);
+}
+
+int main() {
+    double zero = 0.0;
+    double nan = zero / zero;
+    double neg_zero = -zero;
+
+    int result = 0;
+    // comparisons with NaN are all false, except for !=
+    if (check(nan, 1.0) == 32)
+        result += 1;
+    if (check(1.0, nan) == 32)
+        result += 2;
+    if (check(nan, nan) == 32)
+        result += 4;
+    if (check(1.0, 2.0) == 35)
+        result += 8;
+    if (check(neg_zero, zero) == 26)
+        result += 16;
+    // -0.0 and NaN used as conditions
+    if (!neg_zero && nan)
+        result += 32;
+    if (neg_zero || !nan)
+        result += 64;
+    float small = 1.5f;
+    if (small < 1.75 && small > 1)
+        result += 128;
+    return result;
+}
@@ -0,0 +1,39 @@
+int main() {
+    double d = -7.9;
+    int truncated = d;
+    long big = 4000000000.5;
+    unsigned char uc = 200.7;
+    char c = -100.9;
+
+    unsigned long max = (unsigned long)-1;
+    double max_d = max;
+    unsigned long huge = 17000000000000000000.0;
+    double two_to_the_63 = 9223372036854775808.0;
+    unsigned long huge_back = (unsigned long)(two_to_the_63 + 4096);
+    float from_long = 16777217;
+    unsigned int ui = 4000000000;
+    double from_unsigned_int = ui;
+
+    int result = 0;
+    if (truncated == -7)
+        result += 1;
+    if (big == 4000000000)
+        result += 2;
+    if (uc == 200 && c == -100)
+        result += 4;
+    if (max_d == 18446744073709551616.0)
+        result += 8;
+    if (huge / 1000000000 == 17000000000)
+        result += 16;
+    if (huge_back / 4096 == 2251799813685249)
+        result += 32;
+    if (from_long == 16777216)
+        result += 64;
+    if (from_unsigned_int == 4000000000.0)
+        result += 128;
+
+    float f = 1.1;
+    double widened = f;
+    float narrowed = widened;
+    return result + (widened != 1.1) + (narrowed == f);
+}
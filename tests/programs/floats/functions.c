@@ -0,0 +1,37 @@
+double mix(int a, double b, long c, float d, char e, double f) {
+    return a + b + c + d + e + f;
+}
+
+// more than 8 floating point arguments, and more than 6 integer arguments, are passed on the stack
+double many(double a, int n1, double b, double c, int n2, double d, double e, int n3, double f, double g,
+            int n4, double h, double i, int n5, double j, int n6, int n7, float k) {
+    return a + 2 * b + 3 * c + 4 * d + 5 * e + 6 * f + 7 * g + 8 * h + 9 * i + 10 * j + 11 * k + n1 + n2 +
+           n3 + n4 + n5 + n6 + n7;
+}
+
+float half(float x) {
+    return x / 2;
+}
+
+int truncate(double x) {
+    return x;
+}
+
+double from_int(int x) {
+    return x;
+}
+
+int main() {
+    int result = 0;
+    if (mix(1, 2.5, 3, 0.25f, 4, 0.25) == 11.0)
+        result += 1;
+    if (many(1, 1, 1, 1, 2, 1, 1, 3, 1, 1, 4, 1, 1, 5, 1, 6, 7, 1) == 94)
+        result += 2;
+    if (half(5) == 2.5f)
+        result += 4;
+    if (truncate(9.99) == 9)
+        result += 8;
+    if (from_int(3) / 2 == 1.5)
+        result += 16;
+    return result;
+}
@@ -0,0 +1,47 @@
+struct point {
+    float x;
+    double y;
+};
+
+double scale = 2.5;
+float offsets[3] = {0.5f, 1.5, 2};
+double from_int = 7;
+int from_double = 3.99;
+struct point origin = {1.25f, -2.5};
+
+void move(struct point *p, double dx) {
+    p->x += dx;
+    p->y = p->y - dx;
+}
+
+int main() {
+    double local[4] = {1.0, 2.0, 3.0};
+    double *ptr = local;
+    ptr[3] = ptr[0] + ptr[2];
+
+    struct point p = origin;
+    move(&p, 0.75);
+
+    float sum = 0;
+    for (int i = 0; i < 3; i++) {
+        sum += offsets[i];
+    }
+
+    int result = 0;
+    if (local[3] * scale == 10)
+        result += 1;
+    if (sum == 4)
+        result += 2;
+    if (from_int == 7.0 && from_double == 3)
+        result += 4;
+    if (p.x == 2 && p.y == -3.25)
+        result += 8;
+    double x = 0.0;
+    double *px = &x;
+    *px = 1.5;
+    if (x == 1.5)
+        result += 16;
+    if ((x > 1 ? x : 0) == 1.5 && (x < 1 ? 1 : x) == 1.5)
+        result += 32;
+    return result;
+}
@@ -0,0 +1,42 @@
+// prints a double with 3 decimal places
+void print_double(double x) {
+    if (x < 0) {
+        putchar('-');
+        x = -x;
+    }
+    long whole = x;
+    long frac = (x - whole) * 1000 + 0.5;
+    char digits[20];
+    int len = 0;
+    do {
+        digits[len] = '0' + whole % 10;
+        whole /= 10;
+        len++;
+    } while (whole > 0);
+    while (len > 0) {
+        len--;
+        putchar(digits[len]);
+    }
+    putchar('.');
+    putchar('0' + frac / 100);
+    putchar('0' + frac / 10 % 10);
+    putchar('0' + frac % 10);
+    putchar('\n');
+}
+
+double sqrt_newton(double x) {
+    double guess = x / 2;
+    for (int i = 0; i < 20; i++) {
+        guess = (guess + x / guess) / 2;
+    }
+    return guess;
+}
+
+int main() {
+    print_double(3.14159);
+    print_double(-2.5);
+    print_double(sqrt_newton(2));
+    print_double(1e6 / 3);
+    print_double(22.0f / 7);
+    return 0;
+}
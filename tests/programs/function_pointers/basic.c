@@ -0,0 +1,21 @@
+int add(int a, int b) {
+    return a + b;
+}
+
+int sub(int a, int b) {
+    return a - b;
+}
+
+int main() {
+    int (*fp)(int, int) = add;
+    int x = fp(3, 4);
+    fp = &sub;
+    int y = (*fp)(10, 3);
+    if (fp == sub) {
+        puts("equal");
+    }
+    int (*other)(int, int) = x > 5 ? add : sub;
+    void *erased = (void *)other;
+    int z = ((int (*)(int, int))erased)(1, 1) + (**other)(2, 2);
+    return x * 10 + y + z;
+}
@@ -0,0 +1,37 @@
+int square(int x) {
+    return x * x;
+}
+
+int negate(int x) {
+    return -x;
+}
+
+void apply(int *values, int count, int (*f)(int)) {
+    for (int i = 0; i < count; i++) {
+        values[i] = f(values[i]);
+    }
+}
+
+int reduce(int *values, int count, int (*combine)(int, int), int initial) {
+    int result = initial;
+    for (int i = 0; i < count; i++) {
+        result = combine(result, values[i]);
+    }
+    return result;
+}
+
+int max(int a, int b) {
+    return a > b ? a : b;
+}
+
+int plus(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    int values[4] = {1, -2, 3, -4};
+    apply(values, 4, square);
+    int total = reduce(values, 4, plus, 0);
+    apply(values, 4, negate);
+    return total + reduce(values, 4, max, -100);
+}
@@ -0,0 +1,35 @@
+double scale(double x, int factor, float offset) {
+    return x * factor + offset;
+}
+
+char first(char *s) {
+    return s[0];
+}
+
+void shout(char *s) {
+    puts(s);
+}
+
+int count = 0;
+
+void bump(void) {
+    count++;
+}
+
+int many(int a, int b, int c, int d, int e, int f, int g, int h) {
+    return a + b * 2 + c * 3 + d * 4 + e * 5 + f * 6 + g * 7 + h * 8;
+}
+
+int main() {
+    double (*s)(double, int, float) = scale;
+    char (*f)(char *) = first;
+    void (*v)(char *) = shout;
+    void (*b)(void) = bump;
+    int (*m)(int, int, int, int, int, int, int, int) = many;
+
+    v("function pointers");
+    b();
+    (*b)();
+    double d = s(2.5, 3, 0.5f);
+    return (int)d + f("A") + count + m(1, 2, 3, 4, 5, 6, 7, 8);
+}
@@ -0,0 +1,25 @@
+int twice(int x) {
+    return x * 2;
+}
+
+int thrice(int x) {
+    return x * 3;
+}
+
+typedef int (*int_fn)(int);
+
+int_fn choose(int which) {
+    if (which) {
+        return twice;
+    }
+    return thrice;
+}
+
+int fact(int n) {
+    int (*self)(int) = fact;
+    return n <= 1 ? 1 : n * self(n - 1);
+}
+
+int main() {
+    return choose(1)(5) + choose(0)(4) + fact(4);
+}
@@ -0,0 +1,28 @@
+int add_one(int x) {
+    return x + 1;
+}
+
+int twice(int x) {
+    return 2 * x;
+}
+
+int (*pick(int which))(int);
+
+int apply(int (*f)(int), int x) {
+    return f(x);
+}
+
+int (*pick(int which))(int) {
+    if (which) {
+        return twice;
+    }
+    return add_one;
+}
+
+int main() {
+    int (*f)(int) = pick(0);
+    int a = f(10);
+    int b = pick(1)(10);
+    int c = apply(pick(1), 3);
+    return a + b + c;
+}
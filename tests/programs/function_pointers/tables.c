@@ -0,0 +1,39 @@
+typedef long (*binop)(long, long);
+
+long add(long a, long b) {
+    return a + b;
+}
+
+long mul(long a, long b) {
+    return a * b;
+}
+
+long shl(long a, long b) {
+    return a << b;
+}
+
+binop global_ops[3] = {add, mul, &shl};
+
+struct handler {
+    char *name;
+    long (*run)(long, long);
+};
+
+int main() {
+    long (*ops[3])(long, long);
+    ops[0] = add;
+    ops[1] = mul;
+    ops[2] = shl;
+
+    long total = 0;
+    for (int i = 0; i < 3; i++) {
+        total += ops[i](3, 2) + global_ops[i](1, 1);
+    }
+
+    struct handler h;
+    h.name = "handler";
+    h.run = mul;
+    struct handler *hp = &h;
+    puts(hp->name);
+    return total + hp->run(2, 5);
+}
@@ -0,0 +1,31 @@
+struct pair {
+  char c;
+  long l;
+};
+
+int g = 5;
+int arr[4] = {10, 20, 30, 40};
+struct pair pairs[2] = {{'a', 100}, {'b', 200}};
+char text[] = "hello";
+
+int *p = &g;
+int *first = arr;
+int *third = &arr[2];
+int *last = arr + 3;
+int *second = 1 + arr;
+int *back = &arr[3] - 2;
+long *member = &pairs[1].l;
+char *c = &pairs[0].c;
+char *s = text;
+char *lit = "world" + 2;
+int *ptrs[3] = {&g, arr, &arr[1]};
+void *untyped = &g;
+
+int main() {
+  *p = *p + 1;
+  int total = g + *first + *third + *last + *second + *back;
+  total = total + *member / 10 + *c + *s + *lit;
+  total = total + *ptrs[0] + ptrs[1][1] + *ptrs[2];
+  total = total + (untyped == &g);
+  return total % 256;
+}
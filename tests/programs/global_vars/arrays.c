@@ -0,0 +1,22 @@
+int squares[10];
+int primes[5] = {2, 3, 5, 7, 11};
+char greeting[6] = "hello";
+
+int fill_squares() {
+    int i;
+    for (i = 0; i < 10; i++) {
+        squares[i] = i * i;
+    }
+    return 0;
+}
+
+int main() {
+    fill_squares();
+    puts(greeting);
+    int total = 0;
+    int i;
+    for (i = 0; i < 5; i++) {
+        total += primes[i];
+    }
+    return total + squares[9] + sizeof(squares);
+}
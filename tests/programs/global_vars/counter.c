@@ -0,0 +1,14 @@
+int counter;
+
+int increment() {
+    counter++;
+    return counter;
+}
+
+int main() {
+    increment();
+    increment();
+    counter += 10;
+    ++counter;
+    return increment();
+}
@@ -0,0 +1,14 @@
+int a = 5;
+long b = -123456789012;
+char c = 'x';
+short s = 1000 + 24;
+int d;
+
+int main() {
+    putchar(c);
+    putchar('\n');
+    if (b != -123456789012) {
+        return 1;
+    }
+    return a + s + d;
+}
@@ -0,0 +1,18 @@
+int value = 7;
+int *ptr;
+char *name = "global string";
+char *names[3] = {"zero", "one", "two"};
+
+int set_ptr() {
+    ptr = &value;
+    return 0;
+}
+
+int main() {
+    set_ptr();
+    *ptr = *ptr * 6;
+    puts(name);
+    puts(names[2]);
+    puts(names[0]);
+    return value;
+}
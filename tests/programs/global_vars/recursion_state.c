@@ -0,0 +1,19 @@
+int calls;
+int memo[20];
+
+int fib(int n) {
+    calls++;
+    if (n < 2) {
+        return n;
+    }
+    if (memo[n]) {
+        return memo[n];
+    }
+    memo[n] = fib(n - 1) + fib(n - 2);
+    return memo[n];
+}
+
+int main() {
+    int result = fib(15);
+    return result % 256 + calls;
+}
@@ -0,0 +1,18 @@
+int x = 10;
+
+int get_x() {
+    return x;
+}
+
+int main() {
+    int result = 0;
+    {
+        int x = 3;
+        result += x;
+        x = 100;
+    }
+    result += get_x();
+    int x = 20;
+    result += x;
+    return result;
+}
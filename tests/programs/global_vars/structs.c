@@ -0,0 +1,24 @@
+struct point {
+    char tag;
+    int x;
+    long y;
+};
+
+struct point origin;
+struct point p = {'p', 3, 4};
+struct point points[2] = {{'a', 1, 2}, {'b', 5, 6}};
+
+int move(struct point *pt) {
+    pt->x++;
+    pt->y += 10;
+    return 0;
+}
+
+int main() {
+    move(&p);
+    origin = points[1];
+    putchar(p.tag);
+    putchar(origin.tag);
+    putchar('\n');
+    return p.x + p.y + origin.x + origin.y + sizeof(points);
+}
@@ -0,0 +1,10 @@
+int main() {
+    int i = 0;
+    int sum = 0;
+loop:
+    sum = sum + i;
+    i++;
+    if (i < 10)
+        goto loop;
+    return sum;
+}
@@ -0,0 +1,19 @@
+int main() {
+    int i;
+    int j;
+    int found = 0;
+    for (i = 0; i < 10; i++) {
+        for (j = 0; j < 10; j++) {
+            if (i * j == 42) {
+                found = i * 10 + j;
+                goto done;
+            }
+        }
+    }
+    found = 99;
+done:
+    putchar('0' + i);
+    putchar('0' + j);
+    putchar('\n');
+    return found;
+}
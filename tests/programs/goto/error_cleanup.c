@@ -0,0 +1,46 @@
+int resources = 0;
+
+int acquire(int id) {
+    if (id == 3) {
+        return 0;
+    }
+    resources++;
+    return 1;
+}
+
+void release() {
+    resources--;
+}
+
+int setup(int fail_at) {
+    int result = 0;
+    if (!acquire(fail_at == 1 ? 3 : 1))
+        goto fail_first;
+    if (!acquire(fail_at == 2 ? 3 : 2))
+        goto fail_second;
+    if (fail_at == 3)
+        goto fail_third;
+    return 1;
+
+fail_third:
+    release();
+fail_second:
+    release();
+fail_first:
+    result = -1;
+    return result;
+}
+
+int main() {
+    int r;
+    r = setup(1);
+    putchar('0' + resources);
+    r = r + setup(2);
+    putchar('0' + resources);
+    r = r + setup(3);
+    putchar('0' + resources);
+    r = r + setup(0);
+    putchar('0' + resources);
+    putchar('\n');
+    return r + 10;
+}
@@ -0,0 +1,23 @@
+int main() {
+    int total = 0;
+    int i = 5;
+    goto middle;
+    for (i = 0; i < 8; i++) {
+        total = total + 100;
+    middle:
+        switch (i % 3) {
+        case 0:
+            total = total + 1;
+            break;
+        case 1:
+        again:
+            total = total + 2;
+            if (total % 7 == 0)
+                goto again;
+            break;
+        default:
+            total = total + 3;
+        }
+    }
+    return total % 256;
+}
@@ -0,0 +1,26 @@
+int f(int x) {
+    if (x > 0)
+        goto positive;
+    return 0;
+positive:
+    return 1;
+}
+
+int g(int x) {
+    // the same label names may be used in different functions
+    if (x > 0)
+        goto positive;
+    goto end_while_0;
+positive:
+    return 2;
+end_while_0:
+    return 3;
+}
+
+int main() {
+    int i = 0;
+    while (i < 3) {
+        i++;
+    }
+    return f(5) + f(-5) * 10 + g(1) * 100 + g(0);
+}
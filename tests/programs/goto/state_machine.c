@@ -0,0 +1,24 @@
+int count_words(char *s) {
+    int words = 0;
+outside:
+    if (*s == 0)
+        goto end;
+    if (*s == ' ') {
+        s++;
+        goto outside;
+    }
+    words++;
+inside:
+    s++;
+    if (*s == 0)
+        goto end;
+    if (*s == ' ')
+        goto outside;
+    goto inside;
+end:
+    return words;
+}
+
+int main() {
+    return count_words("  the quick  brown fox ") * 10 + count_words("");
+}
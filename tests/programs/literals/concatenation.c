@@ -0,0 +1,19 @@
+char *global = "glo"
+               "bal";
+char global_arr[] = "con" "cat" "enated";
+
+int main() {
+    puts("hello, " "world");
+    puts("one"
+         " two"
+         " three");
+    puts(global);
+    puts(global_arr);
+
+    // escapes are processed before the strings are concatenated
+    char s[] = "\x41" "B" "\101" "";
+    puts(s);
+
+    char empty[] = "" "";
+    return sizeof(global_arr) * 10 + sizeof(s) + sizeof(empty);
+}
@@ -0,0 +1,39 @@
+int main() {
+    char s[] = "\a\b\f\n\r\t\v\\\'\"\?";
+    for (int i = 0; i < sizeof(s); i++) {
+        putchar('0' + s[i] / 10);
+        putchar('0' + s[i] % 10);
+        putchar(' ');
+    }
+    putchar('\n');
+
+    puts("octal: \101\102\103 \60\61");
+    puts("hex: \x41\x42\x43 \x6a\x6B");
+    puts("question\? mark");
+    puts("tab\tand \"quotes\"");
+
+    int result = 0;
+    result += '\x7f' == 127;
+    result += '\377' == -1;
+    result += '\xff' == -1;
+    result += '\0' == 0;
+    result += '\12' == '\n';
+    result += '\x0a' == 10;
+    result += '\a' == 7;
+    result += '\v' == 11;
+    result += '\'' == 39;
+    result += '"' == 34;
+    result += '\"' == 34;
+
+    // an octal escape is at most 3 digits long
+    char octal[] = "\1234";
+    result += sizeof(octal) == 3;
+    result += octal[0] == 'S';
+    result += octal[1] == '4';
+
+    unsigned char high[] = "\xff\200";
+    result += high[0] == 255;
+    result += high[1] == 128;
+
+    return result;
+}
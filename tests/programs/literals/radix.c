@@ -0,0 +1,35 @@
+void print_num(long n) {
+    if (n < 0) {
+        putchar('-');
+        n = -n;
+    }
+    if (n >= 10) {
+        print_num(n / 10);
+    }
+    putchar('0' + n % 10);
+    putchar('\n');
+}
+
+int main() {
+    print_num(0x2A);
+    print_num(0XfF);
+    print_num(052);
+    print_num(0);
+    print_num(00);
+    print_num(0b101010);
+    print_num(0B1);
+    print_num(0x7fffffff);
+    print_num(0x123456789);
+    print_num(0777777777777);
+    print_num(9223372036854775807);
+
+    int arr[0x10];
+    switch (0b11) {
+    case 0x3:
+        print_num(sizeof(arr));
+        break;
+    default:
+        print_num(-1);
+    }
+    return 012 + 0x1 + 0b10;
+}
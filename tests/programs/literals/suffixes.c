@@ -0,0 +1,34 @@
+int main() {
+    int result = 0;
+
+    // the type of an unsuffixed literal depends on its value and its radix
+    result += sizeof(1) == 4;
+    result += sizeof(2147483647) == 4;
+    result += sizeof(2147483648) == 8;
+    result += sizeof(4000000000) == 8;
+    result += sizeof(0xffffffff) == 4;
+    result += sizeof(0x100000000) == 8;
+
+    result += sizeof(1L) == 8;
+    result += sizeof(1ll) == 8;
+    result += sizeof(1u) == 4;
+    result += sizeof(1UL) == 8;
+    result += sizeof(1LLU) == 8;
+    result += sizeof(0x10lu) == 8;
+
+    // unsigned literals make comparisons unsigned
+    result += -1 < 0 ? 1 : 0;
+    result += -1 < 0u ? 0 : 1;
+    result += 0xffffffff > 0 ? 1 : 0;
+    result += -1 == 0xffffffff ? 1 : 0;
+    result += -1L == 0xffffffff ? 0 : 1;
+
+    unsigned long big = 18446744073709551615ul;
+    result += big == -1 ? 1 : 0;
+    result += big / 2 == 9223372036854775807 ? 1 : 0;
+
+    unsigned int u = 4294967295U;
+    result += u / 2 == 2147483647 ? 1 : 0;
+
+    return result;
+}
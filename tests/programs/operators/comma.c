@@ -0,0 +1,7 @@
+int main() {
+    int a = 1;
+    int b;
+    b = (a = 5, a + 2);
+    int c = (a++, b++, a * b);
+    return c + (1, 2, 3);
+}
@@ -0,0 +1,12 @@
+int main() {
+    char s[8] = {'a', 'b', 'c', 'd', 'e', 'f', 'g', 0};
+    int i;
+    int j;
+    for (i = 0, j = 6; i < j; i++, j--) {
+        char tmp = s[i];
+        s[i] = s[j];
+        s[j] = tmp;
+    }
+    puts(s);
+    return i * 10 + j;
+}
@@ -0,0 +1,27 @@
+int counter = 0;
+
+int bump(int by) {
+    counter = counter + by;
+    return counter;
+}
+
+void log_char(char c) {
+    putchar(c);
+}
+
+int max(int a, int b) {
+    return a > b ? a : b;
+}
+
+int main() {
+    int x = (bump(1), bump(2), bump(3));
+    log_char('x'), log_char('y'), log_char('\n');
+    // commas inside parentheses are operators, the others separate arguments
+    int m = max((bump(10), 4), (x, 5));
+    int *p = (bump(0), &x);
+    while (bump(1), counter < 20) {
+        putchar('.');
+    }
+    putchar('\n');
+    return x + m + *p + counter;
+}
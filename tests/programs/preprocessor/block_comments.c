@@ -0,0 +1,13 @@
+/* a block comment
+   spanning several lines */
+int main() {
+    int a = 1 /* inline */ + 2;
+    int b = 3; /* a comment which
+    ends */ int c = 4;
+    // a line comment with /* in it
+    char *s = "/* not a comment */";
+    int d/**/= 5;
+    int e = 6; \
+    int f = 7;
+    return a + b + c + d + e + f + s[0];
+}
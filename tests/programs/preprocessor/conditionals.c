@@ -0,0 +1,45 @@
+#define FEATURE
+#define LEVEL 3
+
+int main() {
+    int result = 0;
+#ifdef FEATURE
+    result = result + 1;
+#else
+    result = result + 100;
+#endif
+
+#ifndef MISSING
+    result = result + 2;
+#endif
+
+#if LEVEL > 5
+    result = result + 100;
+#elif LEVEL == 3 && defined(FEATURE)
+    result = result + 4;
+#elif LEVEL == 3
+    result = result + 100;
+#else
+    result = result + 100;
+#endif
+
+#if 0
+    this is not valid C, but it is never compiled
+#if 1
+    #error nested branches are not taken either
+#endif
+#endif
+
+#if defined MISSING || (UNDEFINED_NAME + 1) * 2 == 2 && (0x10 >> 2) == 4 && 'a' == 97
+    result = result + 8;
+#endif
+
+#if 1 ? 0 : 1 / 0
+    result = result + 100;
+#elif !defined(FEATURE)
+    result = result + 100;
+#else
+    result = result + 16;
+#endif
+    return result;
+}
@@ -0,0 +1,26 @@
+#define SQUARE(x) ((x) * (x))
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+#define ADD3(a, b, c) (a + b + c)
+#define APPLY(f, x) f(x)
+#define NO_ARGS() 7
+#define f(x) (x + f)
+
+int square(int x) {
+    return x * x;
+}
+
+int main() {
+    int f = 1;
+    int a = SQUARE(3);                  // 9
+    int b = MAX(a, SQUARE(2 + 1) + 1);  // 10
+    int c = ADD3(1,
+                 2,
+                 3);                    // 6
+    int d = APPLY(SQUARE, 2);           // 4
+    int e = NO_ARGS();                  // 7
+    int g = f(f(2));                    // a macro is not expanded inside of itself: 4
+    int h = MAX((1, 2), 0);             // 2
+    // the name of a function-like macro which is not invoked is left alone
+    int square = 3;
+    return a + b + c + d + e + g + h + square;
+}
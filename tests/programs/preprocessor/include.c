@@ -0,0 +1,6 @@
+#include "../../include/pp_header.h"
+#include "../../include/pp_header.h"
+
+int main() {
+    return header_func(HEADER_VALUE) + nested_line;
+}
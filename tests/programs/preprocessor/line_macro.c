@@ -0,0 +1,15 @@
+#define CURRENT_LINE __LINE__
+
+int main() {
+    int line = __LINE__;
+    char *file = __FILE__;
+    int i = 0;
+    while (file[i] != 0) {
+        i = i + 1;
+    }
+    // __FILE__ ends with the name of this file
+    if (file[i - 1] != 'c' || file[i - 2] != '.' || file[i - 3] != 'o') {
+        return 1;
+    }
+    return line + CURRENT_LINE;
+}
@@ -0,0 +1,20 @@
+#define SIZE 4
+#define DOUBLE_SIZE (SIZE * 2)
+#define EMPTY
+#define NEG -1
+
+int arr[SIZE];
+
+int main() {
+    EMPTY int total = DOUBLE_SIZE;
+    int i;
+    for (i = 0; i < SIZE; i = i + 1) {
+        arr[i] = i;
+        total = total + arr[i];
+    }
+    // the minus signs must not join into a decrement
+    total = total -NEG;
+#undef SIZE
+#define SIZE 100
+    return total + SIZE;
+}
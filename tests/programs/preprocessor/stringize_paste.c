@@ -0,0 +1,27 @@
+#define STR(x) #x
+#define XSTR(x) STR(x)
+#define CAT(a, b) a##b
+#define MAKE_GETTER(name) int get_##name() { return name; }
+#define VALUE 42
+
+int counter = 5;
+MAKE_GETTER(counter)
+
+int main() {
+    char *s = STR(hello   world);
+    char *q = STR("a\n");
+    char *v = XSTR(VALUE);
+    int CAT(var, 1) = 3;
+    int CAT(x, ) = 2;
+    int result = CAT(1, 0);
+    if (s[5] != ' ' || s[6] != 'w') {
+        return 1;
+    }
+    if (q[0] != '"' || q[2] != '\\' || q[5] != 0) {
+        return 2;
+    }
+    if (v[0] != '4' || v[1] != '2') {
+        return 3;
+    }
+    return result + var1 + x + get_counter();
+}
@@ -0,0 +1,26 @@
+int main() {
+  int result = 0;
+// -1 is converted to unsigned, so it is the biggest value rather than a negative one
+#if -1 < 0u
+  result += 1;
+#endif
+#if 0xFFFFFFFFFFFFFFFF > 0
+  result += 2;
+#endif
+#if -1 > 0u
+  result += 4;
+#endif
+#if (0u - 1) / 2 == 0x7FFFFFFFFFFFFFFF
+  result += 8;
+#endif
+#if -16 >> 2 == -4 && (0u - 16) >> 60 == 15
+  result += 16;
+#endif
+#if (1 ? -1 : 0u) > 0
+  result += 32;
+#endif
+#if -7 % 2 == -1 && -7 / 2 == -3
+  result += 64;
+#endif
+  return result;
+}
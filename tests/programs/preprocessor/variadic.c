@@ -0,0 +1,15 @@
+#define SUM(first, ...) sum(first, __VA_ARGS__)
+#define CALL(f, ...) f(__VA_ARGS__)
+#define COUNT_ARGS(...) count(0, __VA_ARGS__)
+
+int sum(int a, int b, int c) {
+    return a + b + c;
+}
+
+int count(int a, int b) {
+    return a + b + 1;
+}
+
+int main() {
+    return SUM(1, 2, 3) + CALL(sum, 4, 5, 6) + COUNT_ARGS(9);
+}
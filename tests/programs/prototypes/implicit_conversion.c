@@ -0,0 +1,22 @@
+int first_byte(unsigned char c);
+long widen(long x);
+int narrow(char c);
+
+int main() {
+    int big = 300;
+    int negative = -2;
+    unsigned int u = 4294967295;
+    return first_byte(big) + (widen(negative) == -2) + narrow(258) + (widen(u) == 4294967295) * 10;
+}
+
+int first_byte(unsigned char c) {
+    return c;
+}
+
+long widen(long x) {
+    return x;
+}
+
+int narrow(char c) {
+    return c;
+}
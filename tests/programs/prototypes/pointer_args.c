@@ -0,0 +1,31 @@
+struct point {
+    int x;
+    int y;
+};
+
+void move(struct point *p, int dx, int dy);
+int total(int *arr, int len);
+
+int main() {
+    struct point p;
+    p.x = 1;
+    p.y = 2;
+    move(&p, 3, 4);
+
+    int arr[3] = {p.x, p.y, 5};
+    return total(arr, 3);
+}
+
+void move(struct point *p, int dx, int dy) {
+    p->x = p->x + dx;
+    p->y = p->y + dy;
+}
+
+int total(int arr[], int len) {
+    int sum = 0;
+    int i;
+    for (i = 0; i < len; i++) {
+        sum = sum + arr[i];
+    }
+    return sum;
+}
@@ -0,0 +1,9 @@
+int twice(int a) {
+    return 2 * a;
+}
+
+int twice(int a);
+
+int main() {
+    return twice(21);
+}
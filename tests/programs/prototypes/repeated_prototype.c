@@ -0,0 +1,17 @@
+int square(int x);
+int square(int);
+void *identity(void *p);
+
+int main() {
+    int x = 6;
+    int *p = identity(&x);
+    return square(*p);
+}
+
+int square(int x) {
+    return x * x;
+}
+
+void *identity(void *p) {
+    return p;
+}
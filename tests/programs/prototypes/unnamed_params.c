@@ -0,0 +1,19 @@
+int sum(int, char *, long);
+void fill(char *, int, char);
+
+int main() {
+    char buf[4];
+    fill(buf, 4, 3);
+    return sum(2, buf, 10);
+}
+
+int sum(int a, char *s, long b) {
+    return a + s[0] + s[3] + b;
+}
+
+void fill(char *p, int n, char c) {
+    int i;
+    for (i = 0; i < n; i++) {
+        p[i] = c;
+    }
+}
@@ -0,0 +1,36 @@
+int fib(int n) {
+  if (n < 2) {
+    return n;
+  }
+  int first = fib(n - 1);
+  int second = fib(n - 2);
+  return first + second;
+}
+
+void increment(int *p) { *p = *p + 1; }
+
+int sum_digits(long n) {
+  char count = 0;
+  short total = 0;
+  while (n > 0) {
+    total = total + n % 10;
+    n = n / 10;
+    count++;
+  }
+  return total * 10 + count;
+}
+
+int main() {
+  int counter = 0;
+  int kept = 5;
+  long big = 1234567890123;
+  for (int i = 0; i < 10; i++) {
+    increment(&counter);
+    kept = kept + fib(i);
+    putchar('a' + i);
+  }
+  putchar(10);
+  unsigned char small = 250;
+  small = small + counter;
+  return (kept + counter + sum_digits(big) + small) % 256;
+}
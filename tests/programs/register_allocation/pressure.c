@@ -0,0 +1,36 @@
+int mix(int a, int b, int c, int d, int e, int f, int g, int h) {
+  return a - b + c * d - e + f * g - h;
+}
+
+int main() {
+  int a = 1;
+  int b = 2;
+  int c = 3;
+  int d = 4;
+  int e = 5;
+  int f = 6;
+  int g = 7;
+  int h = 8;
+  int i = 9;
+  int j = 10;
+  int k = 11;
+  int l = 12;
+  long total = 0;
+  for (int n = 0; n < 20; n++) {
+    a = a + b;
+    b = b ^ c;
+    c = c + d * 2;
+    d = d - e;
+    e = e + f % 5;
+    f = f * 3 % 101;
+    g = g + h;
+    h = h - i;
+    i = i + j;
+    j = j + k / 2;
+    k = k * 2 % 97;
+    l = l + a % 7;
+    total = total + mix(a, b, c, d, e, f, g, h) % 1000;
+    total = total + i + j + k + l;
+  }
+  return (a + b + c + d + e + f + g + h + i + j + k + l + total) % 256;
+}
@@ -0,0 +1,22 @@
+// tcc flags: -O0
+int add(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    // without optimizations, the registers are allocated for the code as it was generated
+    int total = 0;
+    int a = 1;
+    int b = 2;
+    int c = 3;
+    for (int i = 0; i < 10; i++) {
+        int d = a * i + b;
+        int e = add(d, c);
+        if (e % 2 == 0) {
+            total += e;
+        } else {
+            total -= d;
+        }
+    }
+    return total + a + b + c;
+}
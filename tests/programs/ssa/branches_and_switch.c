@@ -0,0 +1,35 @@
+int classify(int n) {
+  int result = 3;
+  switch (n % 4) {
+  case 0:
+    result = 10;
+  case 1:
+    result = result + 1;
+    break;
+  case 2:
+    result = 20;
+    break;
+  default:
+    return n;
+  }
+  if (n > 6 && result > 15) {
+    result = result * 2;
+  } else if (n > 2) {
+    result = result - 1;
+  }
+  return result;
+}
+
+int main() {
+  int total = 0;
+  for (int i = 0; i < 12; i++) {
+    if (i == 5) {
+      continue;
+    }
+    total = total + classify(i);
+    if (total > 200) {
+      break;
+    }
+  }
+  return total;
+}
@@ -0,0 +1,26 @@
+void increment(int *p) {
+  *p = *p + 1;
+}
+
+int main() {
+  int count = 0;
+  int steps = 0;
+  int x = 3;
+  if (x > 2) {
+    goto middle;
+  }
+top:
+  steps = steps + 1;
+middle:
+  // count is changed through a pointer, so it is never renamed
+  increment(&count);
+  x = x * 2;
+  if (x < 100) {
+    goto top;
+  }
+  do {
+    x = x - 7;
+    steps++;
+  } while (x > 50);
+  return count * 10 + steps + x;
+}
@@ -0,0 +1,54 @@
+int identity(int x) { return x; }
+
+// nested loops, where the inner loop's values flow around both back edges
+int nested(int n) {
+  int total = 0;
+  for (int i = 0; i < n; i++) {
+    int row = 0;
+    for (int j = 0; j <= i; j++) {
+      row += j;
+    }
+    total = total * 3 + row;
+  }
+  return total;
+}
+
+// a loop whose header is jumped back to from two places, by the continue and the end of the body
+int two_back_edges(int n) {
+  int i = 0;
+  int evens = 0;
+  int odds = 0;
+  while (i < n) {
+    i++;
+    if (i % 2 == 0) {
+      evens += i;
+      continue;
+    }
+    odds += i;
+  }
+  return evens * 100 + odds;
+}
+
+// a loop entered in the middle of its body with a goto, which is not a natural loop
+int goto_into_loop(int n) {
+  int count = 0;
+  int i = n;
+  if (n > 3) {
+    goto middle;
+  }
+  while (i > 0) {
+    count += 10;
+  middle:
+    count++;
+    i--;
+  }
+  return count;
+}
+
+int main() {
+  int n = identity(5);
+  putchar('0' + nested(n) % 10);
+  putchar('0' + two_back_edges(n) % 10);
+  putchar('\n');
+  return nested(n) + two_back_edges(n) + goto_into_loop(n) + goto_into_loop(identity(2));
+}
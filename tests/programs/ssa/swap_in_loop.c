@@ -0,0 +1,14 @@
+int main() {
+  // the variables are swapped on every iteration, so their values at the top of the loop
+  // must be copied at the same time
+  int a = 1;
+  int b = 2;
+  int i = 0;
+  while (i < 5) {
+    int t = a;
+    a = b;
+    b = t;
+    i++;
+  }
+  return a * 10 + b;
+}
@@ -0,0 +1,9 @@
+int main() {
+    struct {
+        int a;
+        char b;
+    } s;
+    s.a = 5;
+    s.b = 7;
+    return s.a * s.b + sizeof(s);
+}
@@ -0,0 +1,23 @@
+struct pair {
+    char c;
+    int n;
+};
+
+int main() {
+    struct pair pairs[5];
+    int i;
+    for (i = 0; i < 5; i++) {
+        pairs[i].c = 'a' + i;
+        pairs[i].n = i * i;
+    }
+
+    struct pair *p = pairs;
+    int total = 0;
+    for (i = 0; i < 5; i++) {
+        putchar(p->c);
+        total += p->n;
+        p++;
+    }
+    putchar('\n');
+    return total;
+}
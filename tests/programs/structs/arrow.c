@@ -0,0 +1,17 @@
+struct point {
+    int x;
+    int y;
+};
+
+int sum(struct point *p) {
+    return p->x + p->y;
+}
+
+int main() {
+    struct point p;
+    struct point *ptr = &p;
+    ptr->x = 20;
+    ptr->y = 22;
+    (*ptr).x++;
+    return sum(ptr);
+}
@@ -0,0 +1,28 @@
+struct point {
+    char c;
+    int x;
+    int y;
+};
+
+struct line {
+    struct point a;
+    struct point b;
+};
+
+int main() {
+    struct point p = {'p', 3, 4};
+    struct point partial = {'q'};
+    struct line l = {{'a', 1, 2}, {'b', 5, 7}};
+    int n = 9;
+    struct point runtime = {'r', n, n * 2};
+    struct point pts[2] = {{'s', 1, 1}, {'t', 2, 3}};
+
+    putchar(p.c);
+    putchar(partial.c);
+    putchar(l.a.c);
+    putchar(l.b.c);
+    putchar(runtime.c);
+    putchar(pts[1].c);
+    putchar('\n');
+    return p.x + p.y + partial.x + partial.y + l.b.x * l.b.y + runtime.y + pts[1].y;
+}
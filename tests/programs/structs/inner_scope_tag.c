@@ -0,0 +1,42 @@
+struct s {
+  int a;
+  int b;
+};
+
+int sibling_blocks() {
+  int total = 0;
+  {
+    struct s {
+      char c;
+    };
+    struct s x;
+    x.c = 3;
+    total += sizeof(struct s) + x.c;
+  }
+  {
+    struct s {
+      long l[2];
+    };
+    total += sizeof(struct s);
+  }
+  return total;
+}
+
+int main() {
+  struct s outer;
+  outer.a = 1;
+  outer.b = 2;
+  {
+    struct s {
+      long big;
+      char tag;
+    };
+    struct s inner;
+    inner.big = 40;
+    inner.tag = 2;
+    outer.a += inner.big + inner.tag + sizeof(inner);
+  }
+  struct s after;
+  after.a = sizeof(after);
+  return outer.a + outer.b + after.a + sibling_blocks();
+}
@@ -0,0 +1,26 @@
+struct node {
+    int val;
+    struct node *next;
+};
+
+int sum_list(struct node *head, int len) {
+    int total = 0;
+    while (len--) {
+        total += head->val;
+        head = head->next;
+    }
+    return total;
+}
+
+int main() {
+    struct node a;
+    struct node b;
+    struct node c;
+    a.val = 1;
+    a.next = &b;
+    b.val = 10;
+    b.next = &c;
+    c.val = 100;
+    c.next = &a;
+    return sum_list(&a, 4) + a.next->next->val;
+}
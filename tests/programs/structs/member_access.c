@@ -0,0 +1,13 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    struct point p;
+    p.x = 3;
+    p.y = 4;
+    p.x += 10;
+    p.y++;
+    return p.x * p.y;
+}
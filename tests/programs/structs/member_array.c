@@ -0,0 +1,16 @@
+struct buffer {
+    int len;
+    char data[8];
+};
+
+int main() {
+    struct buffer b;
+    b.len = 0;
+    while (b.len < 7) {
+        b.data[b.len] = 'A' + b.len;
+        b.len++;
+    }
+    b.data[b.len] = 0;
+    puts(b.data);
+    return sizeof(b.data);
+}
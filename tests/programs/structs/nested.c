@@ -0,0 +1,22 @@
+struct point {
+    int x;
+    int y;
+};
+
+struct rect {
+    struct point top_left;
+    struct point bottom_right;
+};
+
+int area(struct rect *r) {
+    return (r->bottom_right.x - r->top_left.x) * (r->bottom_right.y - r->top_left.y);
+}
+
+int main() {
+    struct rect r;
+    r.top_left.x = 1;
+    r.top_left.y = 2;
+    r.bottom_right.x = 6;
+    r.bottom_right.y = 9;
+    return area(&r);
+}
@@ -0,0 +1,12 @@
+int main() {
+    struct counter {
+        int count;
+    };
+    struct counter c;
+    c.count = 0;
+    int i;
+    for (i = 0; i < 10; i++) {
+        c.count += i;
+    }
+    return c.count;
+}
@@ -0,0 +1,37 @@
+struct a {
+    char c;
+    int i;
+};
+
+struct b {
+    char c1;
+    long l;
+    char c2;
+};
+
+struct c {
+    char c1;
+    char c2;
+    short s;
+};
+
+struct d {
+    struct a inner;
+    char c;
+};
+
+struct e {
+    char chars[5];
+    int *p;
+};
+
+int main() {
+    putchar('0' + sizeof(struct a));
+    putchar('0' + sizeof(struct b) - 20);
+    putchar('0' + sizeof(struct c));
+    putchar('0' + sizeof(struct d));
+    putchar('0' + sizeof(struct e));
+    putchar('\n');
+    struct b var;
+    return sizeof(var) + sizeof(struct b *);
+}
@@ -0,0 +1,27 @@
+struct data {
+    char c;
+    long l;
+    short s;
+    char tail[3];
+};
+
+int main() {
+    struct data a;
+    a.c = 'x';
+    a.l = 1234567890123;
+    a.s = 300;
+    a.tail[0] = 'y';
+    a.tail[1] = 'z';
+    a.tail[2] = 0;
+
+    struct data b = a;
+    struct data c;
+    c = b;
+    a.c = 'q';
+
+    putchar(b.c);
+    putchar(c.c);
+    putchar(a.c);
+    puts(c.tail);
+    return (c.l == 1234567890123) + c.s - 290;
+}
@@ -0,0 +1,25 @@
+int is_vowel(char c) {
+    switch (c) {
+    case 'a':
+    case 'e':
+    case 'i':
+    case 'o':
+    case 'u':
+        return 1;
+    }
+    return 0;
+}
+
+int main() {
+    char *s = "the quick brown fox jumps over the lazy dog";
+    int vowels = 0;
+    while (*s) {
+        if (is_vowel(*s)) {
+            putchar(*s);
+            vowels++;
+        }
+        s++;
+    }
+    putchar('\n');
+    return vowels;
+}
@@ -0,0 +1,25 @@
+int f(int x) {
+    int result = 0;
+    switch (x) {
+    default:
+        result = 100;
+        break;
+    case 1:
+        result = 1;
+        break;
+    case 2:
+        result = 2;
+    }
+    return result;
+}
+
+int main() {
+    int only_default = 0;
+    switch (only_default) {
+    default:
+        only_default = 5;
+    }
+    switch (3) {
+    }
+    return f(1) + f(2) + f(3) + only_default;
+}
@@ -0,0 +1,26 @@
+int classify(int x) {
+    switch (x) {
+    case 0:
+        return 10;
+    case 1:
+        return 20;
+    case 2:
+        return 30;
+    case 4:
+        return 50;
+    case 5:
+        return 60;
+    }
+    return 99;
+}
+
+int main() {
+    int sum = 0;
+    int i;
+    for (i = -3; i < 9; i++) {
+        sum = sum + classify(i);
+        putchar('0' + classify(i) / 10);
+    }
+    putchar('\n');
+    return sum % 256;
+}
@@ -0,0 +1,29 @@
+int f(int x) {
+    switch (x + 1) {
+    case -3:
+        return 1;
+    case -2:
+        return 2;
+    case -1:
+        return 3;
+    case 0:
+        return 4;
+    case 1:
+        return 5;
+    case 3:
+        return 6;
+    }
+    return 7;
+}
+
+int main() {
+    int i;
+    int sum = 0;
+    for (i = -10; i < 10; i++) {
+        putchar('0' + f(i));
+        sum = sum * 3 + f(i);
+        sum = sum % 1000;
+    }
+    putchar('\n');
+    return sum % 256;
+}
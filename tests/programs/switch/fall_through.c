@@ -0,0 +1,21 @@
+int main() {
+    int i;
+    for (i = 0; i < 6; i++) {
+        switch (i) {
+        case 0:
+            putchar('a');
+        case 1:
+            putchar('b');
+        case 2:
+            putchar('c');
+            break;
+        case 3:
+        case 4:
+            putchar('d');
+        default:
+            putchar('e');
+        }
+        putchar('\n');
+    }
+    return 0;
+}
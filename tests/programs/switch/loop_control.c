@@ -0,0 +1,27 @@
+int main() {
+    int i = 0;
+    int count = 0;
+    while (1) {
+        switch (i % 4) {
+        case 0:
+            i++;
+            continue;
+        case 1:
+            count = count + 2;
+            break;
+        case 2:
+            count = count + 3;
+            break;
+        case 3:
+            if (i > 20) {
+                i = 1000;
+            }
+            break;
+        }
+        if (i == 1000) {
+            break;
+        }
+        i++;
+    }
+    return count;
+}
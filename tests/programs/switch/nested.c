@@ -0,0 +1,45 @@
+int main() {
+    int total = 0;
+    int i;
+    int j;
+    for (i = 0; i < 4; i++) {
+        for (j = 0; j < 4; j++) {
+            switch (i) {
+            case 0:
+                switch (j) {
+                case 0:
+                    total = total + 1;
+                    break;
+                case 1:
+                    total = total + 2;
+                    break;
+                default:
+                    total = total + 3;
+                }
+                break;
+            case 1:
+                if (j == 2) {
+                    continue;
+                }
+                total = total + 10;
+                break;
+            case 2: {
+                int k = 0;
+                while (k < j) {
+                    switch (k) {
+                    case 1:
+                        total = total + 100;
+                    }
+                    k++;
+                }
+                break;
+            }
+            default:
+                total = total + 1000;
+            }
+            putchar('0' + total % 7);
+        }
+    }
+    putchar('\n');
+    return total % 256;
+}
@@ -0,0 +1,23 @@
+int lookup(long x) {
+    switch (x) {
+    case 7:
+        return 1;
+    case 1000:
+        return 2;
+    case -50000:
+        return 3;
+    case 9000000000:
+        return 4;
+    }
+    return 0;
+}
+
+int main() {
+    putchar('0' + lookup(7));
+    putchar('0' + lookup(1000));
+    putchar('0' + lookup(-50000));
+    putchar('0' + lookup(9000000000));
+    putchar('0' + lookup(8));
+    putchar('\n');
+    return lookup(1000) * 10 + lookup(5);
+}
@@ -0,0 +1,43 @@
+int f(unsigned int x) {
+    switch (x) {
+    case 4294967295:
+        return 1;
+    case 0:
+        return 2;
+    case 1:
+        return 3;
+    case 2:
+        return 4;
+    case 3:
+        return 5;
+    }
+    return 6;
+}
+
+int g(unsigned char c) {
+    switch (c) {
+    case 250:
+        return 1;
+    case 251:
+        return 2;
+    case 252:
+        return 3;
+    case 253:
+        return 4;
+    }
+    return 5;
+}
+
+int main() {
+    unsigned int big = 4294967295;
+    unsigned char c = 251;
+    putchar('0' + f(big));
+    putchar('0' + f(0));
+    putchar('0' + f(3));
+    putchar('0' + f(100));
+    putchar('0' + g(c));
+    putchar('0' + g(c + 2));
+    putchar('0' + g(7));
+    putchar('\n');
+    return f(2) * 10 + g(250);
+}
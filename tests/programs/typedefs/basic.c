@@ -0,0 +1,22 @@
+typedef long word;
+typedef char *str;
+typedef unsigned char byte;
+
+word sum(word *values, int count) {
+    word total = 0;
+    for (int i = 0; i < count; i++) {
+        total += values[i];
+    }
+    return total;
+}
+
+int main() {
+    word values[3];
+    values[0] = 100000000000;
+    values[1] = -99999999990;
+    values[2] = 5;
+    str message = "typedef\n";
+    puts(message);
+    byte b = 300;
+    return sum(values, 3) + b + sizeof(word) + sizeof(str) + sizeof(byte);
+}
@@ -0,0 +1,14 @@
+typedef unsigned short u16;
+typedef double real;
+typedef int *int_ptr;
+
+int main() {
+    int x = 70000;
+    u16 truncated = (u16)x;
+    real half = (real)1 / 2;
+    int arr[2];
+    arr[0] = 1;
+    arr[1] = 2;
+    int_ptr p = (int_ptr)arr;
+    return truncated % 256 + (int)(half * 10) + p[1] + sizeof(u16) + sizeof(real);
+}
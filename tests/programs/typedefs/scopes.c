@@ -0,0 +1,25 @@
+typedef int T;
+
+int shadowed_by_variable() {
+    T x = 5;
+    int T = 3;
+    return x * T;
+}
+
+int local_typedef() {
+    typedef char T;
+    T c = 200;
+    {
+        typedef long T;
+        T l = 2;
+        c += sizeof(T) * l;
+    }
+    return c + sizeof(T);
+}
+
+int main() {
+    T result = shadowed_by_variable() + local_typedef();
+    typedef enum { SMALL = 1, LARGE = 10 } size;
+    size s = LARGE;
+    return result + s + sizeof(size) + sizeof(T);
+}
@@ -0,0 +1,46 @@
+typedef struct node node_t;
+
+struct node {
+    int value;
+    node_t *next;
+};
+
+typedef struct {
+    int x;
+    int y;
+} point;
+
+typedef point triangle[3];
+
+int perimeter_ish(triangle t) {
+    int total = 0;
+    for (int i = 0; i < 3; i++) {
+        total += t[i].x + t[i].y;
+    }
+    return total;
+}
+
+int main() {
+    node_t a;
+    node_t b;
+    a.value = 3;
+    a.next = &b;
+    b.value = 4;
+    b.next = &a;
+
+    triangle t;
+    t[0].x = 1;
+    t[0].y = 2;
+    t[1].x = 3;
+    t[1].y = 4;
+    t[2].x = 5;
+    t[2].y = 6;
+
+    int total = 0;
+    node_t *n = &a;
+    for (int i = 0; i < 5; i++) {
+        total += n->value;
+        n = n->next;
+    }
+    return total + perimeter_ish(t) + sizeof(triangle) + sizeof(node_t);
+}
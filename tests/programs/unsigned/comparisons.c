@@ -0,0 +1,33 @@
+int main() {
+    unsigned int u = 1;
+    int result = 0;
+
+    // -1 is converted to a very large unsigned int
+    if (u < -1) {
+        result = result + 1;
+    }
+
+    unsigned int big = 3000000000;
+    if (big > 5) {
+        result = result + 2;
+    }
+
+    int negative = -5;
+    unsigned long ul = 10;
+    if (negative > ul) {
+        result = result + 4;
+    }
+
+    // long can represent every unsigned int, so this comparison is signed
+    long l = -5;
+    if (l < u) {
+        result = result + 8;
+    }
+
+    unsigned char c = 200;
+    if (c > 100) {
+        result = result + 16;
+    }
+
+    return result;
+}
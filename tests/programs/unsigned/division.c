@@ -0,0 +1,12 @@
+int main() {
+    unsigned int big = 4000000000;
+    unsigned int q = big / 3;
+    unsigned int r = big % 7;
+
+    // too big to fit in a signed long
+    unsigned long huge = 9000000000000000000;
+    huge = huge * 2;
+    unsigned long q2 = huge / 1000000000000000000;
+
+    return q / 100000000 + r + q2;
+}
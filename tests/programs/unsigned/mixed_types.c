@@ -0,0 +1,20 @@
+int main() {
+    // long is bigger than unsigned int, so the result is a signed long
+    unsigned int u = 3;
+    long l = -10;
+    long result1 = u + l;
+
+    // unsigned long wins over long
+    unsigned long ul = 7;
+    long neg = -1;
+    int result2 = 0;
+    if (ul + neg == 6) {
+        result2 = 1;
+    }
+
+    unsigned int big = 4294967295;
+    unsigned long extended = big;
+    extended = extended + 1;
+
+    return (result1 == -7) + result2 * 2 + (extended == 4294967296) * 4 + sizeof(unsigned short);
+}
@@ -0,0 +1,19 @@
+int main() {
+    unsigned char c = 250;
+    c = c + 10;
+
+    unsigned int u = 0;
+    u = u - 1;
+
+    unsigned short s = 65535;
+    s++;
+
+    int result = c;
+    if (u == 4294967295) {
+        result = result + 100;
+    }
+    if (s == 0) {
+        result = result + 50;
+    }
+    return result;
+}
@@ -0,0 +1,19 @@
+unsigned int values[4] = {4000000000, 1, 2, 3};
+
+unsigned int max(unsigned int *arr, int len) {
+    unsigned int result = 0;
+    int i;
+    for (i = 0; i < len; i++) {
+        if (arr[i] > result) {
+            result = arr[i];
+        }
+    }
+    return result;
+}
+
+int main() {
+    unsigned char bytes[3] = {200, 100, 50};
+    unsigned char *p = bytes;
+    int total = *p + *(p + 1) + p[2];
+    return (max(values, 4) == 4000000000) + total - 300;
+}
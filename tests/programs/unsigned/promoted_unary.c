@@ -0,0 +1,29 @@
+int negate_uchar(unsigned char uc) { return -uc; }
+
+int complement_uchar(unsigned char uc) { return ~uc; }
+
+long negate_ushort(unsigned short us) {
+  long wide = -us;
+  return wide;
+}
+
+int main() {
+  unsigned char uc = 5;
+  unsigned short us = 7;
+  char c = -3;
+  short s = 300;
+  if (-uc != -5 || ~uc != -6 || -us != -7) {
+    return 1;
+  }
+  if (negate_uchar(5) != -5 || complement_uchar(5) != -6 || negate_ushort(7) != -7) {
+    return 2;
+  }
+  if (sizeof(-uc) != 4 || sizeof(~c) != 4 || sizeof(+s) != 4 || sizeof(!c) != 4) {
+    return 3;
+  }
+  if (+uc != 5 || +c != -3 || -+c != 3 || +-s != -300) {
+    return 4;
+  }
+  long total = -uc + ~us + -c + ~s + +us + !uc;
+  return total + 100;
+}
@@ -0,0 +1,11 @@
+int sum(signed a, signed char b, unsigned c) {
+    return a + b + c;
+}
+
+int main() {
+    signed int x = -7;
+    signed long y = 20;
+    signed char z = -3;
+    unsigned u = 5;
+    return sum(x, z, u) + y;
+}
@@ -0,0 +1,36 @@
+long quotient(int a, int b) {
+  long q = a / b;
+  return q;
+}
+
+long remainder(int a, int b) {
+  long r = a % b;
+  return r;
+}
+
+int identity(int x) { return x; }
+
+int main() {
+  int a = identity(-7);
+  int b = identity(2);
+  long q = quotient(a, b);
+  long r = remainder(a, b);
+  long product = a * b;
+  unsigned int big = identity(4000000000);
+  unsigned int two = identity(2);
+  long uq = big / two;
+  long ur = big % 7;
+  long total = 0;
+  for (int i = -5; i < 5; i++) {
+    long wide_q = i / 3;
+    long wide_r = i % 3;
+    total = total * 3 + wide_q + wide_r;
+  }
+  if (q != -3 || r != -1 || product != -14) {
+    return 1;
+  }
+  if (uq != 2000000000 || ur != 4000000000 % 7) {
+    return 2;
+  }
+  return (total + quotient(-100, 7) * 10 + remainder(-100, 7)) % 256;
+}
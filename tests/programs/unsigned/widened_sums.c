@@ -0,0 +1,17 @@
+unsigned int identity(unsigned int x) { return x; }
+int signed_identity(int x) { return x; }
+
+int main() {
+  unsigned int zero = identity(0);
+  unsigned int max = identity(4294967295);
+  int minus_two = signed_identity(-2);
+  int three = signed_identity(3);
+  // the unsigned int results wrap around before they are converted to long
+  long below = zero - 1;
+  long above = max + 2;
+  // the int results are sign extended
+  long negative = minus_two - three;
+  long sum = minus_two + 1;
+  int ok = (below == 4294967295) + 2 * (above == 1) + 4 * (negative == -5) + 8 * (sum == -1);
+  return ok;
+}
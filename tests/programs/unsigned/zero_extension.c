@@ -0,0 +1,14 @@
+int main() {
+    unsigned char c = 255;
+    int i = c;
+
+    unsigned short s = 65535;
+    long l = s;
+
+    unsigned int u = 4294967295;
+    long l2 = u;
+
+    char signed_c = c;
+
+    return (i == 255) + (l == 65535) * 2 + (l2 == 4294967295) * 4 + (signed_c == -1) * 8;
+}
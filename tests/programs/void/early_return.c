@@ -0,0 +1,14 @@
+void clamp(int *x, int max) {
+    if (*x <= max) {
+        return;
+    }
+    *x = max;
+}
+
+int main() {
+    int a = 5;
+    int b = 50;
+    clamp(&a, 10);
+    clamp(&b, 10);
+    return a + b;
+}
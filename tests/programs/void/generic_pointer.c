@@ -0,0 +1,35 @@
+struct node {
+    void *data;
+    int tag;
+};
+
+int sum_node(struct node *n) {
+    if (n->tag == 0) {
+        int *ip = n->data;
+        return *ip;
+    }
+    char *cp = n->data;
+    return cp[0] + cp[1];
+}
+
+void *identity(void *p) {
+    return p;
+}
+
+int main() {
+    int value = 40;
+    char chars[2] = {1, 2};
+    struct node a;
+    struct node b;
+    a.data = &value;
+    a.tag = 0;
+    b.data = chars;
+    b.tag = 1;
+
+    int *same = identity(&value);
+    int result = 0;
+    if (same == a.data) {
+        result = 1;
+    }
+    return sum_node(&a) + sum_node(&b) + result;
+}
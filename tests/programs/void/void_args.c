@@ -0,0 +1,18 @@
+int counter;
+
+void tick(void) {
+    counter++;
+}
+
+int get(void) {
+    return counter;
+}
+
+int main(void) {
+    int i;
+    for (i = 0; i < 4; tick()) {
+        i++;
+    }
+    tick();
+    return get();
+}
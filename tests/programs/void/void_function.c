@@ -0,0 +1,17 @@
+int total;
+
+void add(int x) {
+    total = total + x;
+}
+
+void add_twice(int x) {
+    add(x);
+    add(x);
+    return;
+}
+
+int main() {
+    add(3);
+    add_twice(5);
+    return total;
+}
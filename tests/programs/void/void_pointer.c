@@ -0,0 +1,24 @@
+void swap_bytes(void *a, void *b, int num_bytes) {
+    char *p = a;
+    char *q = b;
+    int i;
+    for (i = 0; i < num_bytes; i++) {
+        char tmp = p[i];
+        p[i] = q[i];
+        q[i] = tmp;
+    }
+}
+
+int main() {
+    int x = 7;
+    int y = 30;
+    swap_bytes(&x, &y, sizeof(int));
+
+    long l1 = 1;
+    long l2 = 2;
+    void *v = &l1;
+    long *lp = v;
+    swap_bytes(lp, &l2, sizeof(long));
+
+    return x * 2 + y + l1 * 100;
+}
@@ -0,0 +1,13 @@
+int main() {
+  int a = 0;
+  int b = 5;
+  int count = 0;
+  // warns, but assigns b to a and then tests it, just as gcc does
+  if (a = b) {
+    count = count + a;
+  }
+  while (a = a - 1) {
+    count = count + 1;
+  }
+  return (b = 0) ? 100 : count;
+}
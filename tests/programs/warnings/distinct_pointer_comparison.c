@@ -0,0 +1,10 @@
+int main() {
+  int arr[2];
+  char *c = (char *)arr;
+  int *i = arr;
+  // comparing an int pointer with a char pointer warns, but compares the addresses
+  if (c == i) {
+    return 1 + (c + 4 == i + 1);
+  }
+  return 0;
+}
@@ -0,0 +1,26 @@
+int loops_forever(int x) {
+  while (1) {
+    if (x > 10) {
+      return x;
+    }
+    x = x * 2;
+  }
+}
+
+int exits(int x) {
+  if (x) {
+    return x;
+  }
+  exit(3);
+}
+
+// warns, since the function returns nothing when x is not positive
+int sometimes_returns(int x) {
+  if (x > 0) {
+    return x;
+  }
+}
+
+int main() {
+  return loops_forever(3) + sometimes_returns(4) + exits(0);
+}
@@ -0,0 +1,12 @@
+int truncate(double d) {
+  return d;
+}
+
+int main() {
+  long big = 4294967298;
+  int small = big;
+  char c = 300;
+  float f = 2.75;
+  short s = c;
+  return small + c + truncate(f) + s;
+}
@@ -0,0 +1,13 @@
+// tcc flags: -Wparentheses -Werror
+int main() {
+  int x = 3;
+  int n = 0;
+  // the extra parentheses show that the assignments are intended, so they are not warned about
+  while ((x = x - 1)) {
+    n = n + 1;
+  }
+  if ((x = 4)) {
+    n = n + x;
+  }
+  return n;
+}
@@ -0,0 +1,10 @@
+int unused_param(int a, int b) {
+  int unused_local;
+  int used = a;
+  return used;
+}
+
+int main() {
+  int x = 3;
+  return unused_param(x, 4);
+}
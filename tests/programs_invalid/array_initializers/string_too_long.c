@@ -0,0 +1,4 @@
+int main() {
+    char s[3] = "hello";
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main() {
+    int a[][] = {{1, 2}, {3, 4}};
+    return 0;
+}
@@ -0,0 +1,6 @@
+int main() {
+    int a = 3;
+    int *p = &a;
+    int *q = p & 7;
+    return 0;
+}
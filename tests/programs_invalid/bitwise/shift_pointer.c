@@ -0,0 +1,6 @@
+int main() {
+    int a = 3;
+    int *p = &a;
+    p <<= 1;
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main() {
+    long x = 5;
+    (int)x = 3;
+    return x;
+}
@@ -0,0 +1,9 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    struct point p = {1, 2};
+    return (int)p;
+}
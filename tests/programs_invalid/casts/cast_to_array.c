@@ -0,0 +1,5 @@
+int main() {
+    int a[3] = {1, 2, 3};
+    int *p = a;
+    return ((int[3])p)[0];
+}
@@ -0,0 +1,10 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    int a = 1;
+    struct point p = (struct point)a;
+    return p.x;
+}
@@ -0,0 +1,6 @@
+void f() {
+}
+
+int main() {
+    return (int)f();
+}
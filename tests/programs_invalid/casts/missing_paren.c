@@ -0,0 +1,4 @@
+int main() {
+    long x = 5;
+    return (int x;
+}
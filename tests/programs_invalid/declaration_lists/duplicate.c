@@ -0,0 +1,4 @@
+int main() {
+    int a = 1, b = 2, a = 3;
+    return a + b;
+}
@@ -0,0 +1,5 @@
+int main() {
+    for (int i = 0, i = 1; i < 3; i++) {
+    }
+    return 0;
+}
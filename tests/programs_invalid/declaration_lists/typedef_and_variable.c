@@ -0,0 +1,8 @@
+typedef int number, count;
+
+int main() {
+    int number = 2;
+    count c = number;
+    number n = c;
+    return n;
+}
@@ -0,0 +1,4 @@
+int main() {
+    int a = b, b = 1;
+    return a;
+}
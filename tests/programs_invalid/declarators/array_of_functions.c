@@ -0,0 +1,4 @@
+int main() {
+    int table[3](int);
+    return 0;
+}
@@ -0,0 +1,5 @@
+typedef int (*bad)(int)[3];
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,5 @@
+int get(void)(int);
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,7 @@
+int main() {
+    int x = 3;
+    int *p = &x;
+    double d = 1.5;
+    p = d;
+    return *p;
+}
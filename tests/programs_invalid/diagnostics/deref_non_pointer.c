@@ -0,0 +1,4 @@
+int main() {
+    long n = 4;
+    return *n;
+}
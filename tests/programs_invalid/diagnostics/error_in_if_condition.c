@@ -0,0 +1,10 @@
+int main() {
+    int x = 3;
+    if (x + ) {
+        x = 1;
+    } else {
+        x = 2;
+    }
+    x = x + ;
+    return x;
+}
@@ -0,0 +1,7 @@
+int main() {
+    int x = 3;
+    if (x > 2) {
+        x = 1
+    }
+    return x
+}
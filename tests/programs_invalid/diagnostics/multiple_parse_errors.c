@@ -0,0 +1,27 @@
+struct point {
+    int x;
+    int y;
+};
+
+int f(int a) {
+    int b = a +;
+    if (a > 0 {
+        return 1;
+    } else {
+        b = 2;
+    }
+    return b
+}
+
+int g() {
+    return 3;
+}
+
+int main() {
+    int x = 1;
+    x = x * ;
+    for (int i = 0; i < 3 i++) {
+        x += i;
+    }
+    return g();
+}
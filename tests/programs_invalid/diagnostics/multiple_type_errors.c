@@ -0,0 +1,18 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    struct point p;
+    int *q = &p.x;
+    double d = 2.0;
+    int n = p + 1;
+    q = d;
+    n = *n;
+    switch (n) {
+        case 1: n = 2; break;
+        case 1: n = 3; break;
+    }
+    return n;
+}
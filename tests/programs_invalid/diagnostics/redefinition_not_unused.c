@@ -0,0 +1,6 @@
+int main() {
+    // only the redefinition is reported, without an unused variable warning for it
+    int a = 1;
+    int a = 2;
+    return a;
+}
@@ -0,0 +1,5 @@
+int main() {
+    int a = 1;
+	int b = a + (2 * c);
+    return b;
+}
@@ -0,0 +1,4 @@
+int main() {
+    int a = 3 @ 4;
+    return a;
+}
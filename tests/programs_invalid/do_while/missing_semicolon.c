@@ -0,0 +1,7 @@
+int main() {
+    int i = 0;
+    do {
+        i++;
+    } while (i < 10)
+    return i;
+}
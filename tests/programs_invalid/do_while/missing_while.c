@@ -0,0 +1,7 @@
+int main() {
+    int i = 0;
+    do {
+        i++;
+    } (i < 10);
+    return i;
+}
@@ -0,0 +1,8 @@
+void f() {
+}
+
+int main() {
+    do {
+    } while (f());
+    return 0;
+}
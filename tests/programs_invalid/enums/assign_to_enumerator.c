@@ -0,0 +1,6 @@
+enum { A, B };
+
+int main() {
+    A = 3;
+    return A;
+}
@@ -0,0 +1,12 @@
+enum { A = 2, B = 1 + 1 };
+
+int main() {
+    int x = 2;
+    switch (x) {
+    case A:
+        return 1;
+    case B:
+        return 2;
+    }
+    return 0;
+}
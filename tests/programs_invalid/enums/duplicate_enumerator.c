@@ -0,0 +1,6 @@
+enum color { RED, GREEN };
+enum fruit { APPLE, GREEN };
+
+int main() {
+    return APPLE;
+}
@@ -0,0 +1,5 @@
+enum empty {};
+
+int main() {
+    return 0;
+}
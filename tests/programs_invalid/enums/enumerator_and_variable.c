@@ -0,0 +1,5 @@
+int main() {
+    int RED = 3;
+    enum color { RED, GREEN };
+    return RED;
+}
@@ -0,0 +1,6 @@
+enum { SIZE = -2 };
+
+int main() {
+    int a[SIZE];
+    return 0;
+}
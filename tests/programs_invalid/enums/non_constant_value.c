@@ -0,0 +1,5 @@
+int main() {
+    int x = 3;
+    enum { A = x };
+    return A;
+}
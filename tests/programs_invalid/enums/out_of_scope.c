@@ -0,0 +1,6 @@
+int main() {
+    {
+        enum { A = 3 };
+    }
+    return A;
+}
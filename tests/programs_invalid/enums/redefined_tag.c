@@ -0,0 +1,6 @@
+enum color { RED };
+enum color { GREEN };
+
+int main() {
+    return 0;
+}
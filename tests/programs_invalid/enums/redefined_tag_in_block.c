@@ -0,0 +1,5 @@
+int main(void) {
+  enum color { RED };
+  enum color { GREEN };
+  return RED;
+}
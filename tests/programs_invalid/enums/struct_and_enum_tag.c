@@ -0,0 +1,6 @@
+struct point { int x; int y; };
+enum point { ORIGIN };
+
+int main(void) {
+  return ORIGIN;
+}
@@ -0,0 +1,7 @@
+int main(void) {
+  {
+    enum shape { CIRCLE, SQUARE };
+  }
+  enum shape s = 0;
+  return s;
+}
@@ -0,0 +1,4 @@
+int main() {
+    enum color c = 0;
+    return c;
+}
@@ -0,0 +1,6 @@
+enum color { RED, GREEN };
+int GREEN;
+
+int main() {
+    return RED;
+}
@@ -0,0 +1,4 @@
+int main() {
+    int a[3] = {1, 2, 3};
+    return a[1.0];
+}
@@ -0,0 +1,5 @@
+int main() {
+    float x = 1.0f;
+    int y = 3;
+    return y & x;
+}
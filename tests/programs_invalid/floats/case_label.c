@@ -0,0 +1,8 @@
+int main() {
+    int x = 1;
+    switch (x) {
+    case 1.5:
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main() {
+    int y = 0;
+    double x = (double)&y;
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main() {
+    double x = 1.0;
+    int *p = (int *)x;
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main() {
+    double x = 1.0;
+    return ~x;
+}
@@ -0,0 +1,5 @@
+int main() {
+    double x = 1.0;
+    x <<= 1;
+    return 0;
+}
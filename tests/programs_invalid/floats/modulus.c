@@ -0,0 +1,4 @@
+int main() {
+    double x = 5.5;
+    return x % 2;
+}
@@ -0,0 +1,4 @@
+int main() {
+    double x = 2.0;
+    return 1 << x;
+}
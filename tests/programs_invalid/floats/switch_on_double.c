@@ -0,0 +1,8 @@
+int main() {
+    double x = 1.0;
+    switch (x) {
+    case 1:
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main() {
+    unsigned double x = 1.0;
+    return 0;
+}
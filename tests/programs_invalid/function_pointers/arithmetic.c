@@ -0,0 +1,9 @@
+int f(int x) {
+    return x;
+}
+
+int main() {
+    int (*fp)(int) = f;
+    fp = fp + 1;
+    return 0;
+}
@@ -0,0 +1,9 @@
+int f(int x) {
+    return x;
+}
+
+int main() {
+    int (*fp)(int) = f;
+    fp++;
+    return 0;
+}
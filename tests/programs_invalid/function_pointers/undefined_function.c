@@ -0,0 +1,6 @@
+int f(int x);
+
+int main() {
+    int (*fp)(int) = f;
+    return 0;
+}
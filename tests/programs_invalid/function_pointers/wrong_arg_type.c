@@ -0,0 +1,9 @@
+int deref(int *p) {
+    return *p;
+}
+
+int main() {
+    int (*fp)(int *) = deref;
+    double d = 1.5;
+    return fp(d);
+}
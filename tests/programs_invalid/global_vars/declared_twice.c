@@ -0,0 +1,6 @@
+int a = 3;
+int a = 4;
+
+int main() {
+    return a;
+}
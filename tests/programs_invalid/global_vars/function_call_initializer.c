@@ -0,0 +1,9 @@
+int f() {
+    return 3;
+}
+
+int a = f();
+
+int main() {
+    return a;
+}
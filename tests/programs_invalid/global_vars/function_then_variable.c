@@ -0,0 +1,6 @@
+int f(void);
+int f;
+
+int main() {
+    return f;
+}
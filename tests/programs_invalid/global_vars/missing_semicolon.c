@@ -0,0 +1,5 @@
+int a = 3
+
+int main() {
+    return a;
+}
@@ -0,0 +1,7 @@
+int i = 1;
+int arr[4];
+int *p = &arr[i];
+
+int main() {
+  return 0;
+}
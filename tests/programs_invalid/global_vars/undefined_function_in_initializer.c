@@ -0,0 +1,6 @@
+int f(void);
+int g = h();
+
+int main() {
+    return g;
+}
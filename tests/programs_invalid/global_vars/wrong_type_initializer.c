@@ -0,0 +1,5 @@
+int a = "hello";
+
+int main() {
+    return a;
+}
@@ -0,0 +1,10 @@
+int main() {
+    int x = 0;
+again:
+    x++;
+    if (x < 3) {
+    again:
+        x++;
+    }
+    return x;
+}
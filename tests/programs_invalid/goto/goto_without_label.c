@@ -0,0 +1,4 @@
+int main() {
+    goto;
+    return 0;
+}
@@ -0,0 +1,8 @@
+int main() {
+    int x = 1;
+    if (x) {
+        goto end;
+    end:
+    }
+    return x;
+}
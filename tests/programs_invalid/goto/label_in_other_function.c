@@ -0,0 +1,9 @@
+int f() {
+target:
+    return 1;
+}
+
+int main() {
+    goto target;
+    return 0;
+}
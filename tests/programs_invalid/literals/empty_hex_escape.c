@@ -0,0 +1,4 @@
+int main() {
+    puts("\x");
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main() {
+    long a = 9223372036854775808L;
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main() {
+    puts("\q");
+    return 0;
+}
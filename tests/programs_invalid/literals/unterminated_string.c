@@ -0,0 +1,4 @@
+int main() {
+    puts("unterminated);
+    return 0;
+}
@@ -0,0 +1,7 @@
+void f() {
+}
+
+int main() {
+    int x = (1, f());
+    return x;
+}
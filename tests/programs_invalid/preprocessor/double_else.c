@@ -0,0 +1,7 @@
+#if 1
+int main() {
+    return 0;
+}
+#else
+#else
+#endif
@@ -0,0 +1,7 @@
+#ifndef REQUIRED
+#error REQUIRED must be defined
+#endif
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,6 @@
+#define VALUE 1
+#define VALUE 2
+
+int main() {
+    return VALUE;
+}
@@ -0,0 +1,5 @@
+#frobnicate
+
+int main() {
+    return 0;
+}
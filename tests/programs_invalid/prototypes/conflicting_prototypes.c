@@ -0,0 +1,10 @@
+int f(int a);
+int f(char *s);
+
+int f(int a) {
+    return a;
+}
+
+int main() {
+    return f(1);
+}
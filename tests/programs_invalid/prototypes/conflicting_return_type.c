@@ -0,0 +1,9 @@
+long f(int a);
+
+int f(int a) {
+    return a;
+}
+
+int main() {
+    return f(1);
+}
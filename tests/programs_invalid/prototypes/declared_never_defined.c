@@ -0,0 +1,5 @@
+int missing(int a);
+
+int main() {
+    return missing(3);
+}
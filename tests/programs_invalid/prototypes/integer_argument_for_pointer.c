@@ -0,0 +1,11 @@
+struct point {
+    int x;
+};
+
+int get_x(struct point *p) {
+    return p->x;
+}
+
+int main() {
+    return get_x(3);
+}
@@ -0,0 +1,9 @@
+int f();
+
+int f(int a) {
+    return a;
+}
+
+int main() {
+    return f(3);
+}
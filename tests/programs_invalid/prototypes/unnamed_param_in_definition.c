@@ -0,0 +1,7 @@
+int f(int) {
+    return 0;
+}
+
+int main() {
+    return f(1);
+}
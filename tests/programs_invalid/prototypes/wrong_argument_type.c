@@ -0,0 +1,10 @@
+int length(char *s);
+
+int main() {
+    int x = 3;
+    return length(x);
+}
+
+int length(char *s) {
+    return 0;
+}
@@ -0,0 +1,15 @@
+struct a {
+    int x;
+};
+
+struct b {
+    int x;
+};
+
+int main() {
+    struct a s1;
+    struct b s2;
+    s1.x = 3;
+    s2 = s1;
+    return 0;
+}
@@ -0,0 +1,6 @@
+struct point;
+
+int main() {
+    struct point p;
+    return 0;
+}
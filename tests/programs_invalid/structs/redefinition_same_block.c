@@ -0,0 +1,11 @@
+int main() {
+  {
+    struct point {
+      int x;
+    };
+    struct point {
+      int y;
+    };
+  }
+  return 0;
+}
@@ -0,0 +1,8 @@
+struct node {
+    int val;
+    struct node next;
+};
+
+int main() {
+    return 0;
+}
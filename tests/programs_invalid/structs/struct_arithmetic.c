@@ -0,0 +1,12 @@
+struct point {
+    int x;
+};
+
+int main() {
+    struct point p;
+    struct point q;
+    p.x = 1;
+    q.x = 2;
+    p + q;
+    return 0;
+}
@@ -0,0 +1,10 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    struct point p;
+    p.z = 3;
+    return 0;
+}
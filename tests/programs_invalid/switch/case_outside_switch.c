@@ -0,0 +1,8 @@
+int main() {
+    int x = 3;
+    if (x) {
+    case 3:
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,8 @@
+int main() {
+    int x = 3;
+    while (x) {
+    default:
+        x--;
+    }
+    return 0;
+}
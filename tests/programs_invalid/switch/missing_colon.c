@@ -0,0 +1,8 @@
+int main() {
+    int x = 3;
+    switch (x) {
+    case 3
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,12 @@
+int main() {
+    int x = 3;
+    switch (x) {
+    default:
+        x = 1;
+    case 2:
+        x = 2;
+    default:
+        x = 3;
+    }
+    return x;
+}
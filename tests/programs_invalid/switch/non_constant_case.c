@@ -0,0 +1,9 @@
+int main() {
+    int x = 3;
+    int y = 3;
+    switch (x) {
+    case y:
+        return 1;
+    }
+    return 0;
+}
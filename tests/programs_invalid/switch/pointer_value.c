@@ -0,0 +1,9 @@
+int main() {
+    int x = 3;
+    int *p = &x;
+    switch (p) {
+    case 0:
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,7 @@
+int main() {
+    {
+        typedef int number;
+    }
+    number n = 3;
+    return n;
+}
@@ -0,0 +1,6 @@
+typedef int T;
+
+int main() {
+    unsigned T x = 3;
+    return x;
+}
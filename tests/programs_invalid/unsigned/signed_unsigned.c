@@ -0,0 +1,4 @@
+int main() {
+    signed unsigned int x = 3;
+    return x;
+}
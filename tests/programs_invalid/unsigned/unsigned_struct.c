@@ -0,0 +1,9 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    unsigned struct point p;
+    return 0;
+}
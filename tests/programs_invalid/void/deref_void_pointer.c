@@ -0,0 +1,5 @@
+int main() {
+    int a = 3;
+    void *p = &a;
+    return *p;
+}
@@ -0,0 +1,8 @@
+void f() {
+    return 3;
+}
+
+int main() {
+    f();
+    return 0;
+}
@@ -0,0 +1,7 @@
+int f() {
+    return;
+}
+
+int main() {
+    return f();
+}
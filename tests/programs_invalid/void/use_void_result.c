@@ -0,0 +1,7 @@
+void nothing() {
+}
+
+int main() {
+    int x = nothing();
+    return x;
+}
@@ -0,0 +1,10 @@
+void f() {
+}
+
+int g(int x) {
+    return x;
+}
+
+int main() {
+    return g(f());
+}
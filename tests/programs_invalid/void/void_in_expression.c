@@ -0,0 +1,6 @@
+void nothing() {
+}
+
+int main() {
+    return 1 + nothing();
+}
@@ -0,0 +1,6 @@
+int main() {
+    int a[2] = {1, 2};
+    void *p = a;
+    p = p + 1;
+    return 0;
+}